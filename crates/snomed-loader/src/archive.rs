@@ -0,0 +1,171 @@
+//! Streaming access to RF2 files inside a release ZIP archive.
+//!
+//! Entries are addressed with virtual paths of the form
+//! `<archive>.zip/<entry name>`, which is what discovery stores in
+//! [`Rf2Files`](crate::Rf2Files). Each opened entry gets its own file handle
+//! positioned at the entry's compressed data, and is decompressed on the fly
+//! while it is read, so nothing is ever extracted to disk.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use flate2::read::DeflateDecoder;
+use zip::{CompressionMethod, ZipArchive};
+
+use crate::source::{is_zip_path, Rf2Reader};
+use crate::types::{Rf2Error, Rf2Result};
+
+/// Returns true if the path names a ZIP archive on disk.
+pub(crate) fn is_archive(path: &Path) -> bool {
+    path.is_file() && is_zip_path(path)
+}
+
+/// Splits a virtual path into the archive on disk and the entry name inside it.
+///
+/// Returns `None` if no ancestor of `path` is a ZIP archive, or if `path`
+/// is the archive itself.
+pub(crate) fn split_archive_path(path: &Path) -> Option<(PathBuf, String)> {
+    let archive = path
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| is_archive(ancestor))?;
+
+    let entry = path
+        .strip_prefix(archive)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    Some((archive.to_path_buf(), entry))
+}
+
+/// Lists the names of all file entries in an archive.
+pub(crate) fn entry_names(archive: &Path) -> Rf2Result<Vec<String>> {
+    let zip = open_archive(archive)?;
+    Ok(zip
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Opens a single archive entry for streaming decompression.
+pub(crate) fn open_entry(archive: &Path, entry: &str) -> Rf2Result<Rf2Reader> {
+    let mut zip = open_archive(archive)?;
+
+    let index = zip
+        .index_for_name(entry)
+        .ok_or_else(|| Rf2Error::FileNotFound {
+            path: archive.join(entry).display().to_string(),
+        })?;
+    let file = zip
+        .by_index_raw(index)
+        .map_err(|e| archive_error(archive, e))?;
+    let compression = file.compression();
+    let data_start = file.data_start();
+    let compressed_size = file.compressed_size();
+    drop(file);
+
+    let mut handle = File::open(archive)?;
+    handle.seek(SeekFrom::Start(data_start))?;
+    let data = BufReader::new(handle).take(compressed_size);
+
+    match compression {
+        CompressionMethod::Stored => Ok(Rf2Reader::new(data)),
+        CompressionMethod::Deflated => {
+            Ok(Rf2Reader::new(BufReader::new(DeflateDecoder::new(data))))
+        }
+        other => Err(Rf2Error::Archive {
+            path: archive.join(entry).display().to_string(),
+            message: format!("unsupported compression method {}", other),
+        }),
+    }
+}
+
+fn open_archive(archive: &Path) -> Rf2Result<ZipArchive<File>> {
+    let file = File::open(archive)?;
+    ZipArchive::new(file).map_err(|e| archive_error(archive, e))
+}
+
+fn archive_error(archive: &Path, error: zip::result::ZipError) -> Rf2Error {
+    Rf2Error::Archive {
+        path: archive.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, Write};
+    use zip::write::SimpleFileOptions;
+
+    fn write_archive(path: &Path, entries: &[(&str, &str, CompressionMethod)]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, contents, method) in entries {
+            let options = SimpleFileOptions::default().compression_method(*method);
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn read_entry(archive: &Path, entry: &str) -> String {
+        let mut reader = open_entry(archive, entry).unwrap();
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+        contents
+    }
+
+    #[test]
+    fn test_split_archive_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("release.ZIP");
+        write_archive(&archive, &[("a.txt", "a", CompressionMethod::Stored)]);
+
+        let (found, entry) =
+            split_archive_path(&archive.join("Release/Snapshot/Terminology/a.txt")).unwrap();
+        assert_eq!(found, archive);
+        assert_eq!(entry, "Release/Snapshot/Terminology/a.txt");
+
+        assert!(split_archive_path(&archive).is_none());
+        assert!(split_archive_path(&dir.path().join("a.txt")).is_none());
+    }
+
+    #[test]
+    fn test_open_entry_stored_and_deflated() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("release.zip");
+        let contents = "id\tactive\n".repeat(1000);
+        write_archive(
+            &archive,
+            &[
+                ("stored.txt", &contents, CompressionMethod::Stored),
+                ("deflated.txt", &contents, CompressionMethod::Deflated),
+            ],
+        );
+
+        assert_eq!(read_entry(&archive, "stored.txt"), contents);
+        assert_eq!(read_entry(&archive, "deflated.txt"), contents);
+
+        let lines = open_entry(&archive, "deflated.txt")
+            .unwrap()
+            .lines()
+            .count();
+        assert_eq!(lines, 1000);
+    }
+
+    #[test]
+    fn test_open_missing_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("release.zip");
+        write_archive(&archive, &[("a.txt", "a", CompressionMethod::Stored)]);
+
+        let result = open_entry(&archive, "b.txt");
+        assert!(matches!(result, Err(Rf2Error::FileNotFound { .. })));
+        assert_eq!(entry_names(&archive).unwrap(), vec!["a.txt"]);
+    }
+}
@@ -0,0 +1,319 @@
+//! Chunked parallel parsing with bounded memory.
+//!
+//! A reader thread cuts a file into blocks of whole lines and hands them to
+//! the parsers over a bounded channel. Blocks are parsed in parallel, one
+//! wave of blocks per rayon thread at a time, and the rows of each block are
+//! passed on in file order. However large the file, only the queued blocks
+//! and the current wave are held in memory.
+
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+use std::sync::mpsc::sync_channel;
+use std::time::Instant;
+
+use rayon::prelude::*;
+
+use crate::parser::Rf2Record;
+use crate::source::{open_rf2_file, Rf2Reader};
+use crate::tokenizer::Rf2Line;
+use crate::types::{ParseStats, Rf2Result, RowError};
+
+/// Block sizes of the chunked pipeline.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChunkConfig {
+    /// Target block size in bytes; a block always ends at a line end.
+    pub chunk_size: usize,
+    /// Number of blocks the reader may get ahead of the parsers.
+    pub queue_depth: usize,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 4 * 1024 * 1024,
+            queue_depth: 4,
+        }
+    }
+}
+
+/// Statistics and bad rows of a file parsed by [`parse_chunked`].
+pub(crate) struct ParsedFile {
+    pub stats: ParseStats,
+    pub errors: Vec<RowError>,
+}
+
+/// Parses a file block by block in parallel, passing rows to `sink` in file order.
+///
+/// `parse_line` returns `Ok(None)` for rows rejected by the filters. Rows
+/// that fail to parse are collected with their line number.
+pub(crate) fn parse_chunked<T, C>(
+    path: &Path,
+    config: &C,
+    parse_line: impl Fn(Rf2Line<'_>, &C) -> Rf2Result<Option<T>> + Sync,
+    chunks: ChunkConfig,
+    mut sink: impl FnMut(Vec<T>),
+) -> Rf2Result<ParsedFile>
+where
+    T: Rf2Record + Send,
+    C: Sync,
+{
+    let start = Instant::now();
+    let reader = ChunkReader::open(path, chunks.chunk_size)?;
+    let wave_size = rayon::current_num_threads();
+    let mut stats = ParseStats::default();
+    let mut errors = Vec::new();
+
+    std::thread::scope(|scope| -> Rf2Result<()> {
+        let (sender, receiver) = sync_channel(chunks.queue_depth);
+        scope.spawn(move || {
+            for chunk in reader {
+                // The receiver hangs up when parsing fails
+                if sender.send(chunk).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut receiver = receiver.into_iter();
+        loop {
+            let wave = receiver
+                .by_ref()
+                .take(wave_size)
+                .collect::<Rf2Result<Vec<Chunk>>>()?;
+            if wave.is_empty() {
+                return Ok(());
+            }
+
+            let parsed: Vec<ParsedChunk<T>> = wave
+                .par_iter()
+                .map(|chunk| parse_chunk(path, chunk, config, &parse_line))
+                .collect();
+
+            for chunk in parsed {
+                stats.total_records += chunk.records;
+                stats.filtered_records += chunk.rows.len();
+                errors.extend(chunk.errors);
+                sink(chunk.rows);
+            }
+        }
+    })?;
+
+    stats.error_count = errors.len();
+    stats.skipped_records = stats.total_records - stats.filtered_records - stats.error_count;
+    stats.parse_time_ms = start.elapsed().as_millis() as u64;
+    Ok(ParsedFile { stats, errors })
+}
+
+/// A block of whole lines.
+struct Chunk {
+    /// Line number of the first line in the file.
+    first_line: u64,
+    text: String,
+}
+
+/// Rows parsed from one block.
+struct ParsedChunk<T> {
+    rows: Vec<T>,
+    errors: Vec<RowError>,
+    /// Non-empty lines read.
+    records: usize,
+}
+
+fn parse_chunk<T: Rf2Record, C>(
+    path: &Path,
+    chunk: &Chunk,
+    config: &C,
+    parse_line: &impl Fn(Rf2Line<'_>, &C) -> Rf2Result<Option<T>>,
+) -> ParsedChunk<T> {
+    let mut parsed = ParsedChunk {
+        rows: Vec::new(),
+        errors: Vec::new(),
+        records: 0,
+    };
+
+    for (offset, raw) in chunk.text.split_inclusive('\n').enumerate() {
+        let line = Rf2Line::new(raw);
+        if line.is_blank() {
+            continue;
+        }
+        parsed.records += 1;
+        match parse_line(line, config) {
+            Ok(Some(row)) => parsed.rows.push(row),
+            Ok(None) => {}
+            Err(error) => {
+                let line = chunk.first_line + offset as u64;
+                parsed.errors.push(RowError::new(path, line, error));
+            }
+        }
+    }
+
+    parsed
+}
+
+/// Cuts an RF2 file into blocks of whole lines, skipping the header.
+struct ChunkReader {
+    reader: Rf2Reader,
+    chunk_size: usize,
+    /// Partial line left over from the previous block.
+    carry: Vec<u8>,
+    next_line: u64,
+    done: bool,
+}
+
+impl ChunkReader {
+    fn open(path: &Path, chunk_size: usize) -> Rf2Result<Self> {
+        let mut reader = open_rf2_file(path)?;
+        reader.read_until(b'\n', &mut Vec::new())?;
+
+        Ok(Self {
+            reader,
+            chunk_size: chunk_size.max(1),
+            carry: Vec::new(),
+            // Line 1 is the header
+            next_line: 2,
+            done: false,
+        })
+    }
+
+    fn next_chunk(&mut self) -> Rf2Result<Option<Chunk>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let mut data = std::mem::take(&mut self.carry);
+        let mut wanted = self.chunk_size.saturating_sub(data.len()).max(1);
+        loop {
+            let start = data.len();
+            let read = (&mut self.reader)
+                .take(wanted as u64)
+                .read_to_end(&mut data)?;
+            if read < wanted {
+                self.done = true;
+                break;
+            }
+            if let Some(end) = data[start..].iter().rposition(|&b| b == b'\n') {
+                self.carry = data.split_off(start + end + 1);
+                break;
+            }
+            // The block holds part of a single line; read on to its end
+            wanted = self.chunk_size;
+        }
+
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        let text =
+            String::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let first_line = self.next_line;
+        self.next_line += text.matches('\n').count() as u64;
+        Ok(Some(Chunk { first_line, text }))
+    }
+}
+
+impl Iterator for ChunkReader {
+    type Item = Rf2Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next_chunk();
+        if next.is_err() {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Fields;
+    use crate::types::Rf2Error;
+    use snomed_types::Rf2Concept;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const HEADER: &str = "id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n";
+
+    fn write_file(body: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}{}", HEADER, body).unwrap();
+        file
+    }
+
+    fn parse_id(line: Rf2Line<'_>, _: &()) -> Rf2Result<Option<Rf2Concept>> {
+        let mut fields = Fields::new(line, &["id"]);
+        Ok(Some(Rf2Concept {
+            id: fields.sctid()?,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }))
+    }
+
+    #[test]
+    fn test_chunks_end_on_line_boundaries() {
+        let body = "100\r\n200\n\n30000000000000000000000000000000000\n400";
+        let file = write_file(body);
+
+        let chunks: Vec<Chunk> = ChunkReader::open(file.path(), 8)
+            .unwrap()
+            .collect::<Rf2Result<_>>()
+            .unwrap();
+
+        // Every block but the last ends with a newline, and none splits a line
+        assert!(chunks.len() > 2);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.text.ends_with('\n'));
+        }
+        let joined: String = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(joined, body);
+
+        // A line longer than the block size is read whole
+        let last = chunks.last().unwrap();
+        assert_eq!(last.text, "30000000000000000000000000000000000\n400");
+        assert_eq!(last.first_line, 5);
+    }
+
+    #[test]
+    fn test_parse_chunked_keeps_file_order_and_line_numbers() {
+        let body: String = (1..=500)
+            .map(|i| {
+                if i == 321 {
+                    "bad\n".to_string()
+                } else {
+                    format!("{}\n", i)
+                }
+            })
+            .collect();
+        let file = write_file(&body);
+
+        let small = ChunkConfig {
+            chunk_size: 64,
+            queue_depth: 2,
+        };
+        let mut ids = Vec::new();
+        let parsed = parse_chunked(file.path(), &(), parse_id, small, |rows| {
+            ids.extend(rows.into_iter().map(|c| c.id))
+        })
+        .unwrap();
+
+        let expected: Vec<u64> = (1..=500).filter(|&i| i != 321).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(parsed.stats.total_records, 500);
+        assert_eq!(parsed.stats.filtered_records, 499);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].line, 322);
+        assert_eq!(parsed.errors[0].column, Some("id"));
+    }
+
+    #[test]
+    fn test_parse_chunked_reports_invalid_utf8() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(HEADER.as_bytes()).unwrap();
+        file.write_all(b"100\n\xff\xfe\n").unwrap();
+
+        let result = parse_chunked(file.path(), &(), parse_id, ChunkConfig::default(), |_| {});
+        assert!(matches!(result, Err(Rf2Error::Io(_))));
+    }
+}
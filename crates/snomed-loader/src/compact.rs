@@ -0,0 +1,828 @@
+//! Compact, read-only in-memory store.
+//!
+//! [`CompactStore`] holds the concepts, descriptions and relationships of a
+//! loaded [`SnomedStore`] in a fraction of its memory:
+//!
+//! - Concept ids, and every id a component refers to (modules, types,
+//!   destinations, ...), are numbered densely through one sorted id table.
+//!   Records hold these u32 node numbers instead of 64-bit ids.
+//! - Descriptions and relationships are fixed-size records in flat vectors,
+//!   grouped by concept with offset tables (one u32 per node, plus one past
+//!   the end) instead of a `HashMap` of `Vec`s.
+//! - Each relationship is stored once. Lookups by destination go through a
+//!   second offset table into a list of record numbers.
+//! - Terms live in one string arena, and language codes are interned.
+//!
+//! The store cannot be changed once built; apply deltas to the
+//! [`SnomedStore`] and build it again.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::mem::size_of;
+use std::ops::Range;
+
+use snomed_types::{Rf2Concept, Rf2Relationship, SctId};
+
+use crate::description::DescriptionView;
+use crate::store::SnomedStore;
+use crate::types::{Hierarchy, Rf2Result};
+
+/// A read-only SNOMED CT store with dense indices and interned strings.
+///
+/// Answers the same lookups as [`SnomedStore`] for concepts, descriptions
+/// and both relationship hierarchies. Concepts and relationships are
+/// returned by value; descriptions are returned as [`DescriptionView`]s
+/// borrowing their text from the store's string arena.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::{CompactStore, SnomedStore};
+///
+/// let mut store = SnomedStore::new();
+/// store.load_all(&files)?;
+/// let store = CompactStore::from_store(&store)?;
+///
+/// if let Some(fsn) = store.get_fsn(73211009) {
+///     println!("{}", fsn.term);
+/// }
+/// println!("{} bytes", store.estimated_memory_bytes());
+/// ```
+pub struct CompactStore {
+    /// Sorted ids; the position of an id is its node number.
+    ids: Vec<SctId>,
+    /// Concept record of each node.
+    nodes: Vec<Node>,
+    concept_count: usize,
+    /// Range of each node's descriptions, plus one past the end.
+    description_offsets: Vec<u32>,
+    descriptions: Vec<DescriptionRecord>,
+    /// Start of each description's term in `terms`, plus one past the end.
+    term_offsets: Vec<u32>,
+    terms: String,
+    /// Interned language codes.
+    languages: Vec<String>,
+    inferred: RelationshipIndex,
+    stated: RelationshipIndex,
+    /// Node of the IS_A attribute, if any component refers to it.
+    is_a: Option<u32>,
+}
+
+/// Node flag: the node is a concept of the store, not only referenced.
+const CONCEPT: u8 = 1;
+/// Node flag: the concept is active.
+const ACTIVE: u8 = 2;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    effective_time: u32,
+    module: u32,
+    definition_status: u32,
+    flags: u8,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DescriptionRecord {
+    id: SctId,
+    effective_time: u32,
+    module: u32,
+    type_node: u32,
+    case_significance: u32,
+    language: u16,
+    active: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RelationshipRecord {
+    id: SctId,
+    effective_time: u32,
+    module: u32,
+    source: u32,
+    destination: u32,
+    type_node: u32,
+    characteristic_type: u32,
+    modifier: u32,
+    group: u16,
+    active: bool,
+}
+
+/// Relationships of one hierarchy, stored once and indexed both ways.
+#[derive(Debug, Default)]
+struct RelationshipIndex {
+    /// Records grouped by source node.
+    records: Vec<RelationshipRecord>,
+    /// Range of each node's outgoing records, plus one past the end.
+    source_offsets: Vec<u32>,
+    /// Range of each node's incoming records in `by_destination`.
+    destination_offsets: Vec<u32>,
+    /// Record numbers grouped by destination node.
+    by_destination: Vec<u32>,
+}
+
+impl RelationshipIndex {
+    fn heap_bytes(&self) -> usize {
+        heap_bytes(&self.records)
+            + heap_bytes(&self.source_offsets)
+            + heap_bytes(&self.destination_offsets)
+            + heap_bytes(&self.by_destination)
+    }
+}
+
+impl CompactStore {
+    /// Builds a compact copy of a loaded store.
+    ///
+    /// Reference set indexes, MRCM and edition provenance are not copied.
+    ///
+    /// # Errors
+    /// Returns an I/O error of kind `InvalidData` if a table outgrows the
+    /// store's 32-bit indices.
+    pub fn from_store(store: &SnomedStore) -> Rf2Result<Self> {
+        let ids = node_ids(store);
+        let mut numbers = NodeNumbers::new(&ids)?;
+
+        let mut nodes = vec![Node::default(); ids.len()];
+        for concept in store.concepts() {
+            nodes[numbers.get(concept.id) as usize] = Node {
+                effective_time: concept.effective_time,
+                module: numbers.get(concept.module_id),
+                definition_status: numbers.get(concept.definition_status_id),
+                flags: CONCEPT | if concept.active { ACTIVE } else { 0 },
+            };
+        }
+
+        let mut compact = Self {
+            concept_count: store.concept_count(),
+            nodes,
+            description_offsets: Vec::with_capacity(ids.len() + 1),
+            descriptions: Vec::with_capacity(store.description_count()),
+            term_offsets: Vec::with_capacity(store.description_count() + 1),
+            terms: String::new(),
+            languages: Vec::new(),
+            inferred: RelationshipIndex::default(),
+            stated: RelationshipIndex::default(),
+            is_a: numbers.find(Rf2Relationship::IS_A_TYPE_ID),
+            ids,
+        };
+
+        for &id in &compact.ids {
+            compact
+                .description_offsets
+                .push(index(compact.descriptions.len())?);
+            for description in store.get_descriptions(id).into_iter().flatten() {
+                compact.term_offsets.push(index(compact.terms.len())?);
+                compact.terms.push_str(&description.term);
+                let language = intern(&mut compact.languages, &description.language_code)?;
+                compact.descriptions.push(DescriptionRecord {
+                    id: description.id,
+                    effective_time: description.effective_time,
+                    module: numbers.get(description.module_id),
+                    type_node: numbers.get(description.type_id),
+                    case_significance: numbers.get(description.case_significance_id),
+                    language,
+                    active: description.active,
+                });
+            }
+        }
+        compact
+            .description_offsets
+            .push(index(compact.descriptions.len())?);
+        compact.term_offsets.push(index(compact.terms.len())?);
+
+        compact.inferred = relationship_index(&compact.ids, &mut numbers, |id| {
+            store
+                .get_outgoing_relationships(id)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+        })?;
+        compact.stated = relationship_index(&compact.ids, &mut numbers, |id| {
+            store.get_stated_relationships(id)
+        })?;
+
+        compact.terms.shrink_to_fit();
+        compact.languages.shrink_to_fit();
+        Ok(compact)
+    }
+
+    // Query methods
+
+    /// Gets a concept by its ID.
+    pub fn get_concept(&self, id: SctId) -> Option<Rf2Concept> {
+        let node = self.concept_node(id)?;
+        Some(self.concept_at(node))
+    }
+
+    /// Returns true if a concept exists in the store.
+    pub fn has_concept(&self, id: SctId) -> bool {
+        self.concept_node(id).is_some()
+    }
+
+    /// Gets all descriptions for a concept.
+    pub fn get_descriptions(&self, concept_id: SctId) -> Vec<DescriptionView<'_>> {
+        self.descriptions_of(concept_id).collect()
+    }
+
+    /// Gets the text definitions of a concept.
+    pub fn get_text_definitions(&self, concept_id: SctId) -> Vec<DescriptionView<'_>> {
+        self.descriptions_of(concept_id)
+            .filter(|d| d.is_definition())
+            .collect()
+    }
+
+    /// Gets the FSN (Fully Specified Name) for a concept.
+    pub fn get_fsn(&self, concept_id: SctId) -> Option<DescriptionView<'_>> {
+        self.descriptions_of(concept_id).find(|d| d.is_fsn())
+    }
+
+    /// Gets relationships where this concept is the source.
+    pub fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        self.outgoing(Hierarchy::Inferred, source_id)
+            .map(|r| self.relationship(r))
+            .collect()
+    }
+
+    /// Gets relationships where this concept is the destination.
+    pub fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship> {
+        self.incoming(Hierarchy::Inferred, destination_id)
+            .map(|r| self.relationship(r))
+            .collect()
+    }
+
+    /// Gets stated relationships where this concept is the source.
+    pub fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        self.outgoing(Hierarchy::Stated, source_id)
+            .map(|r| self.relationship(r))
+            .collect()
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the inferred hierarchy.
+    pub fn get_parents(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_parents_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the inferred hierarchy.
+    pub fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_children_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the given hierarchy.
+    pub fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.outgoing(hierarchy, concept_id)
+            .filter(|r| Some(r.type_node) == self.is_a)
+            .map(|r| self.ids[r.destination as usize])
+            .collect()
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the given hierarchy.
+    pub fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.incoming(hierarchy, concept_id)
+            .filter(|r| Some(r.type_node) == self.is_a)
+            .map(|r| self.ids[r.source as usize])
+            .collect()
+    }
+
+    // Statistics
+
+    /// Returns the number of concepts in the store.
+    pub fn concept_count(&self) -> usize {
+        self.concept_count
+    }
+
+    /// Returns the number of descriptions in the store.
+    pub fn description_count(&self) -> usize {
+        self.descriptions.len()
+    }
+
+    /// Returns the number of relationships in the store.
+    pub fn relationship_count(&self) -> usize {
+        self.inferred.records.len()
+    }
+
+    /// Returns the number of stated relationships in the store.
+    pub fn stated_relationship_count(&self) -> usize {
+        self.stated.records.len()
+    }
+
+    /// Returns an iterator over all concepts, in id order.
+    pub fn concepts(&self) -> impl Iterator<Item = Rf2Concept> + '_ {
+        (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].flags & CONCEPT != 0)
+            .map(|i| self.concept_at(i))
+    }
+
+    /// Returns an iterator over all concept IDs, in order.
+    pub fn concept_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        self.ids
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(_, node)| node.flags & CONCEPT != 0)
+            .map(|(&id, _)| id)
+    }
+
+    /// Returns the memory held by the store in bytes.
+    ///
+    /// Unlike [`SnomedStore::estimated_memory_bytes`], this is exact up to
+    /// allocator overhead: every table is a flat vector of fixed-size rows.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        size_of::<Self>()
+            + heap_bytes(&self.ids)
+            + heap_bytes(&self.nodes)
+            + heap_bytes(&self.description_offsets)
+            + heap_bytes(&self.descriptions)
+            + heap_bytes(&self.term_offsets)
+            + self.terms.capacity()
+            + heap_bytes(&self.languages)
+            + self.languages.iter().map(String::capacity).sum::<usize>()
+            + self.inferred.heap_bytes()
+            + self.stated.heap_bytes()
+    }
+
+    fn node(&self, id: SctId) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    fn concept_node(&self, id: SctId) -> Option<usize> {
+        self.node(id)
+            .filter(|&node| self.nodes[node].flags & CONCEPT != 0)
+    }
+
+    fn concept_at(&self, node: usize) -> Rf2Concept {
+        let record = self.nodes[node];
+        Rf2Concept {
+            id: self.ids[node],
+            effective_time: record.effective_time,
+            active: record.flags & ACTIVE != 0,
+            module_id: self.ids[record.module as usize],
+            definition_status_id: self.ids[record.definition_status as usize],
+        }
+    }
+
+    fn descriptions_of(&self, concept_id: SctId) -> impl Iterator<Item = DescriptionView<'_>> {
+        let rows = self
+            .node(concept_id)
+            .map(|node| group(&self.description_offsets, node))
+            .unwrap_or_default();
+        rows.map(move |i| {
+            let record = &self.descriptions[i];
+            let term = self.term_offsets[i] as usize..self.term_offsets[i + 1] as usize;
+            DescriptionView {
+                id: record.id,
+                effective_time: record.effective_time,
+                active: record.active,
+                module_id: self.ids[record.module as usize],
+                concept_id,
+                language_code: &self.languages[record.language as usize],
+                type_id: self.ids[record.type_node as usize],
+                term: &self.terms[term],
+                case_significance_id: self.ids[record.case_significance as usize],
+            }
+        })
+    }
+
+    fn index(&self, hierarchy: Hierarchy) -> &RelationshipIndex {
+        match hierarchy {
+            Hierarchy::Inferred => &self.inferred,
+            Hierarchy::Stated => &self.stated,
+        }
+    }
+
+    fn outgoing(
+        &self,
+        hierarchy: Hierarchy,
+        source_id: SctId,
+    ) -> impl Iterator<Item = &RelationshipRecord> {
+        let index = self.index(hierarchy);
+        let rows = self
+            .node(source_id)
+            .map(|node| group(&index.source_offsets, node))
+            .unwrap_or_default();
+        index.records[rows].iter()
+    }
+
+    fn incoming(
+        &self,
+        hierarchy: Hierarchy,
+        destination_id: SctId,
+    ) -> impl Iterator<Item = &RelationshipRecord> {
+        let index = self.index(hierarchy);
+        let rows = self
+            .node(destination_id)
+            .map(|node| group(&index.destination_offsets, node))
+            .unwrap_or_default();
+        index.by_destination[rows]
+            .iter()
+            .map(|&row| &index.records[row as usize])
+    }
+
+    fn relationship(&self, record: &RelationshipRecord) -> Rf2Relationship {
+        Rf2Relationship {
+            id: record.id,
+            effective_time: record.effective_time,
+            active: record.active,
+            module_id: self.ids[record.module as usize],
+            source_id: self.ids[record.source as usize],
+            destination_id: self.ids[record.destination as usize],
+            relationship_group: record.group,
+            type_id: self.ids[record.type_node as usize],
+            characteristic_type_id: self.ids[record.characteristic_type as usize],
+            modifier_id: self.ids[record.modifier as usize],
+        }
+    }
+}
+
+impl std::fmt::Debug for CompactStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompactStore")
+            .field("concepts", &self.concept_count)
+            .field("descriptions", &self.descriptions.len())
+            .field("relationships", &self.inferred.records.len())
+            .field("stated_relationships", &self.stated.records.len())
+            .finish()
+    }
+}
+
+/// Collects the sorted ids of every concept and every id a component refers to.
+fn node_ids(store: &SnomedStore) -> Vec<SctId> {
+    let mut ids: Vec<SctId> = store.concept_ids().copied().collect();
+    ids.sort_unstable();
+
+    // Ids that are not concepts of the store
+    let mut referenced = HashSet::new();
+    let mut recent = [None; RECENT];
+    let mut note = |id: SctId| {
+        let slot = &mut recent[id as usize % RECENT];
+        if *slot != Some(id) {
+            *slot = Some(id);
+            if ids.binary_search(&id).is_err() {
+                referenced.insert(id);
+            }
+        }
+    };
+
+    for concept in store.concepts() {
+        note(concept.module_id);
+        note(concept.definition_status_id);
+    }
+    for d in store.descriptions() {
+        for id in [d.concept_id, d.module_id, d.type_id, d.case_significance_id] {
+            note(id);
+        }
+    }
+    for hierarchy in [Hierarchy::Inferred, Hierarchy::Stated] {
+        for r in store.relationships_in(hierarchy) {
+            for id in [
+                r.source_id,
+                r.destination_id,
+                r.module_id,
+                r.type_id,
+                r.characteristic_type_id,
+                r.modifier_id,
+            ] {
+                note(id);
+            }
+        }
+    }
+
+    ids.extend(referenced);
+    ids.sort_unstable();
+    ids
+}
+
+/// Number of recent lookups remembered while building.
+///
+/// Modules, types and other metadata ids repeat on almost every row, so
+/// most lookups are answered without hashing.
+const RECENT: usize = 64;
+
+/// Maps ids to node numbers while the store is built.
+struct NodeNumbers {
+    numbers: HashMap<SctId, u32>,
+    recent: [Option<(SctId, u32)>; RECENT],
+}
+
+impl NodeNumbers {
+    fn new(ids: &[SctId]) -> io::Result<Self> {
+        let numbers = ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| Ok((id, index(i)?)))
+            .collect::<io::Result<_>>()?;
+        Ok(Self {
+            numbers,
+            recent: [None; RECENT],
+        })
+    }
+
+    fn find(&self, id: SctId) -> Option<u32> {
+        self.numbers.get(&id).copied()
+    }
+
+    /// Returns the node of an id collected by [`node_ids`].
+    fn get(&mut self, id: SctId) -> u32 {
+        let slot = &mut self.recent[id as usize % RECENT];
+        match *slot {
+            Some((cached, node)) if cached == id => node,
+            _ => {
+                let node = self.numbers[&id];
+                *slot = Some((id, node));
+                node
+            }
+        }
+    }
+}
+
+/// Lays out the relationships of one hierarchy, `outgoing` giving each node's rows.
+fn relationship_index<'a>(
+    ids: &[SctId],
+    numbers: &mut NodeNumbers,
+    outgoing: impl Fn(SctId) -> &'a [Rf2Relationship],
+) -> io::Result<RelationshipIndex> {
+    let mut records = Vec::new();
+    let mut source_offsets = Vec::with_capacity(ids.len() + 1);
+    for (source, &id) in ids.iter().enumerate() {
+        source_offsets.push(index(records.len())?);
+        for r in outgoing(id) {
+            records.push(RelationshipRecord {
+                id: r.id,
+                effective_time: r.effective_time,
+                module: numbers.get(r.module_id),
+                source: source as u32,
+                destination: numbers.get(r.destination_id),
+                type_node: numbers.get(r.type_id),
+                characteristic_type: numbers.get(r.characteristic_type_id),
+                modifier: numbers.get(r.modifier_id),
+                group: r.relationship_group,
+                active: r.active,
+            });
+        }
+    }
+    source_offsets.push(index(records.len())?);
+    records.shrink_to_fit();
+
+    // Count the rows of each destination, then place record numbers in order
+    let mut destination_offsets = vec![0u32; ids.len() + 1];
+    for r in &records {
+        destination_offsets[r.destination as usize + 1] += 1;
+    }
+    for i in 1..destination_offsets.len() {
+        destination_offsets[i] += destination_offsets[i - 1];
+    }
+    let mut next = destination_offsets.clone();
+    let mut by_destination = vec![0; records.len()];
+    for (row, r) in records.iter().enumerate() {
+        let slot = &mut next[r.destination as usize];
+        by_destination[*slot as usize] = row as u32;
+        *slot += 1;
+    }
+
+    Ok(RelationshipIndex {
+        records,
+        source_offsets,
+        destination_offsets,
+        by_destination,
+    })
+}
+
+/// Row range of a node in an offset table.
+fn group(offsets: &[u32], node: usize) -> Range<usize> {
+    offsets[node] as usize..offsets[node + 1] as usize
+}
+
+/// Returns the number of a language code, adding it if new.
+fn intern(languages: &mut Vec<String>, code: &str) -> io::Result<u16> {
+    let position = match languages.iter().position(|l| l == code) {
+        Some(position) => position,
+        None => {
+            languages.push(code.to_string());
+            languages.len() - 1
+        }
+    };
+    u16::try_from(position).map_err(|_| too_large(position))
+}
+
+/// Converts a row, node or byte count to a 32-bit index.
+fn index(value: usize) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| too_large(value))
+}
+
+fn too_large(value: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} does not fit the compact store's indices", value),
+    )
+}
+
+/// Heap bytes held by a vector's buffer.
+fn heap_bytes<T>(rows: &Vec<T>) -> usize {
+    rows.capacity() * size_of::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_types::{well_known, CharacteristicType, DescriptionType, Rf2Description};
+
+    fn concept(id: SctId) -> Rf2Concept {
+        Rf2Concept {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }
+    }
+
+    fn description(id: SctId, concept_id: SctId, type_id: SctId, term: &str) -> Rf2Description {
+        Rf2Description {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            concept_id,
+            language_code: "en".to_string(),
+            type_id,
+            term: term.to_string(),
+            case_significance_id: 900000000000448009,
+        }
+    }
+
+    /// An inferred IS A relationship in group 0.
+    fn is_a(id: SctId, source_id: SctId, destination_id: SctId) -> Rf2Relationship {
+        Rf2Relationship {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            source_id,
+            destination_id,
+            relationship_group: 0,
+            type_id: Rf2Relationship::IS_A_TYPE_ID,
+            characteristic_type_id: CharacteristicType::INFERRED_ID,
+            modifier_id: 900000000000451002,
+        }
+    }
+
+    fn make_store() -> SnomedStore {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([
+            concept(300),
+            concept(100),
+            concept(200),
+            Rf2Concept {
+                active: false,
+                ..concept(400)
+            },
+        ]);
+        store.insert_descriptions([
+            description(1, 100, DescriptionType::FSN_ID, "Root (root)"),
+            Rf2Description {
+                active: false,
+                ..description(2, 200, DescriptionType::SYNONYM_ID, "Child")
+            },
+            description(3, 200, DescriptionType::FSN_ID, "Child (finding)"),
+            description(4, 200, DescriptionType::DEFINITION_ID, "A child concept"),
+            Rf2Description {
+                language_code: "sv".to_string(),
+                ..description(5, 300, DescriptionType::SYNONYM_ID, "Barn")
+            },
+            // A description of a concept the store does not hold
+            description(6, 500, DescriptionType::FSN_ID, "Orphan (finding)"),
+        ]);
+        store.insert_relationships([
+            is_a(11, 200, 100),
+            is_a(12, 300, 100),
+            Rf2Relationship {
+                relationship_group: 1,
+                type_id: well_known::FINDING_SITE,
+                ..is_a(13, 300, 200)
+            },
+            Rf2Relationship {
+                relationship_group: 2,
+                ..is_a(14, 300, 200)
+            },
+            Rf2Relationship {
+                characteristic_type_id: CharacteristicType::STATED_ID,
+                ..is_a(20, 300, 100)
+            },
+        ]);
+        store
+    }
+
+    #[test]
+    fn test_compact_store_answers_like_snomed_store() {
+        let store = make_store();
+        let compact = CompactStore::from_store(&store).unwrap();
+
+        assert_eq!(compact.concept_count(), store.concept_count());
+        assert_eq!(compact.description_count(), store.description_count());
+        assert_eq!(compact.relationship_count(), store.relationship_count());
+        assert_eq!(
+            compact.stated_relationship_count(),
+            store.stated_relationship_count()
+        );
+        assert_eq!(
+            compact.concept_ids().collect::<Vec<_>>(),
+            vec![100, 200, 300, 400]
+        );
+        assert_eq!(compact.concepts().count(), 4);
+
+        for id in [100, 200, 300, 400, 500, 600] {
+            assert_eq!(compact.get_concept(id).as_ref(), store.get_concept(id));
+            assert_eq!(compact.has_concept(id), store.has_concept(id));
+
+            let descriptions: Vec<Rf2Description> = compact
+                .get_descriptions(id)
+                .iter()
+                .map(DescriptionView::to_description)
+                .collect();
+            assert_eq!(
+                descriptions,
+                store.get_descriptions(id).cloned().unwrap_or_default()
+            );
+            assert_eq!(
+                compact.get_fsn(id).map(|d| d.to_description()).as_ref(),
+                store.get_fsn(id)
+            );
+            assert_eq!(
+                compact.get_text_definitions(id).len(),
+                store.get_text_definitions(id).len()
+            );
+
+            assert_eq!(
+                compact.get_outgoing_relationships(id),
+                store
+                    .get_outgoing_relationships(id)
+                    .cloned()
+                    .unwrap_or_default()
+            );
+            assert_eq!(
+                compact.get_incoming_relationships(id),
+                store
+                    .get_incoming_relationships(id)
+                    .cloned()
+                    .unwrap_or_default()
+            );
+            assert_eq!(
+                compact.get_stated_relationships(id),
+                store.get_stated_relationships(id)
+            );
+            for hierarchy in [Hierarchy::Inferred, Hierarchy::Stated] {
+                assert_eq!(
+                    compact.get_parents_in(id, hierarchy),
+                    store.get_parents_in(id, hierarchy)
+                );
+                assert_eq!(
+                    compact.get_children_in(id, hierarchy),
+                    store.get_children_in(id, hierarchy)
+                );
+            }
+        }
+
+        assert_eq!(compact.get_parents(300), vec![100, 200]);
+        assert_eq!(compact.get_children(100), vec![200, 300]);
+        assert_eq!(compact.get_parents_in(300, Hierarchy::Stated), vec![100]);
+        assert_eq!(compact.get_fsn(500).unwrap().term, "Orphan (finding)");
+        assert_eq!(compact.get_descriptions(300)[0].language_code, "sv");
+        assert_eq!(compact.languages, vec!["en", "sv"]);
+    }
+
+    #[test]
+    fn test_compact_store_of_empty_store() {
+        let compact = CompactStore::from_store(&SnomedStore::new()).unwrap();
+
+        assert_eq!(compact.concept_count(), 0);
+        assert!(compact.get_concept(100).is_none());
+        assert!(compact.get_descriptions(100).is_empty());
+        assert!(compact.get_children(100).is_empty());
+    }
+
+    #[test]
+    fn test_compact_store_uses_less_memory() {
+        let mut store = SnomedStore::new();
+        store.insert_concepts((1..=1000).map(concept));
+        store.insert_descriptions((1..=1000).flat_map(|id| {
+            [
+                description(id * 10, id, DescriptionType::FSN_ID, "Concept (finding)"),
+                description(id * 10 + 1, id, DescriptionType::SYNONYM_ID, "Concept"),
+            ]
+        }));
+        store.insert_relationships((2..=1000).flat_map(|id| {
+            [
+                is_a(id * 10, id, id / 2),
+                Rf2Relationship {
+                    characteristic_type_id: CharacteristicType::STATED_ID,
+                    ..is_a(id * 10 + 1, id, 1)
+                },
+            ]
+        }));
+
+        let compact = CompactStore::from_store(&store).unwrap();
+        assert_eq!(compact.relationship_count(), 999);
+        assert_eq!(compact.stated_relationship_count(), 999);
+        assert!(
+            compact.estimated_memory_bytes() * 2 < store.estimated_memory_bytes(),
+            "{} vs {}",
+            compact.estimated_memory_bytes(),
+            store.estimated_memory_bytes()
+        );
+    }
+}
@@ -3,9 +3,9 @@
 //! Parses sct2_Concept_*.txt RF2 files.
 
 use csv::StringRecord;
-use snomed_types::Rf2Concept;
+use snomed_types::{Rf2Concept, SctId};
 
-use crate::parser::{parse, Rf2Record};
+use crate::parser::{field, parse, Rf2Component, Rf2Record};
 use crate::types::{Rf2Config, Rf2Result};
 
 /// Expected columns in a concept file.
@@ -22,11 +22,11 @@ impl Rf2Record for Rf2Concept {
 
     fn from_record(record: &StringRecord) -> Rf2Result<Self> {
         Ok(Rf2Concept {
-            id: parse::sctid(record.get(0).unwrap_or(""))?,
-            effective_time: parse::effective_time(record.get(1).unwrap_or(""))?,
-            active: parse::boolean(record.get(2).unwrap_or(""))?,
-            module_id: parse::sctid(record.get(3).unwrap_or(""))?,
-            definition_status_id: parse::sctid(record.get(4).unwrap_or(""))?,
+            id: field(record, 0, "id", parse::sctid)?,
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            definition_status_id: field(record, 4, "definitionStatusId", parse::sctid)?,
         })
     }
 
@@ -38,6 +38,16 @@ impl Rf2Record for Rf2Concept {
     }
 }
 
+impl Rf2Component for Rf2Concept {
+    fn component_id(&self) -> SctId {
+        self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
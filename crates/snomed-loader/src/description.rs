@@ -3,9 +3,9 @@
 //! Parses sct2_Description_*.txt RF2 files.
 
 use csv::StringRecord;
-use snomed_types::Rf2Description;
+use snomed_types::{DescriptionType, Rf2Description, SctId};
 
-use crate::parser::{parse, Rf2Record};
+use crate::parser::{field, parse, Rf2Component, Rf2Record};
 use crate::types::{DescriptionConfig, Rf2Config, Rf2Result};
 
 /// Expected columns in a description file.
@@ -26,15 +26,15 @@ impl Rf2Record for Rf2Description {
 
     fn from_record(record: &StringRecord) -> Rf2Result<Self> {
         Ok(Rf2Description {
-            id: parse::sctid(record.get(0).unwrap_or(""))?,
-            effective_time: parse::effective_time(record.get(1).unwrap_or(""))?,
-            active: parse::boolean(record.get(2).unwrap_or(""))?,
-            module_id: parse::sctid(record.get(3).unwrap_or(""))?,
-            concept_id: parse::sctid(record.get(4).unwrap_or(""))?,
-            language_code: record.get(5).unwrap_or("").to_string(),
-            type_id: parse::sctid(record.get(6).unwrap_or(""))?,
-            term: record.get(7).unwrap_or("").to_string(),
-            case_significance_id: parse::sctid(record.get(8).unwrap_or(""))?,
+            id: field(record, 0, "id", parse::sctid)?,
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            concept_id: field(record, 4, "conceptId", parse::sctid)?,
+            language_code: field(record, 5, "languageCode", Ok)?.to_string(),
+            type_id: field(record, 6, "typeId", parse::sctid)?,
+            term: field(record, 7, "term", Ok)?.to_string(),
+            case_significance_id: field(record, 8, "caseSignificanceId", parse::sctid)?,
         })
     }
 
@@ -46,6 +46,16 @@ impl Rf2Record for Rf2Description {
     }
 }
 
+impl Rf2Component for Rf2Description {
+    fn component_id(&self) -> SctId {
+        self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
 /// Extended filter for descriptions with language and type filtering.
 pub trait DescriptionFilter {
     /// Returns true if the description passes the extended filter.
@@ -75,6 +85,85 @@ impl DescriptionFilter for Rf2Description {
     }
 }
 
+/// A description whose language code and term are borrowed, e.g. from a
+/// mapped file or a store's string arena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptionView<'a> {
+    /// Description SCTID.
+    pub id: SctId,
+    /// Effective time (YYYYMMDD).
+    pub effective_time: u32,
+    /// Whether the description is active.
+    pub active: bool,
+    /// Module SCTID.
+    pub module_id: SctId,
+    /// The concept this description belongs to.
+    pub concept_id: SctId,
+    /// Language code (e.g. "en").
+    pub language_code: &'a str,
+    /// Description type (FSN, synonym or definition).
+    pub type_id: SctId,
+    /// The description text.
+    pub term: &'a str,
+    /// Case significance SCTID.
+    pub case_significance_id: SctId,
+}
+
+impl DescriptionView<'_> {
+    /// Copies the view into an owned description.
+    pub fn to_description(&self) -> Rf2Description {
+        Rf2Description {
+            id: self.id,
+            effective_time: self.effective_time,
+            active: self.active,
+            module_id: self.module_id,
+            concept_id: self.concept_id,
+            language_code: self.language_code.to_string(),
+            type_id: self.type_id,
+            term: self.term.to_string(),
+            case_significance_id: self.case_significance_id,
+        }
+    }
+
+    /// Returns true if this is a Fully Specified Name.
+    pub fn is_fsn(&self) -> bool {
+        self.type_id == DescriptionType::FSN_ID
+    }
+
+    /// Returns true if this is a synonym.
+    pub fn is_synonym(&self) -> bool {
+        self.type_id == DescriptionType::SYNONYM_ID
+    }
+
+    /// Returns true if this is a text definition.
+    pub fn is_definition(&self) -> bool {
+        self.type_id == DescriptionType::DEFINITION_ID
+    }
+}
+
+impl DescriptionFilter for DescriptionView<'_> {
+    fn passes_description_filter(&self, config: &DescriptionConfig) -> bool {
+        if config.base.active_only && !self.active {
+            return false;
+        }
+
+        if !config.language_codes.is_empty()
+            && !config
+                .language_codes
+                .iter()
+                .any(|code| code == self.language_code)
+        {
+            return false;
+        }
+
+        if !config.type_ids.is_empty() && !config.type_ids.contains(&self.type_id) {
+            return false;
+        }
+
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,141 @@
+//! Provenance of components loaded from multiple release packages.
+//!
+//! A store can be built from the International release plus any number of
+//! national or extension editions with
+//! [`SnomedStore::load_edition`](crate::SnomedStore::load_edition). Every
+//! component remembers the edition its current version came from, and an
+//! [`EditionView`] restricts queries to a chosen set of editions.
+
+use std::collections::BTreeSet;
+
+use snomed_types::{Rf2Concept, Rf2Description, SctId};
+
+use crate::store::SnomedStore;
+use crate::types::DeltaStats;
+
+/// Index of an edition in the order it was loaded into a store.
+pub type EditionId = u16;
+
+/// A release package loaded into a [`SnomedStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edition {
+    /// Position in load order.
+    pub id: EditionId,
+    /// Name given when loading (e.g. "INT", "UK").
+    pub name: String,
+    /// Release date taken from the package's file names (YYYYMMDD).
+    pub release_date: Option<String>,
+    /// Module ids seen in the package's rows.
+    pub module_ids: BTreeSet<SctId>,
+    /// Outcome of merging the package into the store.
+    pub stats: DeltaStats,
+}
+
+impl Edition {
+    /// Returns true if the package contains rows from the given module.
+    pub fn has_module(&self, module_id: SctId) -> bool {
+        self.module_ids.contains(&module_id)
+    }
+}
+
+/// A read-only view of a store restricted to components from some editions.
+///
+/// Obtained from [`SnomedStore::edition_view`](crate::SnomedStore::edition_view).
+///
+/// # Example
+///
+/// ```ignore
+/// let options = LoadOptions::default();
+/// let mut store = SnomedStore::new();
+/// store.load_edition("INT", &discover_rf2_files("/releases/int")?, &options)?;
+/// store.load_edition("UK", &discover_rf2_files("/releases/uk")?, &options)?;
+///
+/// // Only International content, ignoring the UK extension
+/// let international = store.edition_view(&["INT"]).unwrap();
+/// assert!(international.get_concept(uk_only_concept).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditionView<'a> {
+    store: &'a SnomedStore,
+    editions: Vec<EditionId>,
+}
+
+impl<'a> EditionView<'a> {
+    pub(crate) fn new(store: &'a SnomedStore, editions: Vec<EditionId>) -> Self {
+        Self { store, editions }
+    }
+
+    /// Returns the editions included in this view.
+    pub fn editions(&self) -> &[EditionId] {
+        &self.editions
+    }
+
+    /// Returns true if the component's current version came from an included edition.
+    pub fn contains(&self, component_id: SctId) -> bool {
+        self.store
+            .component_edition_id(component_id)
+            .is_some_and(|id| self.editions.contains(&id))
+    }
+
+    /// Gets a concept by its ID.
+    pub fn get_concept(&self, id: SctId) -> Option<&'a Rf2Concept> {
+        self.store.get_concept(id).filter(|c| self.contains(c.id))
+    }
+
+    /// Returns true if the concept exists in this view.
+    pub fn has_concept(&self, id: SctId) -> bool {
+        self.get_concept(id).is_some()
+    }
+
+    /// Gets the descriptions of a concept that belong to this view.
+    pub fn get_descriptions(&self, concept_id: SctId) -> Vec<&'a Rf2Description> {
+        self.store
+            .get_descriptions(concept_id)
+            .map(|descs| descs.iter().filter(|d| self.contains(d.id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the FSN (Fully Specified Name) for a concept.
+    pub fn get_fsn(&self, concept_id: SctId) -> Option<&'a Rf2Description> {
+        self.get_descriptions(concept_id)
+            .into_iter()
+            .find(|d| d.is_fsn())
+    }
+
+    /// Gets parent concepts via IS_A relationships in this view.
+    pub fn get_parents(&self, concept_id: SctId) -> Vec<SctId> {
+        self.store
+            .get_outgoing_relationships(concept_id)
+            .map(|rels| {
+                rels.iter()
+                    .filter(|r| r.is_is_a() && self.contains(r.id))
+                    .map(|r| r.destination_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets child concepts via IS_A relationships in this view.
+    pub fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
+        self.store
+            .get_incoming_relationships(concept_id)
+            .map(|rels| {
+                rels.iter()
+                    .filter(|r| r.is_is_a() && self.contains(r.id))
+                    .map(|r| r.source_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the concepts in this view.
+    pub fn concepts(&self) -> impl Iterator<Item = &'a Rf2Concept> + '_ {
+        self.store.concepts().filter(|c| self.contains(c.id))
+    }
+
+    /// Returns the number of concepts in this view.
+    pub fn concept_count(&self) -> usize {
+        self.concepts().count()
+    }
+}
@@ -0,0 +1,598 @@
+//! Per-component version history built from RF2 Full releases.
+//!
+//! Unlike [`SnomedStore`](crate::SnomedStore), which holds one version of each
+//! component, [`HistoryStore`] keeps every row of the Full concept, description
+//! and relationship files so that changes can be replayed as a timeline.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use snomed_loader::{discover_rf2_full_files, HistoryStore};
+//!
+//! let files = discover_rf2_full_files("/path/to/snomed/release")?;
+//! let history = HistoryStore::load(&files)?;
+//!
+//! for change in history.concept_timeline(73211009) {
+//!     println!("{}: {:?}", change.effective_time, change.kind);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use snomed_types::{Rf2Concept, Rf2Description, Rf2Relationship, SctId};
+
+use crate::parser::{Rf2Component, Rf2Parser};
+use crate::types::{Rf2Config, Rf2Files, Rf2Result};
+
+/// A single change to a concept or one of its descriptions or relationships.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConceptChange {
+    /// Effective time (YYYYMMDD) of the row that introduced the change.
+    pub effective_time: u32,
+    /// The component that changed (concept, description or relationship id).
+    pub component_id: SctId,
+    /// What changed.
+    pub kind: ChangeKind,
+}
+
+/// The kind of change recorded in a [`ConceptChange`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// First release of the concept.
+    ConceptCreated,
+    /// The concept became active again.
+    ConceptActivated,
+    /// The concept was inactivated.
+    ConceptInactivated,
+    /// The definition status flipped between primitive and fully defined.
+    DefinitionStatusChanged {
+        /// Previous definition status id.
+        from: SctId,
+        /// New definition status id.
+        to: SctId,
+    },
+    /// The concept moved to another module.
+    ModuleChanged {
+        /// Previous module id.
+        from: SctId,
+        /// New module id.
+        to: SctId,
+    },
+    /// A description was added (or reactivated).
+    DescriptionAdded {
+        /// Description type id.
+        type_id: SctId,
+        /// The description term.
+        term: String,
+    },
+    /// A description was inactivated.
+    DescriptionInactivated {
+        /// Description type id.
+        type_id: SctId,
+        /// The description term.
+        term: String,
+    },
+    /// The active Fully Specified Name changed.
+    FsnChanged {
+        /// Previous FSN.
+        from: String,
+        /// New FSN.
+        to: String,
+    },
+    /// An IS_A relationship to a parent was added (or reactivated).
+    ParentAdded {
+        /// The parent concept id.
+        parent_id: SctId,
+    },
+    /// An IS_A relationship to a parent was inactivated.
+    ParentRemoved {
+        /// The parent concept id.
+        parent_id: SctId,
+    },
+    /// A defining attribute relationship was added (or reactivated).
+    AttributeAdded {
+        /// Attribute (relationship type) id.
+        type_id: SctId,
+        /// Attribute value (destination) id.
+        destination_id: SctId,
+        /// Role group number.
+        group: u16,
+    },
+    /// A defining attribute relationship was inactivated.
+    AttributeRemoved {
+        /// Attribute (relationship type) id.
+        type_id: SctId,
+        /// Attribute value (destination) id.
+        destination_id: SctId,
+        /// Role group number.
+        group: u16,
+    },
+}
+
+/// Store holding every version of every component from a Full release.
+///
+/// Versions are kept sorted by effective time.
+#[derive(Debug, Default)]
+pub struct HistoryStore {
+    /// Concept versions indexed by concept ID.
+    concepts: HashMap<SctId, Vec<Rf2Concept>>,
+    /// Description versions indexed by concept ID.
+    descriptions_by_concept: HashMap<SctId, Vec<Rf2Description>>,
+    /// Relationship versions indexed by source concept ID.
+    relationships_by_source: HashMap<SctId, Vec<Rf2Relationship>>,
+}
+
+impl HistoryStore {
+    /// Creates a new empty history store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every row of the Full concept, description and relationship files.
+    pub fn load(files: &Rf2Files) -> Rf2Result<Self> {
+        let mut store = Self::new();
+
+        if let Some(ref concept_path) = files.concept_file {
+            store.load_concepts(concept_path)?;
+        }
+
+        if let Some(ref description_path) = files.description_file {
+            store.load_descriptions(description_path)?;
+        }
+
+        if let Some(ref relationship_path) = files.relationship_file {
+            store.load_relationships(relationship_path)?;
+        }
+
+        Ok(store)
+    }
+
+    /// Loads all concept rows (active and inactive) from a Full concept file.
+    pub fn load_concepts<P: AsRef<Path>>(&mut self, path: P) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Concept>::from_path(path, all_rows())?;
+        let count = insert_versions(&mut self.concepts, parser.flatten(), |c| c.id);
+        sort_versions(&mut self.concepts);
+        Ok(count)
+    }
+
+    /// Loads all description rows from a Full description file.
+    pub fn load_descriptions<P: AsRef<Path>>(&mut self, path: P) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Description>::from_path(path, all_rows())?;
+        let count = insert_versions(&mut self.descriptions_by_concept, parser.flatten(), |d| {
+            d.concept_id
+        });
+        sort_versions(&mut self.descriptions_by_concept);
+        Ok(count)
+    }
+
+    /// Loads all relationship rows from a Full relationship file.
+    pub fn load_relationships<P: AsRef<Path>>(&mut self, path: P) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Relationship>::from_path(path, all_rows())?;
+        let count = insert_versions(&mut self.relationships_by_source, parser.flatten(), |r| {
+            r.source_id
+        });
+        sort_versions(&mut self.relationships_by_source);
+        Ok(count)
+    }
+
+    /// Bulk inserts concept versions.
+    pub fn insert_concepts(&mut self, concepts: impl IntoIterator<Item = Rf2Concept>) {
+        insert_versions(&mut self.concepts, concepts, |c| c.id);
+        sort_versions(&mut self.concepts);
+    }
+
+    /// Bulk inserts description versions.
+    pub fn insert_descriptions(&mut self, descriptions: impl IntoIterator<Item = Rf2Description>) {
+        insert_versions(&mut self.descriptions_by_concept, descriptions, |d| {
+            d.concept_id
+        });
+        sort_versions(&mut self.descriptions_by_concept);
+    }
+
+    /// Bulk inserts relationship versions.
+    pub fn insert_relationships(
+        &mut self,
+        relationships: impl IntoIterator<Item = Rf2Relationship>,
+    ) {
+        insert_versions(&mut self.relationships_by_source, relationships, |r| {
+            r.source_id
+        });
+        sort_versions(&mut self.relationships_by_source);
+    }
+
+    // Query methods
+
+    /// Gets every version of a concept, oldest first.
+    pub fn concept_versions(&self, concept_id: SctId) -> &[Rf2Concept] {
+        self.concepts
+            .get(&concept_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets every version of every description of a concept, oldest first.
+    pub fn description_versions(&self, concept_id: SctId) -> &[Rf2Description] {
+        self.descriptions_by_concept
+            .get(&concept_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets every version of every relationship with this concept as source, oldest first.
+    pub fn relationship_versions(&self, source_id: SctId) -> &[Rf2Relationship] {
+        self.relationships_by_source
+            .get(&source_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns true if any version of the concept is known.
+    pub fn has_concept(&self, concept_id: SctId) -> bool {
+        self.concepts.contains_key(&concept_id)
+    }
+
+    /// Builds the change timeline for a concept, oldest change first.
+    ///
+    /// Covers activation flips, definition status and module changes on the
+    /// concept itself, description additions and inactivations, FSN edits,
+    /// and parent / attribute changes from its outgoing relationships.
+    pub fn concept_timeline(&self, concept_id: SctId) -> Vec<ConceptChange> {
+        let mut changes = Vec::new();
+
+        self.concept_changes(concept_id, &mut changes);
+        self.description_changes(concept_id, &mut changes);
+        self.relationship_changes(concept_id, &mut changes);
+
+        // Stable sort keeps concept, description, relationship order within a release
+        changes.sort_by_key(|c| c.effective_time);
+        changes
+    }
+
+    fn concept_changes(&self, concept_id: SctId, changes: &mut Vec<ConceptChange>) {
+        let mut previous: Option<&Rf2Concept> = None;
+
+        for version in self.concept_versions(concept_id) {
+            let mut push = |kind| {
+                changes.push(ConceptChange {
+                    effective_time: version.effective_time,
+                    component_id: concept_id,
+                    kind,
+                })
+            };
+
+            match previous {
+                None => {
+                    push(ChangeKind::ConceptCreated);
+                    if !version.active {
+                        push(ChangeKind::ConceptInactivated);
+                    }
+                }
+                Some(prev) => {
+                    if prev.active != version.active {
+                        push(if version.active {
+                            ChangeKind::ConceptActivated
+                        } else {
+                            ChangeKind::ConceptInactivated
+                        });
+                    }
+                    if prev.definition_status_id != version.definition_status_id {
+                        push(ChangeKind::DefinitionStatusChanged {
+                            from: prev.definition_status_id,
+                            to: version.definition_status_id,
+                        });
+                    }
+                    if prev.module_id != version.module_id {
+                        push(ChangeKind::ModuleChanged {
+                            from: prev.module_id,
+                            to: version.module_id,
+                        });
+                    }
+                }
+            }
+
+            previous = Some(version);
+        }
+    }
+
+    fn description_changes(&self, concept_id: SctId, changes: &mut Vec<ConceptChange>) {
+        let versions = self.description_versions(concept_id);
+        let mut was_active: HashMap<SctId, bool> = HashMap::new();
+        let mut active_fsns: HashMap<SctId, (u32, &str)> = HashMap::new();
+        let mut current_fsn: Option<&str> = None;
+
+        for (i, version) in versions.iter().enumerate() {
+            let previously_active = was_active.insert(version.id, version.active);
+            if previously_active.unwrap_or(false) != version.active {
+                let term = version.term.clone();
+                let type_id = version.type_id;
+                changes.push(ConceptChange {
+                    effective_time: version.effective_time,
+                    component_id: version.id,
+                    kind: if version.active {
+                        ChangeKind::DescriptionAdded { type_id, term }
+                    } else {
+                        ChangeKind::DescriptionInactivated { type_id, term }
+                    },
+                });
+            }
+
+            if version.is_fsn() {
+                if version.active {
+                    active_fsns.insert(version.id, (version.effective_time, &version.term));
+                } else {
+                    active_fsns.remove(&version.id);
+                }
+            }
+
+            // Compare FSNs once all rows of this release have been applied
+            let release_continues = matches!(
+                versions.get(i + 1),
+                Some(next) if next.effective_time == version.effective_time
+            );
+            if release_continues {
+                continue;
+            }
+
+            let fsn = active_fsns
+                .iter()
+                .max_by_key(|(id, (time, _))| (*time, **id))
+                .map(|(_, (_, term))| *term);
+            if let (Some(from), Some(to)) = (current_fsn, fsn) {
+                if from != to {
+                    changes.push(ConceptChange {
+                        effective_time: version.effective_time,
+                        component_id: concept_id,
+                        kind: ChangeKind::FsnChanged {
+                            from: from.to_string(),
+                            to: to.to_string(),
+                        },
+                    });
+                }
+            }
+            if fsn.is_some() {
+                current_fsn = fsn;
+            }
+        }
+    }
+
+    fn relationship_changes(&self, concept_id: SctId, changes: &mut Vec<ConceptChange>) {
+        let mut was_active: HashMap<SctId, bool> = HashMap::new();
+
+        for version in self.relationship_versions(concept_id) {
+            let previously_active = was_active.insert(version.id, version.active);
+            if previously_active.unwrap_or(false) == version.active {
+                continue;
+            }
+
+            let kind = match (version.is_is_a(), version.active) {
+                (true, true) => ChangeKind::ParentAdded {
+                    parent_id: version.destination_id,
+                },
+                (true, false) => ChangeKind::ParentRemoved {
+                    parent_id: version.destination_id,
+                },
+                (false, true) => ChangeKind::AttributeAdded {
+                    type_id: version.type_id,
+                    destination_id: version.destination_id,
+                    group: version.relationship_group,
+                },
+                (false, false) => ChangeKind::AttributeRemoved {
+                    type_id: version.type_id,
+                    destination_id: version.destination_id,
+                    group: version.relationship_group,
+                },
+            };
+
+            changes.push(ConceptChange {
+                effective_time: version.effective_time,
+                component_id: version.id,
+                kind,
+            });
+        }
+    }
+
+    // Statistics
+
+    /// Returns the number of concepts with at least one version.
+    pub fn concept_count(&self) -> usize {
+        self.concepts.len()
+    }
+
+    /// Returns the total number of stored rows across all component types.
+    pub fn version_count(&self) -> usize {
+        self.concepts.values().map(Vec::len).sum::<usize>()
+            + self
+                .descriptions_by_concept
+                .values()
+                .map(Vec::len)
+                .sum::<usize>()
+            + self
+                .relationships_by_source
+                .values()
+                .map(Vec::len)
+                .sum::<usize>()
+    }
+}
+
+/// Parser config that keeps inactive rows; history needs every version.
+fn all_rows() -> Rf2Config {
+    Rf2Config {
+        active_only: false,
+        ..Default::default()
+    }
+}
+
+fn insert_versions<T>(
+    index: &mut HashMap<SctId, Vec<T>>,
+    rows: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> SctId,
+) -> usize {
+    let mut count = 0;
+    for row in rows {
+        index.entry(key(&row)).or_default().push(row);
+        count += 1;
+    }
+    count
+}
+
+fn sort_versions<T: Rf2Component>(index: &mut HashMap<SctId, Vec<T>>) {
+    for versions in index.values_mut() {
+        versions.sort_by_key(|v| v.effective_time());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_types::{CharacteristicType, DefinitionStatus, DescriptionType, ModifierType};
+
+    fn concept(effective_time: u32, active: bool, definition_status_id: SctId) -> Rf2Concept {
+        Rf2Concept {
+            id: 100,
+            effective_time,
+            active,
+            module_id: 900000000000207008,
+            definition_status_id,
+        }
+    }
+
+    fn fsn(id: SctId, effective_time: u32, active: bool, term: &str) -> Rf2Description {
+        Rf2Description {
+            id,
+            effective_time,
+            active,
+            module_id: 900000000000207008,
+            concept_id: 100,
+            language_code: "en".to_string(),
+            type_id: DescriptionType::FSN_ID,
+            term: term.to_string(),
+            case_significance_id: 900000000000448009,
+        }
+    }
+
+    fn is_a(id: SctId, effective_time: u32, active: bool, parent: SctId) -> Rf2Relationship {
+        Rf2Relationship {
+            id,
+            effective_time,
+            active,
+            module_id: 900000000000207008,
+            source_id: 100,
+            destination_id: parent,
+            relationship_group: 0,
+            type_id: 116680003,
+            characteristic_type_id: CharacteristicType::INFERRED_ID,
+            modifier_id: ModifierType::EXISTENTIAL_ID,
+        }
+    }
+
+    #[test]
+    fn test_versions_are_sorted() {
+        let mut history = HistoryStore::new();
+        history.insert_concepts([
+            concept(20200131, true, DefinitionStatus::FULLY_DEFINED_ID),
+            concept(20020131, true, DefinitionStatus::PRIMITIVE_ID),
+        ]);
+
+        let versions = history.concept_versions(100);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].effective_time, 20020131);
+        assert!(history.concept_versions(999).is_empty());
+    }
+
+    #[test]
+    fn test_concept_timeline() {
+        let mut history = HistoryStore::new();
+        history.insert_concepts([
+            concept(20020131, true, DefinitionStatus::PRIMITIVE_ID),
+            concept(20150131, true, DefinitionStatus::FULLY_DEFINED_ID),
+            concept(20210131, false, DefinitionStatus::FULLY_DEFINED_ID),
+        ]);
+        history.insert_descriptions([
+            fsn(1, 20020131, true, "Old name (disorder)"),
+            fsn(1, 20180131, false, "Old name (disorder)"),
+            fsn(2, 20180131, true, "New name (disorder)"),
+        ]);
+        history.insert_relationships([
+            is_a(10, 20020131, true, 200),
+            is_a(10, 20150131, false, 200),
+            is_a(11, 20150131, true, 300),
+        ]);
+
+        let kinds: Vec<_> = history
+            .concept_timeline(100)
+            .into_iter()
+            .map(|c| (c.effective_time, c.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                (20020131, ChangeKind::ConceptCreated),
+                (
+                    20020131,
+                    ChangeKind::DescriptionAdded {
+                        type_id: DescriptionType::FSN_ID,
+                        term: "Old name (disorder)".to_string()
+                    }
+                ),
+                (20020131, ChangeKind::ParentAdded { parent_id: 200 }),
+                (
+                    20150131,
+                    ChangeKind::DefinitionStatusChanged {
+                        from: DefinitionStatus::PRIMITIVE_ID,
+                        to: DefinitionStatus::FULLY_DEFINED_ID
+                    }
+                ),
+                (20150131, ChangeKind::ParentRemoved { parent_id: 200 }),
+                (20150131, ChangeKind::ParentAdded { parent_id: 300 }),
+                (
+                    20180131,
+                    ChangeKind::DescriptionInactivated {
+                        type_id: DescriptionType::FSN_ID,
+                        term: "Old name (disorder)".to_string()
+                    }
+                ),
+                (
+                    20180131,
+                    ChangeKind::DescriptionAdded {
+                        type_id: DescriptionType::FSN_ID,
+                        term: "New name (disorder)".to_string()
+                    }
+                ),
+                (
+                    20180131,
+                    ChangeKind::FsnChanged {
+                        from: "Old name (disorder)".to_string(),
+                        to: "New name (disorder)".to_string()
+                    }
+                ),
+                (20210131, ChangeKind::ConceptInactivated),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unchanged_rows_produce_no_events() {
+        let mut history = HistoryStore::new();
+        // A module-only re-release of an unchanged relationship
+        history
+            .insert_relationships([is_a(10, 20020131, true, 200), is_a(10, 20100131, true, 200)]);
+
+        let timeline = history.concept_timeline(100);
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].kind, ChangeKind::ParentAdded { parent_id: 200 });
+    }
+
+    #[test]
+    fn test_version_count() {
+        let mut history = HistoryStore::new();
+        history.insert_concepts([concept(20020131, true, DefinitionStatus::PRIMITIVE_ID)]);
+        history.insert_descriptions([fsn(1, 20020131, true, "Name (disorder)")]);
+        history.insert_relationships([is_a(10, 20020131, true, 200)]);
+
+        assert_eq!(history.concept_count(), 1);
+        assert_eq!(history.version_count(), 3);
+        assert!(history.has_concept(100));
+    }
+}
@@ -10,6 +10,10 @@
 //!
 //! - `parallel` - Enables parallel parsing via rayon (default)
 //! - `progress` - Enables progress bar support via indicatif (optional)
+//! - `zip` - Enables reading releases directly from the distributed ZIP archive
+//! - `mmap` - Enables memory-mapped, zero-copy parsing ([`MappedFile`]) and the
+//!   read-only [`MappedStore`]
+//! - `snapshot` - Enables binary store snapshots for fast startup ([`SnomedStore::save_snapshot`])
 //!
 //! ## Usage
 //!
@@ -52,6 +56,20 @@
 //! println!("Parents: {:?}", parents);
 //! ```
 //!
+//! ### Loading from the Release Archive
+//!
+//! With the `zip` feature, discovery accepts the distributed archive itself.
+//! Files are decompressed while they are parsed; nothing is extracted to disk.
+//!
+//! ```ignore
+//! use snomed_loader::{SnomedStore, discover_rf2_files};
+//!
+//! let files = discover_rf2_files("SnomedCT_InternationalRF2_PRODUCTION_20250101T120000Z.zip")?;
+//!
+//! let mut store = SnomedStore::new();
+//! store.load_all(&files)?;
+//! ```
+//!
 //! ### Filtering
 //!
 //! ```ignore
@@ -70,26 +88,60 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "zip")]
+mod archive;
+#[cfg(feature = "parallel")]
+mod chunked;
+mod compact;
 mod concept;
 mod description;
+mod edition;
+mod history;
 mod loader;
+#[cfg(feature = "mmap")]
+mod mapped;
+#[cfg(feature = "mmap")]
+mod mapped_store;
 pub mod mrcm;
 mod parser;
+pub mod refset;
 mod relationship;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+mod source;
 mod store;
+mod terminology;
+mod tokenizer;
 mod types;
 
 // Re-export main types and functions
-pub use loader::{discover_rf2_files, format_bytes};
-pub use parser::{parse, Rf2Parser, Rf2Record};
+pub use compact::CompactStore;
+pub use edition::{Edition, EditionId, EditionView};
+pub use history::{ChangeKind, ConceptChange, HistoryStore};
+pub use loader::{
+    discover_release_files, discover_rf2_delta_files, discover_rf2_files, discover_rf2_full_files,
+    format_bytes,
+};
+#[cfg(feature = "mmap")]
+pub use mapped::{MappedFile, MappedRecords, Rf2View};
+#[cfg(feature = "mmap")]
+pub use mapped_store::{MappedStore, MAPPED_STORE_VERSION};
+pub use parser::{parse, LenientParser, Rf2Component, Rf2Parser, Rf2Record};
+#[cfg(feature = "snapshot")]
+pub use snapshot::SNAPSHOT_VERSION;
+pub use source::{open_rf2_file, Rf2Reader};
 pub use store::SnomedStore;
+pub use terminology::{TerminologyStore, Unsupported};
+pub use tokenizer::{Fields, Rf2Line};
 pub use types::{
-    DescriptionConfig, ParseStats, RelationshipConfig, Rf2Config, Rf2Error, Rf2Files, Rf2Result,
+    DeltaStats, DescriptionConfig, FileReport, Hierarchy, LoadOptions, LoadReport, ParseStats,
+    RelationshipConfig, ReleaseType, Rf2Config, Rf2Error, Rf2Files, Rf2Result, RowError,
+    SnapshotSource,
 };
 
 // Re-export filter traits
-pub use description::DescriptionFilter;
-pub use relationship::RelationshipFilter;
+pub use description::{DescriptionFilter, DescriptionView};
+pub use relationship::{OutgoingRelationship, RelationshipFilter};
 
 // Re-export snomed-types for convenience
 pub use snomed_types;
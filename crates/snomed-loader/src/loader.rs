@@ -3,14 +3,64 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::types::{Rf2Error, Rf2Files, Rf2Result};
+use crate::refset::RefsetPattern;
+use crate::source::is_zip_path;
+use crate::types::{ReleaseType, Rf2Error, Rf2Files, Rf2Result};
 
 /// Discovers RF2 files in a SNOMED CT release directory.
 ///
 /// Searches for the Snapshot/Terminology directory and locates
 /// concept, description, and relationship files. Also searches
-/// for MRCM reference set files in Refset/Metadata.
+/// for MRCM and reference set descriptor files in Refset/Metadata.
 pub fn discover_rf2_files<P: AsRef<Path>>(path: P) -> Rf2Result<Rf2Files> {
+    discover_release_files(path, ReleaseType::Snapshot)
+}
+
+/// Discovers RF2 Delta files in a SNOMED CT release directory.
+///
+/// Searches for the Delta/Terminology directory. Unlike snapshot discovery,
+/// missing files are not an error: a Delta release only needs to contain
+/// the file types that changed.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::{discover_rf2_delta_files, discover_rf2_files, LoadOptions, SnomedStore};
+///
+/// let options = LoadOptions::default();
+/// let mut store = SnomedStore::new();
+/// store.load(&discover_rf2_files("/path/to/20250101")?, &options)?;
+///
+/// let delta = discover_rf2_delta_files("/path/to/20250201")?;
+/// let report = store.apply_delta(&delta, &options)?;
+/// println!("{} upserted, {} removed", report.changes.upserted, report.changes.removed);
+/// ```
+pub fn discover_rf2_delta_files<P: AsRef<Path>>(path: P) -> Rf2Result<Rf2Files> {
+    discover_release_files(path, ReleaseType::Delta)
+}
+
+/// Discovers RF2 Full files in a SNOMED CT release directory.
+///
+/// Searches for the Full/Terminology directory. Full files hold every
+/// historical row and are used to reconstruct past snapshots, see
+/// [`SnomedStore::load_all_as_of`](crate::SnomedStore::load_all_as_of).
+pub fn discover_rf2_full_files<P: AsRef<Path>>(path: P) -> Rf2Result<Rf2Files> {
+    discover_release_files(path, ReleaseType::Full)
+}
+
+/// Discovers RF2 files of the given release type in a SNOMED CT release directory.
+///
+/// Looks for `<ReleaseType>/Terminology` (e.g. `Delta/Terminology`) and the
+/// `Metadata`, `Language`, `Content` and `Map` folders of `<ReleaseType>/Refset`,
+/// matching `*_<ReleaseType>` file names.
+///
+/// With the `zip` feature, `path` may also be the distributed release archive
+/// (`SnomedCT_*.zip`). The discovered paths then point inside the archive and
+/// are streamed from it by the parsers without extracting anything to disk.
+pub fn discover_release_files<P: AsRef<Path>>(
+    path: P,
+    release_type: ReleaseType,
+) -> Rf2Result<Rf2Files> {
     let path = path.as_ref();
 
     if !path.exists() {
@@ -19,95 +69,235 @@ pub fn discover_rf2_files<P: AsRef<Path>>(path: P) -> Rf2Result<Rf2Files> {
         });
     }
 
+    if path.is_file() && is_zip_path(path) {
+        return discover_archive_files(path, release_type);
+    }
+
     // Try to find the Terminology directory
-    let terminology_dir = find_terminology_dir(path)?;
+    let terminology_dir = find_terminology_dir(path, release_type)?;
 
-    let mut files = Rf2Files::new();
+    let mut files = Rf2Files {
+        release_type,
+        ..Rf2Files::new()
+    };
 
     // Scan for RF2 files in Terminology directory
     for entry in fs::read_dir(&terminology_dir)? {
         let entry = entry?;
         let filename = entry.file_name();
-        let filename_str = filename.to_string_lossy();
+        classify_terminology_file(&filename.to_string_lossy(), entry.path(), &mut files);
+    }
+
+    // Try to find reference set files in the Refset directory
+    if let Some(release_dir) = terminology_dir.parent() {
+        let refset_dir = release_dir.join("Refset");
+        discover_refset_files(
+            &refset_dir.join("Metadata"),
+            &mut files,
+            classify_metadata_file,
+        )?;
+        discover_refset_files(
+            &refset_dir.join("Language"),
+            &mut files,
+            classify_language_file,
+        )?;
+        discover_refset_files(
+            &refset_dir.join("Content"),
+            &mut files,
+            classify_content_file,
+        )?;
+        discover_refset_files(&refset_dir.join("Map"), &mut files, classify_map_file)?;
+    }
 
-        if !filename_str.ends_with(".txt") {
+    check_required_files(&files, &terminology_dir)?;
+
+    Ok(files)
+}
+
+/// Discovers RF2 files inside a release ZIP archive.
+#[cfg(feature = "zip")]
+fn discover_archive_files(archive: &Path, release_type: ReleaseType) -> Rf2Result<Rf2Files> {
+    let mut files = Rf2Files {
+        release_type,
+        ..Rf2Files::new()
+    };
+
+    for name in crate::archive::entry_names(archive)? {
+        let Some((dir, filename)) = name.rsplit_once('/') else {
             continue;
+        };
+        let entry_path = archive.join(&name);
+
+        if dir.ends_with("Terminology") {
+            classify_terminology_file(filename, entry_path, &mut files);
+        } else if dir.ends_with("Refset/Metadata") {
+            classify_metadata_file(filename, entry_path, &mut files);
+        } else if dir.ends_with("Refset/Language") {
+            classify_language_file(filename, entry_path, &mut files);
+        } else if dir.ends_with("Refset/Content") {
+            classify_content_file(filename, entry_path, &mut files);
+        } else if dir.ends_with("Refset/Map") {
+            classify_map_file(filename, entry_path, &mut files);
         }
+    }
 
-        if filename_str.starts_with("sct2_Concept_Snapshot") {
-            files.concept_file = Some(entry.path());
-            // Extract release date from filename
-            if let Some(date) = extract_release_date(&filename_str) {
-                files.release_date = Some(date);
-            }
-        } else if filename_str.starts_with("sct2_Description_Snapshot") {
-            files.description_file = Some(entry.path());
-        } else if filename_str.starts_with("sct2_Relationship_Snapshot") {
-            files.relationship_file = Some(entry.path());
-        } else if filename_str.starts_with("sct2_StatedRelationship_Snapshot") {
-            files.stated_relationship_file = Some(entry.path());
-        } else if filename_str.starts_with("sct2_TextDefinition_Snapshot") {
-            files.text_definition_file = Some(entry.path());
-        }
+    check_required_files(&files, archive)?;
+
+    Ok(files)
+}
+
+/// Reports that ZIP support was not compiled in.
+#[cfg(not(feature = "zip"))]
+fn discover_archive_files(archive: &Path, _release_type: ReleaseType) -> Rf2Result<Rf2Files> {
+    Err(Rf2Error::Archive {
+        path: archive.display().to_string(),
+        message: "reading release archives requires the `zip` feature".to_string(),
+    })
+}
+
+/// Records a Terminology file in `files` if its name matches the release type.
+fn classify_terminology_file(filename: &str, path: PathBuf, files: &mut Rf2Files) {
+    if !filename.ends_with(".txt") {
+        return;
     }
 
-    // Try to find MRCM files in Refset/Metadata directory
-    if let Some(snapshot_dir) = terminology_dir.parent() {
-        let metadata_dir = snapshot_dir.join("Refset").join("Metadata");
-        if metadata_dir.exists() {
-            discover_mrcm_files(&metadata_dir, &mut files)?;
+    let kind = files.release_type.as_str();
+    let is_file = |name: &str| filename.starts_with(&format!("sct2_{}_{}", name, kind));
+
+    if is_file("Concept") {
+        files.concept_file = Some(path);
+        // Extract release date from filename
+        if let Some(date) = extract_release_date(filename) {
+            files.release_date = Some(date);
         }
+    } else if is_file("Description") {
+        files.description_file = Some(path);
+    } else if is_file("Relationship") {
+        files.relationship_file = Some(path);
+    } else if is_file("RelationshipConcreteValues") {
+        files.concrete_relationship_file = Some(path);
+    } else if is_file("StatedRelationship") {
+        files.stated_relationship_file = Some(path);
+    } else if is_file("TextDefinition") {
+        files.text_definition_file = Some(path);
+    } else if filename.starts_with(&format!("sct2_sRefset_OWLExpression{}", kind)) {
+        files.owl_expression_file = Some(path);
     }
+}
 
-    if !files.has_required_files() {
+/// Fails if a Snapshot or Full release is missing required files.
+fn check_required_files(files: &Rf2Files, searched: &Path) -> Rf2Result<()> {
+    // Delta releases legitimately omit unchanged file types
+    if files.release_type != ReleaseType::Delta && !files.has_required_files() {
         let missing = files.missing_files();
         return Err(Rf2Error::RequiredFileMissing {
             file_type: missing.join(", "),
-            directory: terminology_dir.display().to_string(),
+            directory: searched.display().to_string(),
         });
     }
 
-    Ok(files)
+    Ok(())
 }
 
-/// Discovers MRCM reference set files in a Metadata directory.
-fn discover_mrcm_files(metadata_dir: &Path, files: &mut Rf2Files) -> Rf2Result<()> {
-    if !metadata_dir.exists() {
+/// Discovers reference set files in a Refset subdirectory using `classify`.
+fn discover_refset_files(
+    refset_dir: &Path,
+    files: &mut Rf2Files,
+    classify: fn(&str, PathBuf, &mut Rf2Files),
+) -> Rf2Result<()> {
+    if !refset_dir.exists() {
         return Ok(());
     }
 
-    for entry in fs::read_dir(metadata_dir)? {
+    for entry in fs::read_dir(refset_dir)? {
         let entry = entry?;
         let filename = entry.file_name();
-        let filename_str = filename.to_string_lossy();
+        classify(&filename.to_string_lossy(), entry.path(), files);
+    }
 
-        if !filename_str.ends_with(".txt") {
-            continue;
-        }
+    Ok(())
+}
 
-        if filename_str.contains("MRCMDomainSnapshot") && !filename_str.contains("ModuleScope") {
-            files.mrcm_domain = Some(entry.path());
-        } else if filename_str.contains("MRCMAttributeDomainSnapshot") {
-            files.mrcm_attribute_domain = Some(entry.path());
-        } else if filename_str.contains("MRCMAttributeRangeSnapshot") {
-            files.mrcm_attribute_range = Some(entry.path());
-        }
+/// Records an MRCM or descriptor reference set file in `files` if its name matches the release type.
+fn classify_metadata_file(filename: &str, path: PathBuf, files: &mut Rf2Files) {
+    if !filename.ends_with(".txt") {
+        return;
     }
 
-    Ok(())
+    let kind = files.release_type.as_str();
+    let is_refset = |name: &str| filename.contains(&format!("{}{}", name, kind));
+
+    if is_refset("MRCMDomain") && !filename.contains("ModuleScope") {
+        files.mrcm_domain = Some(path);
+    } else if is_refset("MRCMAttributeDomain") {
+        files.mrcm_attribute_domain = Some(path);
+    } else if is_refset("MRCMAttributeRange") {
+        files.mrcm_attribute_range = Some(path);
+    } else if is_refset("RefsetDescriptor") {
+        files.refset_descriptor_file = Some(path);
+    }
+}
+
+/// Records a language reference set file in `files` if its name matches the release type.
+///
+/// A release may hold one file per language (e.g. `-en`, `-es`), so all matches are kept.
+fn classify_language_file(filename: &str, path: PathBuf, files: &mut Rf2Files) {
+    let pattern = format!("Refset_Language{}", files.release_type.as_str());
+    if filename.ends_with(".txt") && filename.contains(&pattern) {
+        files.language_refset_files.push(path);
+    }
+}
+
+/// Records a content reference set file in `files` if its name matches the release type.
+fn classify_content_file(filename: &str, path: PathBuf, files: &mut Rf2Files) {
+    if !filename.ends_with(".txt") {
+        return;
+    }
+
+    let kind = files.release_type.as_str();
+    let is_refset = |name: &str| filename.contains(&format!("Refset_{}{}", name, kind));
+
+    // Older releases name the association file "AssociationReference"
+    if is_refset("Association") || is_refset("AssociationReference") {
+        files.association_refset_file = Some(path);
+    } else if is_refset("AttributeValue") {
+        files.attribute_value_refset_file = Some(path);
+    } else if RefsetPattern::from_file_name(filename)
+        .is_some_and(|pattern| pattern.release_type == files.release_type)
+    {
+        files.refset_files.push(path);
+    }
+}
+
+/// Records a map reference set file in `files` if its name matches the release type.
+///
+/// Extensions and national editions may ship several map files of each kind, so all matches are kept.
+fn classify_map_file(filename: &str, path: PathBuf, files: &mut Rf2Files) {
+    if !filename.ends_with(".txt") {
+        return;
+    }
+
+    let kind = files.release_type.as_str();
+    if filename.contains(&format!("Refset_ExtendedMap{}", kind)) {
+        files.extended_map_files.push(path);
+    } else if filename.contains(&format!("Refset_SimpleMap{}", kind)) {
+        files.simple_map_files.push(path);
+    }
 }
 
 /// Finds the Terminology directory within an RF2 release structure.
-fn find_terminology_dir(base: &Path) -> Rf2Result<PathBuf> {
+fn find_terminology_dir(base: &Path, release_type: ReleaseType) -> Rf2Result<PathBuf> {
+    let kind = release_type.as_str();
+
     // Check if base is already the Terminology directory
     if base.ends_with("Terminology") && base.is_dir() {
         return Ok(base.to_path_buf());
     }
 
-    // Check for Snapshot/Terminology
-    let snapshot_term = base.join("Snapshot").join("Terminology");
-    if snapshot_term.exists() {
-        return Ok(snapshot_term);
+    // Check for <ReleaseType>/Terminology
+    let release_term = base.join(kind).join("Terminology");
+    if release_term.exists() {
+        return Ok(release_term);
     }
 
     // Check for just Terminology
@@ -122,10 +312,10 @@ fn find_terminology_dir(base: &Path) -> Rf2Result<PathBuf> {
         if entry.file_type()?.is_dir() {
             let subdir = entry.path();
 
-            // Check subdir/Snapshot/Terminology
-            let sub_snapshot_term = subdir.join("Snapshot").join("Terminology");
-            if sub_snapshot_term.exists() {
-                return Ok(sub_snapshot_term);
+            // Check subdir/<ReleaseType>/Terminology
+            let sub_release_term = subdir.join(kind).join("Terminology");
+            if sub_release_term.exists() {
+                return Ok(sub_release_term);
             }
 
             // Check subdir/Terminology
@@ -137,7 +327,11 @@ fn find_terminology_dir(base: &Path) -> Rf2Result<PathBuf> {
     }
 
     Err(Rf2Error::DirectoryNotFound {
-        path: format!("Terminology directory not found in {}", base.display()),
+        path: format!(
+            "{}/Terminology directory not found in {}",
+            kind,
+            base.display()
+        ),
     })
 }
 
@@ -191,6 +385,179 @@ mod tests {
         assert_eq!(extract_release_date("invalid_filename.txt"), None);
     }
 
+    #[test]
+    fn test_discover_delta_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let delta_term = dir.path().join("Delta").join("Terminology");
+        let snapshot_term = dir.path().join("Snapshot").join("Terminology");
+        fs::create_dir_all(&delta_term).unwrap();
+        fs::create_dir_all(&snapshot_term).unwrap();
+        fs::write(delta_term.join("sct2_Concept_Delta_INT_20250201.txt"), "").unwrap();
+        fs::write(
+            snapshot_term.join("sct2_Concept_Snapshot_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+
+        let files = discover_rf2_delta_files(dir.path()).unwrap();
+        assert_eq!(files.release_type, ReleaseType::Delta);
+        assert_eq!(files.release_date.as_deref(), Some("20250201"));
+        assert!(files
+            .concept_file
+            .as_ref()
+            .unwrap()
+            .ends_with("Delta/Terminology/sct2_Concept_Delta_INT_20250201.txt"));
+        // Delta releases may omit unchanged file types
+        assert!(files.description_file.is_none());
+
+        // Snapshot discovery still requires all core files
+        assert!(matches!(
+            discover_rf2_files(dir.path()),
+            Err(Rf2Error::RequiredFileMissing { .. })
+        ));
+    }
+
+    #[test]
+    fn test_discover_full_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let full_term = dir.path().join("Full").join("Terminology");
+        fs::create_dir_all(&full_term).unwrap();
+        for name in [
+            "Concept",
+            "Description",
+            "Relationship",
+            "RelationshipConcreteValues",
+        ] {
+            let filename = format!("sct2_{}_Full_INT_20250201.txt", name);
+            fs::write(full_term.join(filename), "").unwrap();
+        }
+        fs::write(
+            full_term.join("sct2_sRefset_OWLExpressionFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        let full_language = dir.path().join("Full").join("Refset").join("Language");
+        fs::create_dir_all(&full_language).unwrap();
+        fs::write(
+            full_language.join("der2_cRefset_LanguageFull-en_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        let full_map = dir.path().join("Full").join("Refset").join("Map");
+        fs::create_dir_all(&full_map).unwrap();
+        fs::write(
+            full_map.join("der2_iisssccRefset_ExtendedMapFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            full_map.join("der2_sRefset_SimpleMapFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            full_map.join("der2_sRefset_SimpleMapFull_GB1000000_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        let full_content = dir.path().join("Full").join("Refset").join("Content");
+        fs::create_dir_all(&full_content).unwrap();
+        fs::write(
+            full_content.join("der2_cRefset_AssociationFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            full_content.join("der2_cRefset_AttributeValueFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            full_content.join("der2_Refset_SimpleFull_INT_20250201.txt"),
+            "",
+        )
+        .unwrap();
+
+        let files = discover_rf2_full_files(dir.path()).unwrap();
+        assert_eq!(files.release_type, ReleaseType::Full);
+        assert!(files.has_required_files());
+        // StatedRelationship shares the Relationship prefix only up to the release type
+        assert!(files.stated_relationship_file.is_none());
+        assert!(files
+            .relationship_file
+            .as_ref()
+            .unwrap()
+            .ends_with("sct2_Relationship_Full_INT_20250201.txt"));
+        assert!(files.concrete_relationship_file.is_some());
+        assert!(files.owl_expression_file.is_some());
+        assert_eq!(files.language_refset_files.len(), 1);
+        assert!(files.association_refset_file.is_some());
+        assert!(files.attribute_value_refset_file.is_some());
+        assert_eq!(files.refset_files.len(), 1);
+        assert_eq!(files.extended_map_files.len(), 1);
+        // Every map file is kept, not just the last one discovered
+        assert_eq!(files.simple_map_files.len(), 2);
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn test_discover_archive_files() {
+        use std::io::Write;
+
+        use snomed_types::Rf2Concept;
+
+        use crate::parser::Rf2Parser;
+        use crate::types::Rf2Config;
+
+        let root = "SnomedCT_InternationalRF2_PRODUCTION_20250201T120000Z";
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join(format!("{}.zip", root));
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        let entries = [
+            "Snapshot/Terminology/sct2_Concept_Snapshot_INT_20250201.txt",
+            "Snapshot/Terminology/sct2_Description_Snapshot-en_INT_20250201.txt",
+            "Snapshot/Terminology/sct2_Relationship_Snapshot_INT_20250201.txt",
+            "Snapshot/Refset/Metadata/der2_sssssssRefset_MRCMDomainSnapshot_INT_20250201.txt",
+            "Snapshot/Refset/Metadata/der2_cciRefset_RefsetDescriptorSnapshot_INT_20250201.txt",
+            "Full/Terminology/sct2_Concept_Full_INT_20250201.txt",
+        ];
+        for entry in entries {
+            writer
+                .start_file(format!("{}/{}", root, entry), options)
+                .unwrap();
+            writer
+                .write_all(b"id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n")
+                .unwrap();
+            writer
+                .write_all(b"138875005\t20020131\t1\t900000000000207008\t900000000000074008\n")
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let files = discover_rf2_files(&archive).unwrap();
+        assert_eq!(files.release_date.as_deref(), Some("20250201"));
+        assert!(files.has_required_files());
+        assert!(files.mrcm_domain.is_some());
+        assert!(files.refset_descriptor_file.is_some());
+
+        let concept_file = files.concept_file.unwrap();
+        assert!(concept_file.starts_with(&archive));
+        let concepts = Rf2Parser::<_, Rf2Concept>::from_path(&concept_file, Rf2Config::default())
+            .unwrap()
+            .parse_all()
+            .unwrap();
+        assert_eq!(concepts.len(), 1);
+        assert_eq!(concepts[0].id, 138875005);
+
+        // The Full folder of the same archive lacks descriptions and relationships
+        assert!(matches!(
+            discover_rf2_full_files(&archive),
+            Err(Rf2Error::RequiredFileMissing { .. })
+        ));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(500), "500 bytes");
@@ -0,0 +1,316 @@
+//! Memory-mapped, zero-copy RF2 parsing (requires the `mmap` feature).
+//!
+//! [`MappedFile`] maps an RF2 file into memory and yields records that
+//! borrow from the mapping: ids are parsed in place and text fields such as
+//! terms are `&str` slices of the file, so no `String` is allocated per line
+//! or per field. Rows are filtered exactly like the streaming parser.
+
+use std::fs::File;
+use std::io;
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::SplitInclusive;
+
+use memmap2::Mmap;
+use snomed_types::{Rf2Concept, Rf2Description, Rf2Relationship};
+
+use crate::description::DescriptionView;
+use crate::parser::{check_header, parse, Rf2Record};
+use crate::tokenizer::{Fields, Rf2Line};
+use crate::types::{Rf2Config, Rf2Error, Rf2Result, RowError};
+
+/// A record that can be read in place from a mapped RF2 line.
+///
+/// Implemented by [`Rf2Concept`] and [`Rf2Relationship`], which hold no
+/// text, and by [`DescriptionView`], which borrows its term.
+pub trait Rf2View<'a>: Sized {
+    /// Expected column names for this record type.
+    const EXPECTED_COLUMNS: &'static [&'static str];
+
+    /// Parses a record from the fields of one line.
+    fn from_fields(fields: &mut Fields<'a>) -> Rf2Result<Self>;
+
+    /// Returns true if this record passes the given filter config.
+    fn passes_filter(&self, config: &Rf2Config) -> bool;
+}
+
+/// An RF2 file mapped into memory.
+///
+/// The file must not be modified or truncated while it is mapped; RF2
+/// release files are read-only inputs, so this holds for normal use.
+/// Files inside a ZIP archive cannot be mapped.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::{DescriptionView, MappedFile, Rf2Config};
+///
+/// let file = MappedFile::open("sct2_Description_Snapshot-en_INT_20250101.txt")?;
+/// for description in file.records::<DescriptionView>(Rf2Config::default())?.flatten() {
+///     if description.term.contains("diabetes") {
+///         println!("{} {}", description.concept_id, description.term);
+///     }
+/// }
+/// ```
+pub struct MappedFile {
+    path: PathBuf,
+    map: Mmap,
+}
+
+impl MappedFile {
+    /// Maps an RF2 file.
+    ///
+    /// # Errors
+    /// Returns [`Rf2Error::FileNotFound`] if the path is not a file on disk.
+    pub fn open<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Rf2Error::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let file = File::open(path)?;
+        // SAFETY: the mapping is only read, and release files are not
+        // modified while they are being loaded (see the type docs).
+        let map = unsafe { Mmap::map(&file)? };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            map,
+        })
+    }
+
+    /// Returns the path of the mapped file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the records of the file that pass `config`.
+    ///
+    /// # Errors
+    /// Returns an error if the file is not valid UTF-8 or has invalid headers.
+    pub fn records<'a, V: Rf2View<'a>>(
+        &'a self,
+        config: Rf2Config,
+    ) -> Rf2Result<MappedRecords<'a, V>> {
+        let text = std::str::from_utf8(&self.map)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut lines = text.split_inclusive('\n').enumerate();
+        match lines.next() {
+            Some((_, header)) => check_header(Rf2Line::new(header).fields(), V::EXPECTED_COLUMNS)?,
+            None => check_header(std::iter::empty(), V::EXPECTED_COLUMNS)?,
+        }
+
+        Ok(MappedRecords {
+            path: &self.path,
+            lines,
+            config,
+            records_read: 0,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl std::fmt::Debug for MappedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedFile")
+            .field("path", &self.path)
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+/// Iterator over the records of a [`MappedFile`].
+///
+/// Rows that fail to parse are yielded as [`Rf2Error::Row`] and iteration
+/// continues with the next line.
+pub struct MappedRecords<'a, V> {
+    path: &'a Path,
+    lines: Enumerate<SplitInclusive<'a, char>>,
+    config: Rf2Config,
+    records_read: usize,
+    _marker: PhantomData<V>,
+}
+
+impl<V> MappedRecords<'_, V> {
+    /// Returns the number of records read so far, including malformed ones.
+    pub fn records_read(&self) -> usize {
+        self.records_read
+    }
+}
+
+impl<'a, V: Rf2View<'a>> Iterator for MappedRecords<'a, V> {
+    type Item = Rf2Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, raw) in self.lines.by_ref() {
+            let line = Rf2Line::new(raw);
+            if line.is_blank() {
+                continue;
+            }
+            self.records_read += 1;
+
+            match V::from_fields(&mut Fields::new(line, V::EXPECTED_COLUMNS)) {
+                Ok(record) if record.passes_filter(&self.config) => return Some(Ok(record)),
+                Ok(_) => {}
+                Err(error) => {
+                    let error = RowError::new(self.path, index as u64 + 1, error);
+                    return Some(Err(Rf2Error::Row(Box::new(error))));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Rf2View<'a> for Rf2Concept {
+    const EXPECTED_COLUMNS: &'static [&'static str] = <Rf2Concept as Rf2Record>::EXPECTED_COLUMNS;
+
+    fn from_fields(fields: &mut Fields<'a>) -> Rf2Result<Self> {
+        Ok(Rf2Concept {
+            id: fields.sctid()?,
+            effective_time: fields.parse(parse::effective_time)?,
+            active: fields.parse(parse::boolean)?,
+            module_id: fields.sctid()?,
+            definition_status_id: fields.sctid()?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        Rf2Record::passes_filter(self, config)
+    }
+}
+
+impl<'a> Rf2View<'a> for Rf2Relationship {
+    const EXPECTED_COLUMNS: &'static [&'static str] =
+        <Rf2Relationship as Rf2Record>::EXPECTED_COLUMNS;
+
+    fn from_fields(fields: &mut Fields<'a>) -> Rf2Result<Self> {
+        Ok(Rf2Relationship {
+            id: fields.sctid()?,
+            effective_time: fields.parse(parse::effective_time)?,
+            active: fields.parse(parse::boolean)?,
+            module_id: fields.sctid()?,
+            source_id: fields.sctid()?,
+            destination_id: fields.sctid()?,
+            relationship_group: fields.parse(parse::integer)?,
+            type_id: fields.sctid()?,
+            characteristic_type_id: fields.sctid()?,
+            modifier_id: fields.sctid()?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        Rf2Record::passes_filter(self, config)
+    }
+}
+
+impl<'a> Rf2View<'a> for DescriptionView<'a> {
+    const EXPECTED_COLUMNS: &'static [&'static str] =
+        <Rf2Description as Rf2Record>::EXPECTED_COLUMNS;
+
+    fn from_fields(fields: &mut Fields<'a>) -> Rf2Result<Self> {
+        Ok(DescriptionView {
+            id: fields.sctid()?,
+            effective_time: fields.parse(parse::effective_time)?,
+            active: fields.parse(parse::boolean)?,
+            module_id: fields.sctid()?,
+            concept_id: fields.sctid()?,
+            language_code: fields.next_field()?,
+            type_id: fields.sctid()?,
+            term: fields.next_field()?,
+            case_significance_id: fields.sctid()?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::description::DescriptionFilter;
+    use crate::types::DescriptionConfig;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const DESCRIPTIONS: &str = "\u{feff}id\teffectiveTime\tactive\tmoduleId\tconceptId\tlanguageCode\ttypeId\tterm\tcaseSignificanceId\r\n\
+        1\t20020131\t1\t900000000000207008\t100\ten\t900000000000013009\tTape 1\" wide\t900000000000448009\r\n\
+        2\t20020131\t0\t900000000000207008\t100\ten\t900000000000013009\tRetired\t900000000000448009\r\n\
+        \r\n\
+        3\t2002013\t1\t900000000000207008\t100\ten\t900000000000013009\tBad date\t900000000000448009\r\n\
+        4\t20020131\t1\t900000000000207008\t200\tes\t900000000000013009\tDiabetes\t900000000000448009\r\n\
+        5\t20020131\t1\t900000000000207008\t200\n";
+
+    fn write_file(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_mapped_descriptions_borrow_fields() {
+        let file = write_file(DESCRIPTIONS);
+        let mapped = MappedFile::open(file.path()).unwrap();
+        let mut records = mapped
+            .records::<DescriptionView>(Rf2Config::default())
+            .unwrap();
+
+        let first = records.next().unwrap().unwrap();
+        assert_eq!(first.term, "Tape 1\" wide");
+        assert_eq!(first.to_description().language_code, "en");
+
+        // The inactive row is filtered; the bad date is reported with its location
+        let Err(Rf2Error::Row(error)) = records.next().unwrap() else {
+            panic!("expected a row error");
+        };
+        assert_eq!(error.line, 5);
+        assert_eq!(error.column, Some("effectiveTime"));
+
+        assert_eq!(records.next().unwrap().unwrap().term, "Diabetes");
+        let Err(Rf2Error::Row(error)) = records.next().unwrap() else {
+            panic!("expected a row error");
+        };
+        assert_eq!(error.line, 7);
+        assert_eq!(error.column, Some("languageCode"));
+
+        assert!(records.next().is_none());
+        assert_eq!(records.records_read(), 5);
+    }
+
+    #[test]
+    fn test_mapped_description_filter() {
+        let file = write_file(DESCRIPTIONS);
+        let mapped = MappedFile::open(file.path()).unwrap();
+        let config = DescriptionConfig::english_terms();
+
+        let terms: Vec<&str> = mapped
+            .records::<DescriptionView>(config.base.clone())
+            .unwrap()
+            .flatten()
+            .filter(|d| d.passes_description_filter(&config))
+            .map(|d| d.term)
+            .collect();
+        assert_eq!(terms, vec!["Tape 1\" wide"]);
+    }
+
+    #[test]
+    fn test_mapped_file_checks_header() {
+        let file = write_file(DESCRIPTIONS);
+        let mapped = MappedFile::open(file.path()).unwrap();
+
+        let result = mapped.records::<Rf2Concept>(Rf2Config::default());
+        assert!(matches!(result, Err(Rf2Error::UnexpectedColumn { .. })));
+        assert!(matches!(
+            MappedFile::open("/nonexistent/concepts.txt"),
+            Err(Rf2Error::FileNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,1096 @@
+//! Read-only store layout that is queried in place through a memory map
+//! (requires the `mmap` feature).
+//!
+//! [`MappedStore::write`] lays a loaded [`SnomedStore`] out as flat
+//! little-endian tables; [`MappedStore::open`] maps the file and answers
+//! queries straight from the mapping. Nothing is copied into the heap at
+//! open, so several processes mapping the same file share one copy in the
+//! page cache.
+//!
+//! File layout:
+//!
+//! - Header: magic `SNOMEDMS`, format version (u32), section count (u32),
+//!   then an (offset, length) pair of u64s per section.
+//! - Source: the [`SnapshotSource`] the store was built from, as its data
+//!   path, release date and options, each a u32 length and UTF-8 bytes. An
+//!   undated release has length `u32::MAX` and no bytes.
+//! - Concepts: sorted concept ids (u64) and a parallel table of fixed-size
+//!   concept records.
+//! - Descriptions: fixed-size records grouped by concept, with a sorted
+//!   array of concept ids and an offset table (u32, one past the end for
+//!   the last id) into the records. Language codes and terms are
+//!   (offset, length) references into a UTF-8 string arena.
+//! - Relationships, once for the inferred and once for the stated view:
+//!   records sorted by source concept, sorted source ids with an offset
+//!   table, and sorted destination ids with an offset table into a list of
+//!   record numbers, so each relationship is stored once.
+//!
+//! Sections start on 8-byte boundaries.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use memmap2::Mmap;
+use snomed_types::{Rf2Concept, Rf2Description, Rf2Relationship, SctId};
+
+use crate::description::DescriptionView;
+use crate::store::SnomedStore;
+use crate::types::{Hierarchy, Rf2Error, Rf2Result, SnapshotSource};
+
+/// Marks the start of a mapped store file.
+const MAGIC: &[u8; 8] = b"SNOMEDMS";
+
+/// Mapped store format version.
+pub const MAPPED_STORE_VERSION: u32 = 2;
+
+// Sections, in file order
+const SOURCE: usize = 0;
+const CONCEPT_IDS: usize = 1;
+const CONCEPTS: usize = 2;
+const DESCRIPTION_KEYS: usize = 3;
+const DESCRIPTION_OFFSETS: usize = 4;
+const DESCRIPTIONS: usize = 5;
+const STRINGS: usize = 6;
+/// First section of the inferred relationship block.
+const INFERRED: usize = 7;
+/// First section of the stated relationship block.
+const STATED: usize = INFERRED + BLOCK_LEN;
+const SECTION_COUNT: usize = STATED + BLOCK_LEN;
+
+// Sections of a relationship block, relative to its first section
+const RELATIONSHIPS: usize = 0;
+const SOURCE_KEYS: usize = 1;
+const SOURCE_OFFSETS: usize = 2;
+const DESTINATION_KEYS: usize = 3;
+const DESTINATION_OFFSETS: usize = 4;
+const DESTINATION_ROWS: usize = 5;
+const BLOCK_LEN: usize = 6;
+
+/// Record sizes in bytes.
+const CONCEPT_LEN: usize = 24;
+const DESCRIPTION_LEN: usize = 64;
+const RELATIONSHIP_LEN: usize = 64;
+
+const HEADER_LEN: usize = 16 + SECTION_COUNT * 16;
+
+/// A read-only SNOMED CT store queried in place from a memory-mapped file.
+///
+/// Answers the same lookups as [`SnomedStore`] for concepts, descriptions
+/// and both relationship hierarchies. Concepts and relationships are small
+/// fixed-size records and are returned by value; descriptions are returned
+/// as [`DescriptionView`]s borrowing their text from the mapping.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::{MappedStore, SnapshotSource, SnomedStore};
+///
+/// // Once, after loading the release
+/// let source = SnapshotSource::new(data_path, &files, "default");
+/// MappedStore::write(&store, "snomed.store", &source)?;
+///
+/// // In each service replica
+/// let store = MappedStore::open("snomed.store", &source)?;
+/// if let Some(fsn) = store.get_fsn(73211009) {
+///     println!("{}", fsn.term);
+/// }
+/// let parents = store.get_parents(73211009);
+/// ```
+pub struct MappedStore {
+    path: PathBuf,
+    map: Mmap,
+    /// Byte range of each section in the map.
+    sections: Vec<Range<usize>>,
+}
+
+impl MappedStore {
+    /// Writes a store in the mapped layout.
+    ///
+    /// `source` records what the store was loaded from; see
+    /// [`open`](Self::open).
+    /// The file is written beside `path` and renamed into place, so a reader
+    /// never maps a partial file. On Unix, processes that have the old file
+    /// mapped keep their view of it; on Windows the rename fails with a
+    /// sharing violation while any process still maps the old file.
+    ///
+    /// # Errors
+    /// Returns an I/O error of kind `InvalidData` if a table outgrows the
+    /// layout's 32-bit offsets.
+    pub fn write<P: AsRef<Path>>(
+        store: &SnomedStore,
+        path: P,
+        source: &SnapshotSource,
+    ) -> Rf2Result<()> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        let result = LayoutWriter::create(&temp).and_then(|mut out| {
+            write_layout(store, source, &mut out)?;
+            out.finish()
+        });
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp);
+            return Err(e.into());
+        }
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Maps a file written by [`write`](Self::write).
+    ///
+    /// The header and the shape of every table are checked up front, so
+    /// queries never read outside the mapping.
+    ///
+    /// # Errors
+    /// Returns [`Rf2Error::InvalidSnapshot`] if the file is not a mapped
+    /// store, was written with another format version, is damaged, or was
+    /// built from another source than `expected`.
+    pub fn open<P: AsRef<Path>>(path: P, expected: &SnapshotSource) -> Rf2Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Rf2Error::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only. Like `MappedFile`, the store assumes
+        // the file is not truncated while mapped; `write` replaces files by
+        // renaming, which leaves existing mappings intact.
+        let map = unsafe { Mmap::map(&file)? };
+        let sections = read_sections(&map).map_err(|message| invalid(path, message))?;
+
+        let store = Self {
+            path: path.to_path_buf(),
+            map,
+            sections,
+        };
+        store.validate().map_err(|message| invalid(path, message))?;
+
+        let source = read_source(&store.map[store.sections[SOURCE].clone()])
+            .map_err(|message| invalid(path, message))?;
+        if source != *expected {
+            let message = format!("built from {}, expected {}", source, expected);
+            return Err(invalid(path, message));
+        }
+        Ok(store)
+    }
+
+    /// Returns the path of the mapped file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Query methods
+
+    /// Gets a concept by its ID.
+    pub fn get_concept(&self, id: SctId) -> Option<Rf2Concept> {
+        let index = find(self.table(CONCEPT_IDS, 8), id)?;
+        Some(self.concept_at(index))
+    }
+
+    /// Returns true if a concept exists in the store.
+    pub fn has_concept(&self, id: SctId) -> bool {
+        find(self.table(CONCEPT_IDS, 8), id).is_some()
+    }
+
+    /// Gets all descriptions for a concept.
+    pub fn get_descriptions(&self, concept_id: SctId) -> Vec<DescriptionView<'_>> {
+        self.group(DESCRIPTION_KEYS, DESCRIPTION_OFFSETS, concept_id)
+            .map(|i| self.description_at(i))
+            .collect()
+    }
+
+    /// Gets the text definitions of a concept.
+    pub fn get_text_definitions(&self, concept_id: SctId) -> Vec<DescriptionView<'_>> {
+        self.group(DESCRIPTION_KEYS, DESCRIPTION_OFFSETS, concept_id)
+            .map(|i| self.description_at(i))
+            .filter(|d| d.is_definition())
+            .collect()
+    }
+
+    /// Gets the FSN (Fully Specified Name) for a concept.
+    pub fn get_fsn(&self, concept_id: SctId) -> Option<DescriptionView<'_>> {
+        self.group(DESCRIPTION_KEYS, DESCRIPTION_OFFSETS, concept_id)
+            .map(|i| self.description_at(i))
+            .find(|d| d.is_fsn())
+    }
+
+    /// Gets relationships where this concept is the source.
+    pub fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        self.outgoing(Hierarchy::Inferred, source_id).collect()
+    }
+
+    /// Gets relationships where this concept is the destination.
+    pub fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship> {
+        self.incoming(Hierarchy::Inferred, destination_id).collect()
+    }
+
+    /// Gets stated relationships where this concept is the source.
+    pub fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        self.outgoing(Hierarchy::Stated, source_id).collect()
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the inferred hierarchy.
+    pub fn get_parents(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_parents_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the inferred hierarchy.
+    pub fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_children_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the given hierarchy.
+    pub fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.outgoing(hierarchy, concept_id)
+            .filter(|r| r.is_is_a())
+            .map(|r| r.destination_id)
+            .collect()
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the given hierarchy.
+    pub fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.incoming(hierarchy, concept_id)
+            .filter(|r| r.is_is_a())
+            .map(|r| r.source_id)
+            .collect()
+    }
+
+    // Statistics
+
+    /// Returns the number of concepts in the store.
+    pub fn concept_count(&self) -> usize {
+        self.table(CONCEPT_IDS, 8).len()
+    }
+
+    /// Returns the number of descriptions in the store.
+    pub fn description_count(&self) -> usize {
+        self.table(DESCRIPTIONS, DESCRIPTION_LEN).len()
+    }
+
+    /// Returns the number of relationships in the store.
+    pub fn relationship_count(&self) -> usize {
+        self.table(INFERRED + RELATIONSHIPS, RELATIONSHIP_LEN).len()
+    }
+
+    /// Returns the number of stated relationships in the store.
+    pub fn stated_relationship_count(&self) -> usize {
+        self.table(STATED + RELATIONSHIPS, RELATIONSHIP_LEN).len()
+    }
+
+    /// Returns an iterator over all concepts, in id order.
+    pub fn concepts(&self) -> impl Iterator<Item = Rf2Concept> + '_ {
+        (0..self.concept_count()).map(|i| self.concept_at(i))
+    }
+
+    /// Returns an iterator over all concept IDs, in order.
+    pub fn concept_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        let ids = self.table(CONCEPT_IDS, 8);
+        (0..ids.len()).map(move |i| ids.u64(i))
+    }
+
+    /// Returns the size of the mapped file in bytes.
+    ///
+    /// The mapping lives in the page cache and is shared between processes
+    /// that map the same file.
+    pub fn mapped_bytes(&self) -> usize {
+        self.map.len()
+    }
+
+    fn table(&self, section: usize, width: usize) -> Table<'_> {
+        Table {
+            bytes: &self.map[self.sections[section].clone()],
+            width,
+        }
+    }
+
+    /// Row range of `id` in a grouped index.
+    fn group(&self, keys: usize, offsets: usize, id: SctId) -> Range<usize> {
+        let offsets = self.table(offsets, 4);
+        match find(self.table(keys, 8), id) {
+            Some(i) => offsets.u32(i) as usize..offsets.u32(i + 1) as usize,
+            None => 0..0,
+        }
+    }
+
+    fn outgoing(
+        &self,
+        hierarchy: Hierarchy,
+        source_id: SctId,
+    ) -> impl Iterator<Item = Rf2Relationship> + '_ {
+        let block = block_of(hierarchy);
+        self.group(block + SOURCE_KEYS, block + SOURCE_OFFSETS, source_id)
+            .map(move |i| self.relationship_at(block, i))
+    }
+
+    fn incoming(
+        &self,
+        hierarchy: Hierarchy,
+        destination_id: SctId,
+    ) -> impl Iterator<Item = Rf2Relationship> + '_ {
+        let block = block_of(hierarchy);
+        let rows = self.table(block + DESTINATION_ROWS, 4);
+        self.group(
+            block + DESTINATION_KEYS,
+            block + DESTINATION_OFFSETS,
+            destination_id,
+        )
+        .map(move |i| self.relationship_at(block, rows.u32(i) as usize))
+    }
+
+    fn concept_at(&self, index: usize) -> Rf2Concept {
+        let mut row = Row(self.table(CONCEPTS, CONCEPT_LEN).row(index));
+        Rf2Concept {
+            id: self.table(CONCEPT_IDS, 8).u64(index),
+            module_id: row.u64(),
+            definition_status_id: row.u64(),
+            effective_time: row.u32(),
+            active: row.u32() != 0,
+        }
+    }
+
+    fn description_at(&self, index: usize) -> DescriptionView<'_> {
+        let mut row = Row(self.table(DESCRIPTIONS, DESCRIPTION_LEN).row(index));
+        let strings = &self.map[self.sections[STRINGS].clone()];
+        // Each string is checked as it is read, so opening never scans the arena
+        let text = |row: &mut Row<'_>| {
+            let (start, len) = (row.u32() as usize, row.u32() as usize);
+            strings
+                .get(start..start + len)
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .unwrap_or_default()
+        };
+        DescriptionView {
+            id: row.u64(),
+            module_id: row.u64(),
+            concept_id: row.u64(),
+            type_id: row.u64(),
+            case_significance_id: row.u64(),
+            effective_time: row.u32(),
+            active: row.u32() != 0,
+            language_code: text(&mut row),
+            term: text(&mut row),
+        }
+    }
+
+    fn relationship_at(&self, block: usize, index: usize) -> Rf2Relationship {
+        let mut row = Row(self
+            .table(block + RELATIONSHIPS, RELATIONSHIP_LEN)
+            .row(index));
+        Rf2Relationship {
+            id: row.u64(),
+            module_id: row.u64(),
+            source_id: row.u64(),
+            destination_id: row.u64(),
+            type_id: row.u64(),
+            characteristic_type_id: row.u64(),
+            modifier_id: row.u64(),
+            effective_time: row.u32(),
+            relationship_group: row.u16(),
+            active: row.u16() != 0,
+        }
+    }
+
+    /// Checks that the tables fit together, so lookups stay in bounds.
+    fn validate(&self) -> Result<(), String> {
+        let widths = [
+            (CONCEPT_IDS, 8),
+            (CONCEPTS, CONCEPT_LEN),
+            (DESCRIPTION_KEYS, 8),
+            (DESCRIPTION_OFFSETS, 4),
+            (DESCRIPTIONS, DESCRIPTION_LEN),
+        ];
+        let blocks = [INFERRED, STATED].into_iter().flat_map(|block| {
+            [
+                (block + RELATIONSHIPS, RELATIONSHIP_LEN),
+                (block + SOURCE_KEYS, 8),
+                (block + SOURCE_OFFSETS, 4),
+                (block + DESTINATION_KEYS, 8),
+                (block + DESTINATION_OFFSETS, 4),
+                (block + DESTINATION_ROWS, 4),
+            ]
+        });
+        for (section, width) in widths.into_iter().chain(blocks) {
+            if self.table(section, width).len() * width != self.sections[section].len() {
+                return Err(format!("section {} is not a table of {}", section, width));
+            }
+        }
+
+        if self.table(CONCEPT_IDS, 8).len() != self.table(CONCEPTS, CONCEPT_LEN).len() {
+            return Err("concept ids and records differ in length".to_string());
+        }
+        self.check_group(
+            DESCRIPTION_KEYS,
+            DESCRIPTION_OFFSETS,
+            self.description_count(),
+        )?;
+        for block in [INFERRED, STATED] {
+            let records = self.table(block + RELATIONSHIPS, RELATIONSHIP_LEN).len();
+            let rows = self.table(block + DESTINATION_ROWS, 4);
+            self.check_group(block + SOURCE_KEYS, block + SOURCE_OFFSETS, records)?;
+            self.check_group(
+                block + DESTINATION_KEYS,
+                block + DESTINATION_OFFSETS,
+                rows.len(),
+            )?;
+            if (0..rows.len()).any(|i| rows.u32(i) as usize >= records) {
+                return Err("destination index points past the relationships".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn check_group(&self, keys: usize, offsets: usize, rows: usize) -> Result<(), String> {
+        let section = offsets;
+        let (keys, offsets) = (self.table(keys, 8), self.table(offsets, 4));
+        let ordered = (1..offsets.len()).all(|i| offsets.u32(i - 1) <= offsets.u32(i));
+        if offsets.len() != keys.len() + 1 || !ordered || offsets.u32(keys.len()) as usize != rows {
+            return Err(format!("offset table {} does not match its rows", section));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for MappedStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedStore")
+            .field("path", &self.path)
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+/// First section of a hierarchy's relationship block.
+fn block_of(hierarchy: Hierarchy) -> usize {
+    match hierarchy {
+        Hierarchy::Inferred => INFERRED,
+        Hierarchy::Stated => STATED,
+    }
+}
+
+fn invalid(path: &Path, message: String) -> Rf2Error {
+    Rf2Error::InvalidSnapshot {
+        path: path.display().to_string(),
+        message,
+    }
+}
+
+/// A table of fixed-width little-endian rows.
+#[derive(Clone, Copy)]
+struct Table<'a> {
+    bytes: &'a [u8],
+    width: usize,
+}
+
+impl<'a> Table<'a> {
+    fn len(&self) -> usize {
+        self.bytes.len() / self.width
+    }
+
+    fn row(&self, index: usize) -> &'a [u8] {
+        &self.bytes[index * self.width..(index + 1) * self.width]
+    }
+
+    fn u64(&self, index: usize) -> u64 {
+        Row(self.row(index)).u64()
+    }
+
+    fn u32(&self, index: usize) -> u32 {
+        Row(self.row(index)).u32()
+    }
+}
+
+/// Binary search for `id` in a sorted table of ids.
+fn find(ids: Table<'_>, id: SctId) -> Option<usize> {
+    let (mut low, mut high) = (0, ids.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match ids.u64(mid).cmp(&id) {
+            std::cmp::Ordering::Less => low = mid + 1,
+            std::cmp::Ordering::Greater => high = mid,
+            std::cmp::Ordering::Equal => return Some(mid),
+        }
+    }
+    None
+}
+
+/// Reads the fields of a record in order.
+struct Row<'a>(&'a [u8]);
+
+impl Row<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (field, rest) = self.0.split_at(N);
+        self.0 = rest;
+        field.try_into().unwrap()
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take())
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take())
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take())
+    }
+}
+
+/// Reads and bounds-checks the section table.
+fn read_sections(data: &[u8]) -> Result<Vec<Range<usize>>, String> {
+    if data.len() < 16 || &data[..8] != MAGIC {
+        return Err("not a mapped store".to_string());
+    }
+    let mut header = Row(&data[8..]);
+    let version = header.u32();
+    if version != MAPPED_STORE_VERSION {
+        return Err(format!(
+            "format version {} is not supported (expected {})",
+            version, MAPPED_STORE_VERSION
+        ));
+    }
+    if header.u32() as usize != SECTION_COUNT || data.len() < HEADER_LEN {
+        return Err("unexpected section table".to_string());
+    }
+
+    (0..SECTION_COUNT)
+        .map(|section| {
+            let (start, len) = (header.u64(), header.u64());
+            start
+                .checked_add(len)
+                .filter(|&end| end <= data.len() as u64)
+                .map(|end| start as usize..end as usize)
+                .ok_or_else(|| format!("section {} runs past the end of the file", section))
+        })
+        .collect()
+}
+
+/// Reads the source section written by [`write_layout`].
+fn read_source(bytes: &[u8]) -> Result<SnapshotSource, String> {
+    let mut rest = bytes;
+    let mut text = || {
+        if rest.len() < 4 {
+            return Err("source section is truncated".to_string());
+        }
+        let len = Row(rest).u32();
+        rest = &rest[4..];
+        if len == u32::MAX {
+            return Ok(None);
+        }
+        if rest.len() < len as usize {
+            return Err("source section is truncated".to_string());
+        }
+        let (text, tail) = rest.split_at(len as usize);
+        rest = tail;
+        match std::str::from_utf8(text) {
+            Ok(text) => Ok(Some(text.to_string())),
+            Err(_) => Err("source section is not UTF-8".to_string()),
+        }
+    };
+    Ok(SnapshotSource {
+        data_path: text()?.unwrap_or_default(),
+        release_date: text()?,
+        options: text()?.unwrap_or_default(),
+    })
+}
+
+/// Writes sections one after another and fills in the section table.
+struct LayoutWriter {
+    file: BufWriter<File>,
+    position: u64,
+    sections: Vec<(u64, u64)>,
+}
+
+impl LayoutWriter {
+    fn create(path: &Path) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&[0; HEADER_LEN])?;
+        Ok(Self {
+            file,
+            position: HEADER_LEN as u64,
+            sections: Vec::with_capacity(SECTION_COUNT),
+        })
+    }
+
+    fn section(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let padding = self.position.next_multiple_of(8) - self.position;
+        self.file.write_all(&[0; 8][..padding as usize])?;
+        self.position += padding;
+
+        self.sections.push((self.position, bytes.len() as u64));
+        self.file.write_all(bytes)?;
+        self.position += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        debug_assert_eq!(self.sections.len(), SECTION_COUNT);
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(MAGIC)?;
+        self.file.write_all(&MAPPED_STORE_VERSION.to_le_bytes())?;
+        self.file.write_all(&(SECTION_COUNT as u32).to_le_bytes())?;
+        for (start, len) in &self.sections {
+            self.file.write_all(&start.to_le_bytes())?;
+            self.file.write_all(&len.to_le_bytes())?;
+        }
+        self.file
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        Ok(())
+    }
+}
+
+/// Writes every section of a store.
+fn write_layout(
+    store: &SnomedStore,
+    source: &SnapshotSource,
+    out: &mut LayoutWriter,
+) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    for text in [
+        Some(&source.data_path),
+        source.release_date.as_ref(),
+        Some(&source.options),
+    ] {
+        match text {
+            Some(text) => {
+                bytes.extend(offset(text.len())?.to_le_bytes());
+                bytes.extend_from_slice(text.as_bytes());
+            }
+            None => bytes.extend(u32::MAX.to_le_bytes()),
+        }
+    }
+    out.section(&bytes)?;
+
+    let mut concepts: Vec<&Rf2Concept> = store.concepts().collect();
+    concepts.sort_unstable_by_key(|c| c.id);
+    out.section(&ids(concepts.iter().map(|c| c.id)))?;
+    let mut records = Vec::with_capacity(concepts.len() * CONCEPT_LEN);
+    for concept in concepts {
+        records.extend(concept.module_id.to_le_bytes());
+        records.extend(concept.definition_status_id.to_le_bytes());
+        records.extend(concept.effective_time.to_le_bytes());
+        records.extend(u32::from(concept.active).to_le_bytes());
+    }
+    out.section(&records)?;
+
+    let mut descriptions: Vec<&Rf2Description> = store.descriptions().collect();
+    descriptions.sort_by_key(|d| d.concept_id);
+    let (keys, offsets) = group_offsets(descriptions.iter().map(|d| d.concept_id))?;
+    out.section(&keys)?;
+    out.section(&offsets)?;
+
+    let mut strings = StringArena::default();
+    let mut records = Vec::with_capacity(descriptions.len() * DESCRIPTION_LEN);
+    for description in descriptions {
+        records.extend(description.id.to_le_bytes());
+        records.extend(description.module_id.to_le_bytes());
+        records.extend(description.concept_id.to_le_bytes());
+        records.extend(description.type_id.to_le_bytes());
+        records.extend(description.case_significance_id.to_le_bytes());
+        records.extend(description.effective_time.to_le_bytes());
+        records.extend(u32::from(description.active).to_le_bytes());
+        records.extend(strings.intern(&description.language_code)?);
+        records.extend(strings.push(&description.term)?);
+    }
+    out.section(&records)?;
+    out.section(&strings.bytes)?;
+
+    for hierarchy in [Hierarchy::Inferred, Hierarchy::Stated] {
+        write_relationships(store, hierarchy, out)?;
+    }
+    Ok(())
+}
+
+fn write_relationships(
+    store: &SnomedStore,
+    hierarchy: Hierarchy,
+    out: &mut LayoutWriter,
+) -> io::Result<()> {
+    let mut relationships: Vec<&Rf2Relationship> = store.relationships_in(hierarchy).collect();
+    relationships.sort_by_key(|r| r.source_id);
+    offset(relationships.len())?;
+
+    let mut records = Vec::with_capacity(relationships.len() * RELATIONSHIP_LEN);
+    for rel in &relationships {
+        for id in [
+            rel.id,
+            rel.module_id,
+            rel.source_id,
+            rel.destination_id,
+            rel.type_id,
+            rel.characteristic_type_id,
+            rel.modifier_id,
+        ] {
+            records.extend(id.to_le_bytes());
+        }
+        records.extend(rel.effective_time.to_le_bytes());
+        records.extend(rel.relationship_group.to_le_bytes());
+        records.extend(u16::from(rel.active).to_le_bytes());
+    }
+    out.section(&records)?;
+
+    let (keys, offsets) = group_offsets(relationships.iter().map(|r| r.source_id))?;
+    out.section(&keys)?;
+    out.section(&offsets)?;
+
+    // Record numbers in destination order
+    let mut rows: Vec<u32> = (0..relationships.len() as u32).collect();
+    rows.sort_by_key(|&i| relationships[i as usize].destination_id);
+    let (keys, offsets) = group_offsets(
+        rows.iter()
+            .map(|&i| relationships[i as usize].destination_id),
+    )?;
+    out.section(&keys)?;
+    out.section(&offsets)?;
+    out.section(
+        &rows
+            .iter()
+            .flat_map(|i| i.to_le_bytes())
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn ids(ids: impl Iterator<Item = SctId>) -> Vec<u8> {
+    ids.flat_map(u64::to_le_bytes).collect()
+}
+
+/// Builds the sorted ids and the offset table of rows grouped by id.
+fn group_offsets(keys: impl Iterator<Item = SctId>) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut ids = Vec::new();
+    let mut offsets = Vec::new();
+    let mut last = None;
+    let mut rows = 0;
+    for key in keys {
+        if last != Some(key) {
+            ids.extend(key.to_le_bytes());
+            offsets.extend(offset(rows)?.to_le_bytes());
+            last = Some(key);
+        }
+        rows += 1;
+    }
+    offsets.extend(offset(rows)?.to_le_bytes());
+    Ok((ids, offsets))
+}
+
+/// Converts a row or byte count to a 32-bit offset.
+fn offset(value: usize) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} does not fit the mapped store's 32-bit offsets", value),
+        )
+    })
+}
+
+/// UTF-8 string arena; strings are referenced as (offset, length) pairs.
+#[derive(Default)]
+struct StringArena {
+    bytes: Vec<u8>,
+    /// Interned strings, e.g. language codes.
+    interned: Vec<(String, [u8; 8])>,
+}
+
+impl StringArena {
+    fn push(&mut self, text: &str) -> io::Result<[u8; 8]> {
+        let start = offset(self.bytes.len())?;
+        let len = offset(text.len())?;
+        self.bytes.extend_from_slice(text.as_bytes());
+        offset(self.bytes.len())?;
+
+        let mut reference = [0; 8];
+        reference[..4].copy_from_slice(&start.to_le_bytes());
+        reference[4..].copy_from_slice(&len.to_le_bytes());
+        Ok(reference)
+    }
+
+    fn intern(&mut self, text: &str) -> io::Result<[u8; 8]> {
+        if let Some((_, reference)) = self.interned.iter().find(|(s, _)| s == text) {
+            return Ok(*reference);
+        }
+        let reference = self.push(text)?;
+        self.interned.push((text.to_string(), reference));
+        Ok(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_types::{well_known, CharacteristicType, DescriptionType};
+
+    fn concept(id: SctId) -> Rf2Concept {
+        Rf2Concept {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }
+    }
+
+    fn description(id: SctId, concept_id: SctId, type_id: SctId, term: &str) -> Rf2Description {
+        Rf2Description {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            concept_id,
+            language_code: "en".to_string(),
+            type_id,
+            term: term.to_string(),
+            case_significance_id: 900000000000448009,
+        }
+    }
+
+    /// An inferred IS A relationship in group 0.
+    fn is_a(id: SctId, source_id: SctId, destination_id: SctId) -> Rf2Relationship {
+        Rf2Relationship {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            source_id,
+            destination_id,
+            relationship_group: 0,
+            type_id: Rf2Relationship::IS_A_TYPE_ID,
+            characteristic_type_id: CharacteristicType::INFERRED_ID,
+            modifier_id: 900000000000451002,
+        }
+    }
+
+    fn make_store() -> SnomedStore {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([
+            concept(300),
+            concept(100),
+            concept(200),
+            Rf2Concept {
+                active: false,
+                ..concept(400)
+            },
+        ]);
+        store.insert_descriptions([
+            description(1, 100, DescriptionType::FSN_ID, "Root (root)"),
+            description(2, 200, DescriptionType::SYNONYM_ID, "Child"),
+            description(3, 200, DescriptionType::FSN_ID, "Child (finding)"),
+            description(
+                4,
+                200,
+                DescriptionType::DEFINITION_ID,
+                "A \"child\" concept",
+            ),
+            Rf2Description {
+                language_code: "sv".to_string(),
+                ..description(5, 300, DescriptionType::SYNONYM_ID, "Barn")
+            },
+        ]);
+        store.insert_relationships([
+            is_a(11, 200, 100),
+            is_a(12, 300, 100),
+            Rf2Relationship {
+                relationship_group: 1,
+                type_id: well_known::FINDING_SITE,
+                ..is_a(13, 300, 200)
+            },
+            Rf2Relationship {
+                relationship_group: 2,
+                ..is_a(14, 300, 200)
+            },
+            Rf2Relationship {
+                characteristic_type_id: CharacteristicType::STATED_ID,
+                ..is_a(20, 300, 100)
+            },
+        ]);
+        store
+    }
+
+    fn make_source() -> SnapshotSource {
+        SnapshotSource {
+            data_path: "/releases/international".to_string(),
+            release_date: Some("20250101".to_string()),
+            options: "profile=default strict=false".to_string(),
+        }
+    }
+
+    fn write_store(store: &SnomedStore) -> (tempfile::TempDir, MappedStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snomed.store");
+        MappedStore::write(store, &path, &make_source()).unwrap();
+        let mapped = MappedStore::open(&path, &make_source()).unwrap();
+        (dir, mapped)
+    }
+
+    #[test]
+    fn test_mapped_store_answers_like_snomed_store() {
+        let store = make_store();
+        let (_dir, mapped) = write_store(&store);
+
+        assert_eq!(mapped.concept_count(), store.concept_count());
+        assert_eq!(mapped.description_count(), store.description_count());
+        assert_eq!(mapped.relationship_count(), store.relationship_count());
+        assert_eq!(
+            mapped.stated_relationship_count(),
+            store.stated_relationship_count()
+        );
+        assert_eq!(
+            mapped.concept_ids().collect::<Vec<_>>(),
+            vec![100, 200, 300, 400]
+        );
+
+        for id in [100, 200, 300, 400, 500] {
+            assert_eq!(mapped.get_concept(id).as_ref(), store.get_concept(id));
+            assert_eq!(mapped.has_concept(id), store.has_concept(id));
+
+            let descriptions: Vec<Rf2Description> = mapped
+                .get_descriptions(id)
+                .iter()
+                .map(DescriptionView::to_description)
+                .collect();
+            assert_eq!(
+                descriptions,
+                store.get_descriptions(id).cloned().unwrap_or_default()
+            );
+            assert_eq!(
+                mapped.get_fsn(id).map(|d| d.to_description()).as_ref(),
+                store.get_fsn(id)
+            );
+            assert_eq!(
+                mapped.get_text_definitions(id).len(),
+                store.get_text_definitions(id).len()
+            );
+
+            assert_eq!(
+                mapped.get_outgoing_relationships(id),
+                store
+                    .get_outgoing_relationships(id)
+                    .cloned()
+                    .unwrap_or_default()
+            );
+            assert_eq!(
+                mapped.get_incoming_relationships(id),
+                store
+                    .get_incoming_relationships(id)
+                    .cloned()
+                    .unwrap_or_default()
+            );
+            assert_eq!(
+                mapped.get_stated_relationships(id),
+                store.get_stated_relationships(id)
+            );
+            for hierarchy in [Hierarchy::Inferred, Hierarchy::Stated] {
+                assert_eq!(
+                    mapped.get_parents_in(id, hierarchy),
+                    store.get_parents_in(id, hierarchy)
+                );
+                assert_eq!(
+                    mapped.get_children_in(id, hierarchy),
+                    store.get_children_in(id, hierarchy)
+                );
+            }
+        }
+
+        assert_eq!(mapped.get_parents(300), vec![100, 200]);
+        assert_eq!(mapped.get_children(100), vec![200, 300]);
+        assert_eq!(mapped.get_parents_in(300, Hierarchy::Stated), vec![100]);
+        assert_eq!(mapped.get_fsn(200).unwrap().term, "Child (finding)");
+        assert_eq!(mapped.get_descriptions(300)[0].language_code, "sv");
+    }
+
+    #[test]
+    fn test_mapped_store_of_empty_store() {
+        let (_dir, mapped) = write_store(&SnomedStore::new());
+
+        assert_eq!(mapped.concept_count(), 0);
+        assert!(mapped.get_concept(100).is_none());
+        assert!(mapped.get_descriptions(100).is_empty());
+        assert!(mapped.get_children(100).is_empty());
+    }
+
+    #[test]
+    fn test_open_rejects_damaged_files() {
+        let (dir, mapped) = write_store(&make_store());
+        let path = mapped.path().to_path_buf();
+        drop(mapped);
+        let image = fs::read(&path).unwrap();
+
+        let mut version = image.clone();
+        version[8] = 9;
+        let mut offsets = image.clone();
+        // Point the concept records past the end of the file
+        offsets[16 + CONCEPTS * 16..16 + CONCEPTS * 16 + 8]
+            .copy_from_slice(&(image.len() as u64).to_le_bytes());
+        let damaged = [
+            (b"SNOMEDSS".to_vec(), "not a mapped store"),
+            (version, "format version 9"),
+            (offsets, "runs past the end"),
+            (image[..image.len() - 4].to_vec(), "runs past the end"),
+        ];
+
+        let copy = dir.path().join("damaged.store");
+        for (bytes, reason) in damaged {
+            fs::write(&copy, bytes).unwrap();
+            match MappedStore::open(&copy, &make_source()) {
+                Err(Rf2Error::InvalidSnapshot { message, .. }) => {
+                    assert!(message.contains(reason), "{}", message)
+                }
+                other => panic!("expected InvalidSnapshot, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_rejects_other_sources() {
+        let (_dir, mapped) = write_store(&make_store());
+        let path = mapped.path().to_path_buf();
+        drop(mapped);
+
+        let others = [
+            SnapshotSource {
+                data_path: "/releases/uk".to_string(),
+                ..make_source()
+            },
+            SnapshotSource {
+                release_date: Some("20250201".to_string()),
+                ..make_source()
+            },
+            SnapshotSource {
+                release_date: None,
+                ..make_source()
+            },
+            SnapshotSource {
+                options: "profile=full strict=false".to_string(),
+                ..make_source()
+            },
+        ];
+
+        for expected in others {
+            match MappedStore::open(&path, &expected) {
+                Err(Rf2Error::InvalidSnapshot { message, .. }) => {
+                    assert!(message.contains("built from"), "{}", message)
+                }
+                other => panic!("expected InvalidSnapshot, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_undated_source_round_trips() {
+        let source = SnapshotSource {
+            release_date: None,
+            ..make_source()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snomed.store");
+        MappedStore::write(&make_store(), &path, &source).unwrap();
+
+        assert!(MappedStore::open(&path, &source).is_ok());
+    }
+}
@@ -1,127 +1,58 @@
 //! MRCM Attribute Domain reference set parser.
 //!
-//! Parses files matching pattern: `der2_cRefset_MRCMAttributeDomainSnapshot_*.txt`
+//! Parses files matching pattern: `der2_cissccRefset_MRCMAttributeDomainSnapshot_*.txt`
 
 use std::path::Path;
 
-use csv::StringRecord;
-use snomed_types::{Cardinality, MrcmAttributeDomain};
+use snomed_types::{Cardinality, MrcmAttributeDomain, RefsetFieldType, RefsetMember};
 
-use crate::parser::{parse, Rf2Parser, Rf2Record};
+use crate::refset::{parse_refset_view_file, MemberValues, RefsetParser, RefsetView};
+use crate::source::Rf2Reader;
 use crate::types::{Rf2Config, Rf2Error, Rf2Result};
 
-/// Expected columns for MRCM Attribute Domain reference set.
-///
-/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId,
-/// domainId, grouped, attributeCardinality, attributeInGroupCardinality,
-/// ruleStrengthId, contentTypeId
-const ATTRIBUTE_DOMAIN_COLUMNS: &[&str] = &[
-    "id",
-    "effectiveTime",
-    "active",
-    "moduleId",
-    "refsetId",
-    "referencedComponentId",
-    "domainId",
-    "grouped",
-    "attributeCardinality",
-    "attributeInGroupCardinality",
-    "ruleStrengthId",
-    "contentTypeId",
-];
-
-impl Rf2Record for MrcmAttributeDomain {
-    const EXPECTED_COLUMNS: &'static [&'static str] = ATTRIBUTE_DOMAIN_COLUMNS;
-
-    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
-        let id = record
-            .get(0)
-            .ok_or_else(|| Rf2Error::MissingColumn {
-                column: "id".to_string(),
-            })?
-            .to_string();
-
-        let effective_time = parse::effective_time(record.get(1).ok_or_else(|| {
-            Rf2Error::MissingColumn {
-                column: "effectiveTime".to_string(),
-            }
-        })?)?;
-
-        let active = parse::boolean(record.get(2).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "active".to_string(),
-        })?)?;
-
-        let module_id = parse::sctid(record.get(3).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "moduleId".to_string(),
-        })?)?;
-
-        let refset_id = parse::sctid(record.get(4).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "refsetId".to_string(),
-        })?)?;
-
-        let referenced_component_id =
-            parse::sctid(record.get(5).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "referencedComponentId".to_string(),
-            })?)?;
-
-        let domain_id = parse::sctid(record.get(6).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "domainId".to_string(),
-        })?)?;
-
-        let grouped = parse::boolean(record.get(7).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "grouped".to_string(),
-        })?)?;
-
-        let attribute_cardinality_str =
-            record.get(8).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "attributeCardinality".to_string(),
-            })?;
-        let attribute_cardinality =
-            Cardinality::parse(attribute_cardinality_str).map_err(|_| Rf2Error::InvalidInteger {
-                value: attribute_cardinality_str.to_string(),
-            })?;
-
-        let attribute_in_group_cardinality_str =
-            record.get(9).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "attributeInGroupCardinality".to_string(),
-            })?;
-        let attribute_in_group_cardinality = Cardinality::parse(attribute_in_group_cardinality_str)
-            .map_err(|_| Rf2Error::InvalidInteger {
-                value: attribute_in_group_cardinality_str.to_string(),
-            })?;
-
-        let rule_strength_id =
-            parse::sctid(record.get(10).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "ruleStrengthId".to_string(),
-            })?)?;
+/// Parses a cardinality column such as `0..*`.
+fn cardinality(value: String) -> Rf2Result<Cardinality> {
+    Cardinality::parse(&value).map_err(|_| Rf2Error::InvalidInteger { value })
+}
 
-        let content_type_id =
-            parse::sctid(record.get(11).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "contentTypeId".to_string(),
-            })?)?;
+/// Parses the `grouped` column, which holds 0 or 1.
+fn grouped(value: i64) -> Rf2Result<bool> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(Rf2Error::InvalidBoolean {
+            value: other.to_string(),
+        }),
+    }
+}
 
+impl RefsetView for MrcmAttributeDomain {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] = &[
+        ("domainId", RefsetFieldType::Component),
+        ("grouped", RefsetFieldType::Integer),
+        ("attributeCardinality", RefsetFieldType::String),
+        ("attributeInGroupCardinality", RefsetFieldType::String),
+        ("ruleStrengthId", RefsetFieldType::Component),
+        ("contentTypeId", RefsetFieldType::Component),
+    ];
+
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        let mut values = MemberValues::new::<Self>(member.values);
         Ok(MrcmAttributeDomain {
-            id,
-            effective_time,
-            active,
-            module_id,
-            refset_id,
-            referenced_component_id,
-            domain_id,
-            grouped,
-            attribute_cardinality,
-            attribute_in_group_cardinality,
-            rule_strength_id,
-            content_type_id,
+            id: member.id,
+            effective_time: member.effective_time,
+            active: member.active,
+            module_id: member.module_id,
+            refset_id: member.refset_id,
+            referenced_component_id: member.referenced_component_id,
+            domain_id: values.component()?,
+            grouped: values.integer_with(grouped)?,
+            attribute_cardinality: values.string_with(cardinality)?,
+            attribute_in_group_cardinality: values.string_with(cardinality)?,
+            rule_strength_id: values.component()?,
+            content_type_id: values.component()?,
         })
     }
-
-    fn passes_filter(&self, config: &Rf2Config) -> bool {
-        if config.active_only && !self.active {
-            return false;
-        }
-        true
-    }
 }
 
 /// Parses MRCM Attribute Domain reference set from a file.
@@ -135,158 +66,147 @@ impl Rf2Record for MrcmAttributeDomain {
 pub fn parse_attribute_domain_file<P: AsRef<Path>>(
     path: P,
     config: Rf2Config,
-) -> Rf2Result<impl Iterator<Item = Rf2Result<MrcmAttributeDomain>>> {
-    let parser = Rf2Parser::<_, MrcmAttributeDomain>::from_path(path, config)?;
-    Ok(parser)
+) -> Rf2Result<RefsetParser<Rf2Reader, MrcmAttributeDomain>> {
+    parse_refset_view_file(path, config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::refset::{parse_refset_view_reader, RefsetPattern};
     use snomed_types::well_known;
 
-    fn make_test_record() -> StringRecord {
-        StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440001",
-            "20240101",
-            "1",                    // active
-            "900000000000207008",   // module_id
-            "723604009",            // MRCM Attribute Domain refset
-            "363698007",            // Finding site attribute
-            "404684003",            // Clinical finding domain
-            "1",                    // grouped = true
-            "0..*",                 // attribute cardinality
-            "0..1",                 // attribute in group cardinality
-            "723597001",            // Mandatory rule
-            "723596005",            // All SNOMED CT content
-        ])
+    const HEADER: &str = "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\t\
+        domainId\tgrouped\tattributeCardinality\tattributeInGroupCardinality\t\
+        ruleStrengthId\tcontentTypeId";
+
+    /// Builds a Finding site row in the Clinical finding domain.
+    fn make_row(active: &str, grouped: &str, cardinalities: &str, rule_strength: &str) -> String {
+        format!(
+            "550e8400-e29b-41d4-a716-446655440001\t20240101\t{active}\t900000000000207008\t\
+             723604009\t363698007\t404684003\t{grouped}\t{cardinalities}\t{rule_strength}\t\
+             723596005"
+        )
+    }
+
+    fn parse_row(row: &str, config: Rf2Config) -> Vec<MrcmAttributeDomain> {
+        let contents = format!("{HEADER}\n{row}\n");
+        let pattern = RefsetPattern::from_file_name(
+            "der2_cissccRefset_MRCMAttributeDomainSnapshot_INT_20250101.txt",
+        )
+        .unwrap();
+        parse_refset_view_reader(contents.as_bytes(), pattern, config)
+            .unwrap()
+            .collect::<Rf2Result<_>>()
+            .unwrap()
+    }
+
+    fn parse_one(row: &str) -> MrcmAttributeDomain {
+        parse_row(row, Rf2Config::default()).remove(0)
     }
 
     #[test]
     fn test_parse_mrcm_attribute_domain() {
-        let record = make_test_record();
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
+        let attr_domain = parse_one(&make_row("1", "1", "0..*\t0..1", "723597001"));
 
         assert_eq!(attr_domain.id, "550e8400-e29b-41d4-a716-446655440001");
         assert_eq!(attr_domain.effective_time, 20240101);
         assert!(attr_domain.active);
         assert_eq!(attr_domain.module_id, 900000000000207008);
-        assert_eq!(attr_domain.refset_id, well_known::MRCM_ATTRIBUTE_DOMAIN_REFSET);
-        assert_eq!(attr_domain.referenced_component_id, well_known::FINDING_SITE);
+        assert_eq!(
+            attr_domain.refset_id,
+            well_known::MRCM_ATTRIBUTE_DOMAIN_REFSET
+        );
+        assert_eq!(
+            attr_domain.referenced_component_id,
+            well_known::FINDING_SITE
+        );
         assert_eq!(attr_domain.domain_id, well_known::CLINICAL_FINDING);
         assert!(attr_domain.grouped);
         assert_eq!(attr_domain.attribute_cardinality, Cardinality::unbounded());
-        assert_eq!(attr_domain.attribute_in_group_cardinality, Cardinality::optional());
-        assert_eq!(attr_domain.rule_strength_id, well_known::MANDATORY_CONCEPT_MODEL_RULE);
-        assert_eq!(attr_domain.content_type_id, well_known::ALL_SNOMED_CT_CONTENT);
+        assert_eq!(
+            attr_domain.attribute_in_group_cardinality,
+            Cardinality::optional()
+        );
+        assert_eq!(
+            attr_domain.rule_strength_id,
+            well_known::MANDATORY_CONCEPT_MODEL_RULE
+        );
+        assert_eq!(
+            attr_domain.content_type_id,
+            well_known::ALL_SNOMED_CT_CONTENT
+        );
     }
 
     #[test]
     fn test_is_mandatory() {
-        let record = make_test_record();
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
-
+        let attr_domain = parse_one(&make_row("1", "1", "0..*\t0..1", "723597001"));
         assert!(attr_domain.is_mandatory());
     }
 
     #[test]
     fn test_is_optional() {
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440001",
-            "20240101",
-            "1",
-            "900000000000207008",
-            "723604009",
-            "363698007",
-            "404684003",
-            "1",
-            "0..*",
-            "0..1",
-            "723598006", // Optional rule
-            "723596005",
-        ]);
-
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
+        // 723598006 is the optional rule strength.
+        let attr_domain = parse_one(&make_row("1", "1", "0..*\t0..1", "723598006"));
         assert!(!attr_domain.is_mandatory());
     }
 
     #[test]
     fn test_is_grouped() {
-        let record = make_test_record();
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
+        let attr_domain = parse_one(&make_row("1", "1", "0..*\t0..1", "723597001"));
         assert!(attr_domain.is_grouped());
 
-        let record_ungrouped = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440001",
-            "20240101",
-            "1",
-            "900000000000207008",
-            "723604009",
-            "363698007",
-            "404684003",
-            "0", // Not grouped
-            "0..*",
-            "0..1",
-            "723597001",
-            "723596005",
-        ]);
-
-        let attr_domain_ungrouped = MrcmAttributeDomain::from_record(&record_ungrouped).unwrap();
+        let attr_domain_ungrouped = parse_one(&make_row("1", "0", "0..*\t0..1", "723597001"));
         assert!(!attr_domain_ungrouped.is_grouped());
     }
 
     #[test]
     fn test_cardinality_parsing() {
-        // Test different cardinality values
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440001",
-            "20240101",
-            "1",
-            "900000000000207008",
-            "723604009",
-            "363698007",
-            "404684003",
-            "1",
-            "1..*", // At least one
-            "1..1", // Exactly one in group
-            "723597001",
-            "723596005",
-        ]);
+        let attr_domain = parse_one(&make_row("1", "1", "1..*\t1..1", "723597001"));
+        assert_eq!(
+            attr_domain.attribute_cardinality,
+            Cardinality::one_or_more()
+        );
+        assert_eq!(
+            attr_domain.attribute_in_group_cardinality,
+            Cardinality::required()
+        );
+    }
 
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
-        assert_eq!(attr_domain.attribute_cardinality, Cardinality::one_or_more());
-        assert_eq!(attr_domain.attribute_in_group_cardinality, Cardinality::required());
+    #[test]
+    fn test_invalid_grouped_flag() {
+        let contents = format!(
+            "{HEADER}\n{}\n",
+            make_row("1", "2", "0..*\t0..1", "723597001")
+        );
+        let pattern = RefsetPattern::from_file_name(
+            "der2_cissccRefset_MRCMAttributeDomainSnapshot_INT_20250101.txt",
+        )
+        .unwrap();
+        let result: Rf2Result<Vec<MrcmAttributeDomain>> =
+            parse_refset_view_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect();
+        assert!(matches!(
+            result,
+            Err(Rf2Error::Row(row)) if row.column == Some("grouped")
+        ));
     }
 
     #[test]
     fn test_filter_inactive() {
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440001",
-            "20240101",
-            "0", // Inactive
-            "900000000000207008",
-            "723604009",
-            "363698007",
-            "404684003",
-            "1",
-            "0..*",
-            "0..1",
-            "723597001",
-            "723596005",
-        ]);
-
-        let attr_domain = MrcmAttributeDomain::from_record(&record).unwrap();
+        let row = make_row("0", "1", "0..*\t0..1", "723597001");
 
         let config_active_only = Rf2Config {
             active_only: true,
             ..Default::default()
         };
-        assert!(!attr_domain.passes_filter(&config_active_only));
+        assert!(parse_row(&row, config_active_only).is_empty());
 
         let config_all = Rf2Config {
             active_only: false,
             ..Default::default()
         };
-        assert!(attr_domain.passes_filter(&config_all));
+        assert_eq!(parse_row(&row, config_all).len(), 1);
     }
 }
@@ -1,112 +1,38 @@
 //! MRCM Attribute Range reference set parser.
 //!
-//! Parses files matching pattern: `der2_cRefset_MRCMAttributeRangeSnapshot_*.txt`
+//! Parses files matching pattern: `der2_ssccRefset_MRCMAttributeRangeSnapshot_*.txt`
 
 use std::path::Path;
 
-use csv::StringRecord;
-use snomed_types::MrcmAttributeRange;
+use snomed_types::{MrcmAttributeRange, RefsetFieldType, RefsetMember};
 
-use crate::parser::{parse, Rf2Parser, Rf2Record};
-use crate::types::{Rf2Config, Rf2Error, Rf2Result};
+use crate::refset::{parse_refset_view_file, MemberValues, RefsetParser, RefsetView};
+use crate::source::Rf2Reader;
+use crate::types::{Rf2Config, Rf2Result};
 
-/// Expected columns for MRCM Attribute Range reference set.
-///
-/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId,
-/// rangeConstraint, attributeRule, ruleStrengthId, contentTypeId
-const ATTRIBUTE_RANGE_COLUMNS: &[&str] = &[
-    "id",
-    "effectiveTime",
-    "active",
-    "moduleId",
-    "refsetId",
-    "referencedComponentId",
-    "rangeConstraint",
-    "attributeRule",
-    "ruleStrengthId",
-    "contentTypeId",
-];
-
-impl Rf2Record for MrcmAttributeRange {
-    const EXPECTED_COLUMNS: &'static [&'static str] = ATTRIBUTE_RANGE_COLUMNS;
-
-    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
-        let id = record
-            .get(0)
-            .ok_or_else(|| Rf2Error::MissingColumn {
-                column: "id".to_string(),
-            })?
-            .to_string();
-
-        let effective_time = parse::effective_time(record.get(1).ok_or_else(|| {
-            Rf2Error::MissingColumn {
-                column: "effectiveTime".to_string(),
-            }
-        })?)?;
-
-        let active = parse::boolean(record.get(2).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "active".to_string(),
-        })?)?;
-
-        let module_id = parse::sctid(record.get(3).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "moduleId".to_string(),
-        })?)?;
-
-        let refset_id = parse::sctid(record.get(4).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "refsetId".to_string(),
-        })?)?;
-
-        let referenced_component_id =
-            parse::sctid(record.get(5).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "referencedComponentId".to_string(),
-            })?)?;
-
-        let range_constraint = record
-            .get(6)
-            .ok_or_else(|| Rf2Error::MissingColumn {
-                column: "rangeConstraint".to_string(),
-            })?
-            .to_string();
-
-        let attribute_rule_str = record.get(7).ok_or_else(|| Rf2Error::MissingColumn {
-            column: "attributeRule".to_string(),
-        })?;
-        let attribute_rule = if attribute_rule_str.is_empty() {
-            None
-        } else {
-            Some(attribute_rule_str.to_string())
-        };
-
-        let rule_strength_id =
-            parse::sctid(record.get(8).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "ruleStrengthId".to_string(),
-            })?)?;
-
-        let content_type_id =
-            parse::sctid(record.get(9).ok_or_else(|| Rf2Error::MissingColumn {
-                column: "contentTypeId".to_string(),
-            })?)?;
+impl RefsetView for MrcmAttributeRange {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] = &[
+        ("rangeConstraint", RefsetFieldType::String),
+        ("attributeRule", RefsetFieldType::String),
+        ("ruleStrengthId", RefsetFieldType::Component),
+        ("contentTypeId", RefsetFieldType::Component),
+    ];
 
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        let mut values = MemberValues::new::<Self>(member.values);
         Ok(MrcmAttributeRange {
-            id,
-            effective_time,
-            active,
-            module_id,
-            refset_id,
-            referenced_component_id,
-            range_constraint,
-            attribute_rule,
-            rule_strength_id,
-            content_type_id,
+            id: member.id,
+            effective_time: member.effective_time,
+            active: member.active,
+            module_id: member.module_id,
+            refset_id: member.refset_id,
+            referenced_component_id: member.referenced_component_id,
+            range_constraint: values.string()?,
+            attribute_rule: values.optional_string()?,
+            rule_strength_id: values.component()?,
+            content_type_id: values.component()?,
         })
     }
-
-    fn passes_filter(&self, config: &Rf2Config) -> bool {
-        if config.active_only && !self.active {
-            return false;
-        }
-        true
-    }
 }
 
 /// Parses MRCM Attribute Range reference set from a file.
@@ -120,141 +46,133 @@ impl Rf2Record for MrcmAttributeRange {
 pub fn parse_attribute_range_file<P: AsRef<Path>>(
     path: P,
     config: Rf2Config,
-) -> Rf2Result<impl Iterator<Item = Rf2Result<MrcmAttributeRange>>> {
-    let parser = Rf2Parser::<_, MrcmAttributeRange>::from_path(path, config)?;
-    Ok(parser)
+) -> Rf2Result<RefsetParser<Rf2Reader, MrcmAttributeRange>> {
+    parse_refset_view_file(path, config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::refset::{parse_refset_view_reader, RefsetPattern};
     use snomed_types::well_known;
 
-    fn make_test_record() -> StringRecord {
-        StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440002",
-            "20240101",
-            "1",                                        // active
-            "900000000000207008",                       // module_id
-            "723592007",                                // MRCM Attribute Range refset
-            "363698007",                                // Finding site attribute
-            "<< 123037004 |Body structure|",            // range constraint
-            "",                                         // no attribute rule
-            "723597001",                                // Mandatory rule
-            "723596005",                                // All SNOMED CT content
-        ])
+    const HEADER: &str = "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\t\
+        rangeConstraint\tattributeRule\truleStrengthId\tcontentTypeId";
+
+    /// Builds a row with the given active flag, attribute and columns from `rangeConstraint` on.
+    fn make_row(active: &str, attribute: &str, rest: &str) -> String {
+        format!(
+            "550e8400-e29b-41d4-a716-446655440002\t20240101\t{active}\t900000000000207008\t\
+             723592007\t{attribute}\t{rest}"
+        )
+    }
+
+    fn parse_row(row: &str, config: Rf2Config) -> Vec<MrcmAttributeRange> {
+        let contents = format!("{HEADER}\n{row}\n");
+        let pattern = RefsetPattern::from_file_name(
+            "der2_ssccRefset_MRCMAttributeRangeSnapshot_INT_20250101.txt",
+        )
+        .unwrap();
+        parse_refset_view_reader(contents.as_bytes(), pattern, config)
+            .unwrap()
+            .collect::<Rf2Result<_>>()
+            .unwrap()
+    }
+
+    fn parse_one(row: &str) -> MrcmAttributeRange {
+        parse_row(row, Rf2Config::default()).remove(0)
     }
 
     #[test]
     fn test_parse_mrcm_attribute_range() {
-        let record = make_test_record();
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+        let attr_range = parse_one(&make_row(
+            "1",
+            "363698007",
+            "<< 123037004 |Body structure|\t\t723597001\t723596005",
+        ));
 
         assert_eq!(attr_range.id, "550e8400-e29b-41d4-a716-446655440002");
         assert_eq!(attr_range.effective_time, 20240101);
         assert!(attr_range.active);
         assert_eq!(attr_range.module_id, 900000000000207008);
-        assert_eq!(attr_range.refset_id, well_known::MRCM_ATTRIBUTE_RANGE_REFSET);
+        assert_eq!(
+            attr_range.refset_id,
+            well_known::MRCM_ATTRIBUTE_RANGE_REFSET
+        );
         assert_eq!(attr_range.referenced_component_id, well_known::FINDING_SITE);
         assert_eq!(attr_range.range_constraint, "<< 123037004 |Body structure|");
         assert!(attr_range.attribute_rule.is_none());
-        assert_eq!(attr_range.rule_strength_id, well_known::MANDATORY_CONCEPT_MODEL_RULE);
-        assert_eq!(attr_range.content_type_id, well_known::ALL_SNOMED_CT_CONTENT);
+        assert_eq!(
+            attr_range.rule_strength_id,
+            well_known::MANDATORY_CONCEPT_MODEL_RULE
+        );
+        assert_eq!(
+            attr_range.content_type_id,
+            well_known::ALL_SNOMED_CT_CONTENT
+        );
     }
 
     #[test]
     fn test_with_attribute_rule() {
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440002",
-            "20240101",
+        let attr_range = parse_one(&make_row(
             "1",
-            "900000000000207008",
-            "723592007",
             "363698007",
-            "<< 123037004 |Body structure|",
-            "Some additional rule text", // Has attribute rule
-            "723597001",
-            "723596005",
-        ]);
-
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+            "<< 123037004 |Body structure|\tSome additional rule text\t723597001\t723596005",
+        ));
         assert!(attr_range.has_attribute_rule());
-        assert_eq!(attr_range.attribute_rule.as_deref(), Some("Some additional rule text"));
+        assert_eq!(
+            attr_range.attribute_rule.as_deref(),
+            Some("Some additional rule text")
+        );
     }
 
     #[test]
     fn test_is_mandatory() {
-        let record = make_test_record();
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+        let attr_range = parse_one(&make_row(
+            "1",
+            "363698007",
+            "<< 123037004\t\t723597001\t723596005",
+        ));
         assert!(attr_range.is_mandatory());
     }
 
     #[test]
     fn test_is_optional() {
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440002",
-            "20240101",
+        // 723598006 is the optional rule strength.
+        let attr_range = parse_one(&make_row(
             "1",
-            "900000000000207008",
-            "723592007",
             "363698007",
-            "<< 123037004",
-            "",
-            "723598006", // Optional rule
-            "723596005",
-        ]);
-
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+            "<< 123037004\t\t723598006\t723596005",
+        ));
         assert!(!attr_range.is_mandatory());
     }
 
     #[test]
     fn test_filter_inactive() {
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440002",
-            "20240101",
-            "0", // Inactive
-            "900000000000207008",
-            "723592007",
-            "363698007",
-            "<< 123037004",
-            "",
-            "723597001",
-            "723596005",
-        ]);
-
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+        let row = make_row("0", "363698007", "<< 123037004\t\t723597001\t723596005");
 
         let config_active_only = Rf2Config {
             active_only: true,
             ..Default::default()
         };
-        assert!(!attr_range.passes_filter(&config_active_only));
+        assert!(parse_row(&row, config_active_only).is_empty());
 
         let config_all = Rf2Config {
             active_only: false,
             ..Default::default()
         };
-        assert!(attr_range.passes_filter(&config_all));
+        assert_eq!(parse_row(&row, config_all).len(), 1);
     }
 
     #[test]
     fn test_complex_range_constraint() {
-        // Test with a more complex ECL expression
-        let record = StringRecord::from(vec![
-            "550e8400-e29b-41d4-a716-446655440002",
-            "20240101",
+        // Severity attribute with a more complex ECL expression.
+        let attr_range = parse_one(&make_row(
             "1",
-            "900000000000207008",
-            "723592007",
-            "246112005", // Severity attribute
-            "<< 272141005 |Severities (qualifier value)| OR << 371928007 |Severity modifier (attribute)|",
-            "",
-            "723597001",
-            "723596005",
-        ]);
-
-        let attr_range = MrcmAttributeRange::from_record(&record).unwrap();
+            "246112005",
+            "<< 272141005 |Severities (qualifier value)| OR << 371928007 |Severity modifier (attribute)|\
+             \t\t723597001\t723596005",
+        ));
         assert_eq!(attr_range.referenced_component_id, well_known::SEVERITY);
         assert!(attr_range.range_constraint.contains("OR"));
     }
@@ -39,9 +39,9 @@
 //! Snapshot/
 //! └── Refset/
 //!     └── Metadata/
-//!         ├── der2_cRefset_MRCMDomainSnapshot_*.txt
-//!         ├── der2_cRefset_MRCMAttributeDomainSnapshot_*.txt
-//!         └── der2_cRefset_MRCMAttributeRangeSnapshot_*.txt
+//!         ├── der2_sssssssRefset_MRCMDomainSnapshot_*.txt
+//!         ├── der2_cissccRefset_MRCMAttributeDomainSnapshot_*.txt
+//!         └── der2_ssccRefset_MRCMAttributeRangeSnapshot_*.txt
 //! ```
 
 mod attribute_domain;
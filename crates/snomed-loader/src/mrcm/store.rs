@@ -33,6 +33,7 @@ use super::{parse_attribute_domain_file, parse_attribute_range_file, parse_domai
 /// }
 /// ```
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
 pub struct MrcmStore {
     /// Domains indexed by domain concept ID.
     domains: HashMap<SctId, Vec<MrcmDomain>>,
@@ -93,11 +94,11 @@ impl MrcmStore {
             }
 
             if filename_str.contains("MRCMDomainSnapshot") {
-                store.load_domains(&entry.path(), config.clone())?;
+                store.load_domains(entry.path(), config.clone())?;
             } else if filename_str.contains("MRCMAttributeDomainSnapshot") {
-                store.load_attribute_domains(&entry.path(), config.clone())?;
+                store.load_attribute_domains(entry.path(), config.clone())?;
             } else if filename_str.contains("MRCMAttributeRangeSnapshot") {
-                store.load_attribute_ranges(&entry.path(), config.clone())?;
+                store.load_attribute_ranges(entry.path(), config.clone())?;
             }
         }
 
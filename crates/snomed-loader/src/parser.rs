@@ -2,15 +2,16 @@
 //!
 //! Provides a streaming parser for SNOMED CT RF2 tab-delimited files.
 
-use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use csv::{Reader, ReaderBuilder, StringRecord};
+use csv::StringRecord;
 use snomed_types::SctId;
 
-use crate::types::{Rf2Config, Rf2Error, Rf2Result};
+use crate::source::{open_rf2_file, Rf2Reader};
+use crate::tokenizer::Rf2Line;
+use crate::types::{Rf2Config, Rf2Error, Rf2Result, RowError};
 
 /// Trait for types that can be parsed from RF2 records.
 ///
@@ -26,20 +27,95 @@ pub trait Rf2Record: Sized {
     fn passes_filter(&self, config: &Rf2Config) -> bool;
 }
 
+/// Trait for RF2 components that are versioned by id and effective time.
+///
+/// Full and Delta releases carry several rows per component id; this trait
+/// lets loaders pick the right version without knowing the concrete type.
+pub trait Rf2Component {
+    /// The component's SCTID.
+    fn component_id(&self) -> SctId;
+
+    /// The row's effective time (YYYYMMDD).
+    fn effective_time(&self) -> u32;
+}
+
+/// Reads field `index` of `record`, the `column` column, with `parse`.
+///
+/// Errors, including a missing field, are tagged with the column so that
+/// row errors can name it.
+pub(crate) fn field<'a, T>(
+    record: &'a StringRecord,
+    index: usize,
+    column: &'static str,
+    parse: impl FnOnce(&'a str) -> Rf2Result<T>,
+) -> Rf2Result<T> {
+    record
+        .get(index)
+        .ok_or_else(|| Rf2Error::MissingColumn {
+            column: column.to_string(),
+        })
+        .and_then(parse)
+        .map_err(|error| error.in_column(column))
+}
+
+/// Checks a header row against the expected column names.
+///
+/// Extra trailing columns are allowed.
+pub(crate) fn check_header<'a>(
+    headers: impl IntoIterator<Item = &'a str>,
+    expected: &[&str],
+) -> Rf2Result<()> {
+    let headers: Vec<&str> = headers.into_iter().collect();
+
+    if headers.len() < expected.len() {
+        return Err(Rf2Error::InvalidHeader {
+            expected: expected.len(),
+            found: headers.len(),
+        });
+    }
+
+    for (i, expected_col) in expected.iter().enumerate() {
+        let found = headers[i];
+        if found != *expected_col {
+            return Err(Rf2Error::UnexpectedColumn {
+                position: i,
+                expected: expected_col.to_string(),
+                found: found.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// A streaming parser for RF2 files.
 ///
 /// This parser reads RF2 files record-by-record to avoid loading
-/// entire files into memory.
+/// entire files into memory. Lines are split with [`Rf2Line`], so fields
+/// are never quoted and a `"` is read as part of the field.
+///
+/// Rows that fail to parse are yielded as [`Rf2Error::Row`], which carries
+/// the file path, line number and column. Use [`lenient`](Self::lenient) to
+/// skip such rows and collect them instead.
 pub struct Rf2Parser<R: Read, T: Rf2Record> {
-    reader: Reader<R>,
+    reader: BufReader<R>,
+    /// Raw bytes of the current line.
+    buffer: Vec<u8>,
+    /// Column names from the header row.
+    headers: StringRecord,
+    path: PathBuf,
     config: Rf2Config,
     records_read: usize,
+    line: u64,
     _marker: PhantomData<T>,
 }
 
-impl<T: Rf2Record> Rf2Parser<BufReader<File>, T> {
+impl<T: Rf2Record> Rf2Parser<Rf2Reader, T> {
     /// Creates a new parser from a file path.
     ///
+    /// The path may also name an entry inside a release ZIP archive
+    /// (see [`open_rf2_file`]), which is decompressed while parsing.
+    ///
     /// # Arguments
     /// * `path` - Path to the RF2 file
     /// * `config` - Parser configuration
@@ -47,25 +123,17 @@ impl<T: Rf2Record> Rf2Parser<BufReader<File>, T> {
     /// # Errors
     /// Returns an error if the file cannot be opened or has invalid headers.
     pub fn from_path<P: AsRef<Path>>(path: P, config: Rf2Config) -> Rf2Result<Self> {
-        let path = path.as_ref();
-
-        if !path.exists() {
-            return Err(Rf2Error::FileNotFound {
-                path: path.display().to_string(),
-            });
-        }
-
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        Self::from_reader(reader, config)
+        let reader = open_rf2_file(&path)?;
+        let mut parser = Self::from_reader(reader, config)?;
+        parser.path = path.as_ref().to_path_buf();
+        Ok(parser)
     }
 
     /// Counts the total number of lines in the file (for progress reporting).
     ///
     /// This performs a fast line count by counting newlines.
     pub fn count_lines<P: AsRef<Path>>(path: P) -> Rf2Result<usize> {
-        let file = File::open(path.as_ref())?;
-        let reader = BufReader::new(file);
+        let reader = open_rf2_file(path)?;
         let count = reader.lines().count();
         // Subtract 1 for header row
         Ok(count.saturating_sub(1))
@@ -75,62 +143,96 @@ impl<T: Rf2Record> Rf2Parser<BufReader<File>, T> {
 impl<R: Read, T: Rf2Record> Rf2Parser<R, T> {
     /// Creates a new parser from a reader.
     pub fn from_reader(reader: R, config: Rf2Config) -> Rf2Result<Self> {
-        let mut csv_reader = ReaderBuilder::new()
-            .delimiter(b'\t')
-            .has_headers(true)
-            .flexible(false)
-            .trim(csv::Trim::None)
-            .from_reader(reader);
-
-        // Validate headers
-        Self::validate_headers(&mut csv_reader)?;
-
-        Ok(Self {
-            reader: csv_reader,
+        let mut parser = Self {
+            reader: BufReader::new(reader),
+            buffer: Vec::new(),
+            headers: StringRecord::new(),
+            path: PathBuf::new(),
             config,
             records_read: 0,
+            line: 0,
             _marker: PhantomData,
-        })
-    }
+        };
 
-    /// Validates that the file has the expected column headers.
-    fn validate_headers(reader: &mut Reader<R>) -> Rf2Result<()> {
-        let headers = reader.headers()?;
-        let expected = T::EXPECTED_COLUMNS;
+        // Validate headers
+        parser.validate_headers()?;
 
-        if headers.len() < expected.len() {
-            return Err(Rf2Error::InvalidHeader {
-                expected: expected.len(),
-                found: headers.len(),
-            });
+        Ok(parser)
+    }
+
+    /// Reads the header row and checks it against the expected column names.
+    fn validate_headers(&mut self) -> Rf2Result<()> {
+        if !self.read_line()? {
+            return check_header(std::iter::empty(), T::EXPECTED_COLUMNS);
         }
+        let header = std::str::from_utf8(&self.buffer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.headers = Rf2Line::new(header).fields().collect();
+        check_header(self.headers.iter(), T::EXPECTED_COLUMNS)
+    }
 
-        for (i, expected_col) in expected.iter().enumerate() {
-            let found = headers.get(i).unwrap_or("");
-            // Handle UTF-8 BOM at start of file
-            let found = found.trim_start_matches('\u{feff}');
-            if found != *expected_col {
-                return Err(Rf2Error::UnexpectedColumn {
-                    position: i,
-                    expected: expected_col.to_string(),
-                    found: found.to_string(),
-                });
-            }
+    /// Reads the next raw line into the buffer; returns false at end of input.
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.buffer.clear();
+        let read = self.reader.read_until(b'\n', &mut self.buffer)?;
+        if read > 0 {
+            self.line += 1;
         }
+        Ok(read > 0)
+    }
+
+    /// Returns the file being parsed (empty when parsing from a reader).
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 
-        Ok(())
+    /// Returns the column names from the header row.
+    ///
+    /// These may go beyond [`Rf2Record::EXPECTED_COLUMNS`], which only
+    /// checks the leading columns.
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
     }
 
-    /// Returns the number of records read so far.
+    /// Returns the number of records read so far, including malformed ones.
     pub fn records_read(&self) -> usize {
         self.records_read
     }
 
+    /// Returns the line number of the last record read (the header is line 1).
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
     /// Returns the parser configuration.
     pub fn config(&self) -> &Rf2Config {
         &self.config
     }
 
+    /// Turns this parser into one that skips rows which fail to parse.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut rows = Rf2Parser::<_, Rf2Concept>::from_path(path, Rf2Config::default())?.lenient();
+    /// let concepts: Vec<_> = rows.by_ref().collect();
+    /// for error in rows.finish()? {
+    ///     eprintln!("skipped {}", error);
+    /// }
+    /// ```
+    pub fn lenient(self) -> LenientParser<R, T> {
+        LenientParser {
+            parser: self,
+            errors: Vec::new(),
+            fatal: None,
+        }
+    }
+
+    /// Wraps an error from parsing the current line with its location.
+    fn row_error(&self, error: Rf2Error) -> Rf2Error {
+        Rf2Error::Row(Box::new(RowError::new(&self.path, self.line, error)))
+    }
+
     /// Parses all records into a Vec, applying filters.
     ///
     /// Note: This loads all matching records into memory.
@@ -178,29 +280,97 @@ impl<R: Read, T: Rf2Record> Iterator for Rf2Parser<R, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            let mut record = StringRecord::new();
-            match self.reader.read_record(&mut record) {
-                Ok(true) => {
+            match self.read_line() {
+                Ok(true) => {}
+                Ok(false) => return None, // End of file
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            let text = match std::str::from_utf8(&self.buffer) {
+                Ok(text) => text,
+                Err(e) => {
                     self.records_read += 1;
+                    let error = io::Error::new(io::ErrorKind::InvalidData, e).into();
+                    return Some(Err(self.row_error(error)));
+                }
+            };
 
-                    // Skip empty records
-                    if record.is_empty() || record.iter().all(|f| f.trim().is_empty()) {
-                        continue;
-                    }
+            // Skip blank lines
+            let line = Rf2Line::new(text);
+            if line.is_blank() {
+                continue;
+            }
+            self.records_read += 1;
+
+            let record: StringRecord = line.fields().collect();
+            let expected = T::EXPECTED_COLUMNS.len();
+            if record.len() < expected {
+                let error = Rf2Error::FieldCount {
+                    expected,
+                    found: record.len(),
+                };
+                return Some(Err(self.row_error(error)));
+            }
 
-                    match T::from_record(&record) {
-                        Ok(parsed) => {
-                            if parsed.passes_filter(&self.config) {
-                                return Some(Ok(parsed));
-                            }
-                            // Record filtered out, continue to next
-                            continue;
-                        }
-                        Err(e) => return Some(Err(e)),
+            match T::from_record(&record) {
+                Ok(parsed) => {
+                    if parsed.passes_filter(&self.config) {
+                        return Some(Ok(parsed));
                     }
+                    // Record filtered out, continue to next
+                }
+                Err(e) => return Some(Err(self.row_error(e))),
+            }
+        }
+    }
+}
+
+/// A parser that skips rows which fail to parse, collecting them instead.
+///
+/// Created with [`Rf2Parser::lenient`]. Iteration stops at the first error
+/// that is not about a single row (e.g. an I/O error); it is returned by
+/// [`finish`](Self::finish).
+pub struct LenientParser<R: Read, T: Rf2Record> {
+    parser: Rf2Parser<R, T>,
+    errors: Vec<RowError>,
+    fatal: Option<Rf2Error>,
+}
+
+impl<R: Read, T: Rf2Record> LenientParser<R, T> {
+    /// Returns the rows skipped so far.
+    pub fn errors(&self) -> &[RowError] {
+        &self.errors
+    }
+
+    /// Returns the number of records read so far, including skipped ones.
+    pub fn records_read(&self) -> usize {
+        self.parser.records_read()
+    }
+
+    /// Returns the skipped rows, or the error that stopped iteration.
+    pub fn finish(self) -> Rf2Result<Vec<RowError>> {
+        match self.fatal {
+            Some(error) => Err(error),
+            None => Ok(self.errors),
+        }
+    }
+}
+
+impl<R: Read, T: Rf2Record> Iterator for LenientParser<R, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.fatal.is_some() {
+            return None;
+        }
+        loop {
+            match self.parser.next()? {
+                Ok(row) => return Some(row),
+                Err(Rf2Error::Row(error)) => self.errors.push(*error),
+                Err(error) => {
+                    self.fatal = Some(error);
+                    return None;
                 }
-                Ok(false) => return None, // End of file
-                Err(e) => return Some(Err(e.into())),
             }
         }
     }
@@ -209,6 +379,7 @@ impl<R: Read, T: Rf2Record> Iterator for Rf2Parser<R, T> {
 /// Helper functions for parsing RF2 field values.
 pub mod parse {
     use super::{Rf2Error, Rf2Result, SctId};
+    use snomed_types::ConcreteValue;
 
     /// Parses an SCTID from a string.
     pub fn sctid(value: &str) -> Rf2Result<SctId> {
@@ -266,6 +437,13 @@ pub mod parse {
             value: value.to_string(),
         })
     }
+
+    /// Parses a concrete value (`#500`, `#0.5` or `"tablet"`).
+    pub fn concrete_value(value: &str) -> Rf2Result<ConcreteValue> {
+        ConcreteValue::parse(value).ok_or_else(|| Rf2Error::InvalidConcreteValue {
+            value: value.to_string(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -305,4 +483,68 @@ mod tests {
         assert_eq!(parse::integer::<u16>("123").unwrap(), 123u16);
         assert!(parse::integer::<u16>("abc").is_err());
     }
+
+    const CONCEPTS: &str = "id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n\
+        100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+        200\t2002013\t1\t900000000000207008\t900000000000074008\n\
+        300\t20020131\t1\t900000000000207008\n\
+        400\t20020131\t1\t900000000000207008\t900000000000074008\n";
+
+    #[test]
+    fn test_row_errors_carry_location() {
+        let config = Rf2Config::default();
+        let results: Vec<_> =
+            Rf2Parser::<_, snomed_types::Rf2Concept>::from_reader(CONCEPTS.as_bytes(), config)
+                .unwrap()
+                .collect();
+
+        assert_eq!(results.len(), 4);
+        match &results[1] {
+            Err(Rf2Error::Row(row)) => {
+                assert_eq!(row.line, 3);
+                assert_eq!(row.column, Some("effectiveTime"));
+                assert!(matches!(row.error, Rf2Error::InvalidDate { .. }));
+            }
+            other => panic!("expected a row error, got {:?}", other),
+        }
+        assert!(matches!(&results[2], Err(Rf2Error::Row(row)) if row.line == 4));
+    }
+
+    #[test]
+    fn test_row_error_names_the_failing_column_not_an_equal_field() {
+        // The empty languageCode is valid; the empty typeId is not
+        let descriptions = "id\teffectiveTime\tactive\tmoduleId\tconceptId\tlanguageCode\ttypeId\tterm\tcaseSignificanceId\n\
+            1\t20020131\t1\t900000000000207008\t100\t\t\tTerm\t900000000000448009\n";
+        let mut rows = Rf2Parser::<_, snomed_types::Rf2Description>::from_reader(
+            descriptions.as_bytes(),
+            Rf2Config::default(),
+        )
+        .unwrap();
+
+        match rows.next() {
+            Some(Err(Rf2Error::Row(row))) => {
+                assert_eq!(row.column, Some("typeId"));
+                assert!(matches!(row.error, Rf2Error::InvalidSctId { .. }));
+            }
+            other => panic!("expected a row error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lenient_parser_collects_bad_rows() {
+        let mut rows = Rf2Parser::<_, snomed_types::Rf2Concept>::from_reader(
+            CONCEPTS.as_bytes(),
+            Rf2Config::default(),
+        )
+        .unwrap()
+        .lenient();
+
+        let ids: Vec<_> = rows.by_ref().map(|c| c.id).collect();
+        assert_eq!(ids, vec![100, 400]);
+        assert_eq!(rows.records_read(), 4);
+
+        let errors = rows.finish().unwrap();
+        let lines: Vec<_> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+    }
 }
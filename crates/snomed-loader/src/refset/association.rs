@@ -0,0 +1,308 @@
+//! Association reference set parser and index.
+//!
+//! Parses files matching pattern: `der2_cRefset_AssociationSnapshot_*.txt`
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use csv::StringRecord;
+use snomed_types::{well_known, AssociationRefsetMember, SctId};
+
+use super::versions::{MemberRow, MemberVersions};
+use crate::parser::{field, parse, Rf2Parser, Rf2Record};
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+/// Historical associations followed when resolving an inactive concept to
+/// active replacements: SAME AS, REPLACED BY, POSSIBLY EQUIVALENT TO and
+/// POSSIBLY REPLACED BY.
+pub const REPLACEMENT_ASSOCIATIONS: &[SctId] = &[
+    well_known::SAME_AS_REFSET,
+    well_known::REPLACED_BY_REFSET,
+    well_known::POSSIBLY_EQUIVALENT_TO_REFSET,
+    well_known::POSSIBLY_REPLACED_BY_REFSET,
+];
+
+/// Expected columns for association reference sets.
+///
+/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId,
+/// targetComponentId
+const ASSOCIATION_COLUMNS: &[&str] = &[
+    "id",
+    "effectiveTime",
+    "active",
+    "moduleId",
+    "refsetId",
+    "referencedComponentId",
+    "targetComponentId",
+];
+
+impl Rf2Record for AssociationRefsetMember {
+    const EXPECTED_COLUMNS: &'static [&'static str] = ASSOCIATION_COLUMNS;
+
+    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
+        Ok(AssociationRefsetMember {
+            id: field(record, 0, "id", Ok)?.to_string(),
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            refset_id: field(record, 4, "refsetId", parse::sctid)?,
+            referenced_component_id: field(record, 5, "referencedComponentId", parse::sctid)?,
+            target_component_id: field(record, 6, "targetComponentId", parse::sctid)?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+/// Parses an association reference set from a file.
+///
+/// # Arguments
+/// * `path` - Path to the association reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `AssociationRefsetMember` records.
+pub fn parse_association_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<impl Iterator<Item = Rf2Result<AssociationRefsetMember>>> {
+    let parser = Rf2Parser::<_, AssociationRefsetMember>::from_path(path, config)?;
+    Ok(parser)
+}
+
+/// An association from a source component to a target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct Association {
+    /// The reference set member id.
+    pub id: String,
+    /// The association reference set (e.g. REPLACED BY).
+    pub refset_id: SctId,
+    /// The target component.
+    pub target_id: SctId,
+    /// Effective time of the member row.
+    pub effective_time: u32,
+}
+
+impl MemberRow for Association {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Index of active association targets per source component.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::AssociationIndex;
+/// use snomed_types::well_known;
+///
+/// let index = AssociationIndex::load("der2_cRefset_AssociationSnapshot_INT_20250101.txt")?;
+/// for association in index.get(inactive_concept_id) {
+///     if association.refset_id == well_known::REPLACED_BY_REFSET {
+///         println!("Replaced by {}", association.target_id);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssociationIndex {
+    by_source: HashMap<SctId, Vec<Association>>,
+    versions: MemberVersions,
+}
+
+impl AssociationIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of an association reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_association_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Active members are upserted and inactive ones retire their row;
+    /// versions older than the stored or retired version of the same member
+    /// are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = AssociationRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let source_id = member.referenced_component_id;
+            let association = member.active.then(|| Association {
+                id: member.id.clone(),
+                refset_id: member.refset_id,
+                target_id: member.target_component_id,
+                effective_time: member.effective_time,
+            });
+            let associations = self.by_source.entry(source_id).or_default();
+            self.versions.apply(
+                associations,
+                &member.id,
+                member.effective_time,
+                association,
+                &mut stats,
+            );
+            if associations.is_empty() {
+                self.by_source.remove(&source_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets all active associations from a source component.
+    pub fn get(&self, source_id: SctId) -> &[Association] {
+        self.by_source
+            .get(&source_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets the targets of a source component in the given association refsets.
+    pub fn targets<'a>(
+        &'a self,
+        source_id: SctId,
+        refset_ids: &'a [SctId],
+    ) -> impl Iterator<Item = SctId> + 'a {
+        self.get(source_id)
+            .iter()
+            .filter(|a| refset_ids.contains(&a.refset_id))
+            .map(|a| a.target_id)
+    }
+
+    /// Returns the number of active associations.
+    pub fn len(&self) -> usize {
+        self.by_source.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_source.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let ids: usize = self.by_source.values().flatten().map(|a| a.id.len()).sum();
+        self.by_source.len() * size_of::<SctId>()
+            + self.len() * size_of::<Association>()
+            + ids
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_member(
+        source_id: SctId,
+        refset_id: SctId,
+        target_id: SctId,
+    ) -> AssociationRefsetMember {
+        AssociationRefsetMember {
+            id: format!("{}-{}-{}", source_id, refset_id, target_id),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id,
+            referenced_component_id: source_id,
+            target_component_id: target_id,
+        }
+    }
+
+    #[test]
+    fn test_parse_association_member() {
+        let record = StringRecord::from(vec![
+            "80000000-0000-0000-0000-000000000000",
+            "20020131",
+            "1",
+            "900000000000207008",
+            "900000000000526001",
+            "100",
+            "200",
+        ]);
+
+        let member = AssociationRefsetMember::from_record(&record).unwrap();
+        assert_eq!(member.refset_id, well_known::REPLACED_BY_REFSET);
+        assert_eq!(member.referenced_component_id, 100);
+        assert_eq!(member.target_component_id, 200);
+    }
+
+    #[test]
+    fn test_apply_members() {
+        let mut index = AssociationIndex::new();
+        index.apply_members([
+            make_member(100, well_known::POSSIBLY_EQUIVALENT_TO_REFSET, 200),
+            make_member(100, well_known::POSSIBLY_EQUIVALENT_TO_REFSET, 300),
+            make_member(100, well_known::MOVED_TO_REFSET, 400),
+        ]);
+
+        assert_eq!(index.len(), 3);
+        let targets: Vec<_> = index.targets(100, REPLACEMENT_ASSOCIATIONS).collect();
+        assert_eq!(targets, vec![200, 300]);
+
+        let inactivated = AssociationRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member(100, well_known::POSSIBLY_EQUIVALENT_TO_REFSET, 200)
+        };
+        let stats = index.apply_members([inactivated]);
+
+        assert_eq!(stats.removed, 1);
+        assert_eq!(index.targets(100, REPLACEMENT_ASSOCIATIONS).count(), 1);
+        assert!(index.get(999).is_empty());
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let retired = AssociationRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member(100, well_known::REPLACED_BY_REFSET, 200)
+        };
+        let original = make_member(100, well_known::REPLACED_BY_REFSET, 200);
+
+        let mut index = AssociationIndex::new();
+        let stats = index.apply_members([retired.clone(), original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(index.get(100).is_empty());
+        assert!(index.is_empty());
+
+        // A newer active row reinstates it
+        let reinstated = AssociationRefsetMember {
+            effective_time: 20260201,
+            active: true,
+            ..retired
+        };
+        index.apply_members([reinstated]);
+        assert_eq!(
+            index
+                .targets(100, REPLACEMENT_ASSOCIATIONS)
+                .collect::<Vec<_>>(),
+            vec![200]
+        );
+    }
+}
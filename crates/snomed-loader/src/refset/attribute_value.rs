@@ -0,0 +1,343 @@
+//! Attribute value reference set parser and index.
+//!
+//! Parses files matching pattern: `der2_cRefset_AttributeValueSnapshot_*.txt`
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use csv::StringRecord;
+use snomed_types::{well_known, AttributeValueRefsetMember, InactivationReason, SctId};
+
+use super::versions::{MemberRow, MemberVersions};
+use crate::parser::{field, parse, Rf2Parser, Rf2Record};
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+/// Expected columns for attribute value reference sets.
+///
+/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId,
+/// valueId
+const ATTRIBUTE_VALUE_COLUMNS: &[&str] = &[
+    "id",
+    "effectiveTime",
+    "active",
+    "moduleId",
+    "refsetId",
+    "referencedComponentId",
+    "valueId",
+];
+
+impl Rf2Record for AttributeValueRefsetMember {
+    const EXPECTED_COLUMNS: &'static [&'static str] = ATTRIBUTE_VALUE_COLUMNS;
+
+    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
+        Ok(AttributeValueRefsetMember {
+            id: field(record, 0, "id", Ok)?.to_string(),
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            refset_id: field(record, 4, "refsetId", parse::sctid)?,
+            referenced_component_id: field(record, 5, "referencedComponentId", parse::sctid)?,
+            value_id: field(record, 6, "valueId", parse::sctid)?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+/// Parses an attribute value reference set from a file.
+///
+/// # Arguments
+/// * `path` - Path to the attribute value reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `AttributeValueRefsetMember` records.
+pub fn parse_attribute_value_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<impl Iterator<Item = Rf2Result<AttributeValueRefsetMember>>> {
+    let parser = Rf2Parser::<_, AttributeValueRefsetMember>::from_path(path, config)?;
+    Ok(parser)
+}
+
+/// A coded value attached to a component by an attribute value reference set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeValue {
+    /// The reference set member id.
+    pub id: String,
+    /// The attribute value reference set (e.g. concept inactivation indicator).
+    pub refset_id: SctId,
+    /// The value concept.
+    pub value_id: SctId,
+    /// Effective time of the member row.
+    pub effective_time: u32,
+}
+
+impl MemberRow for AttributeValue {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Index of active attribute values per referenced component.
+///
+/// A component holds at most one active value per reference set.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::AttributeValueIndex;
+///
+/// let index = AttributeValueIndex::load("der2_cRefset_AttributeValueSnapshot_INT_20250101.txt")?;
+/// if let Some(reason) = index.inactivation_reason(inactive_concept_id) {
+///     println!("Inactivated: {}", reason.name());
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeValueIndex {
+    by_component: HashMap<SctId, Vec<AttributeValue>>,
+    versions: MemberVersions,
+}
+
+impl AttributeValueIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of an attribute value reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_attribute_value_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Active members are upserted and inactive ones retire their row;
+    /// versions older than the stored or retired version of the same member
+    /// are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = AttributeValueRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let component_id = member.referenced_component_id;
+            let value = member.active.then(|| AttributeValue {
+                id: member.id.clone(),
+                refset_id: member.refset_id,
+                value_id: member.value_id,
+                effective_time: member.effective_time,
+            });
+            let values = self.by_component.entry(component_id).or_default();
+            self.versions
+                .apply(values, &member.id, member.effective_time, value, &mut stats);
+            if values.is_empty() {
+                self.by_component.remove(&component_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets all active attribute values of a component.
+    pub fn get(&self, component_id: SctId) -> &[AttributeValue] {
+        self.by_component
+            .get(&component_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets the value of a component in an attribute value reference set.
+    pub fn value(&self, component_id: SctId, refset_id: SctId) -> Option<SctId> {
+        self.get(component_id)
+            .iter()
+            .find(|v| v.refset_id == refset_id)
+            .map(|v| v.value_id)
+    }
+
+    /// Gets the reason a concept or description was inactivated.
+    ///
+    /// Looks in the concept and description inactivation indicator reference
+    /// sets. Returns `None` if the component has no indicator or the value is
+    /// not a known [`InactivationReason`].
+    pub fn inactivation_reason(&self, component_id: SctId) -> Option<InactivationReason> {
+        self.value(
+            component_id,
+            well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET,
+        )
+        .or_else(|| {
+            self.value(
+                component_id,
+                well_known::DESCRIPTION_INACTIVATION_INDICATOR_REFSET,
+            )
+        })
+        .and_then(InactivationReason::from_id)
+    }
+
+    /// Returns the number of active attribute values.
+    pub fn len(&self) -> usize {
+        self.by_component.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_component.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let ids: usize = self
+            .by_component
+            .values()
+            .flatten()
+            .map(|v| v.id.len())
+            .sum();
+        self.by_component.len() * size_of::<SctId>()
+            + self.len() * size_of::<AttributeValue>()
+            + ids
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_member(
+        component_id: SctId,
+        refset_id: SctId,
+        value_id: SctId,
+    ) -> AttributeValueRefsetMember {
+        AttributeValueRefsetMember {
+            id: format!("{}-{}", component_id, refset_id),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id,
+            referenced_component_id: component_id,
+            value_id,
+        }
+    }
+
+    #[test]
+    fn test_parse_attribute_value_member() {
+        let record = StringRecord::from(vec![
+            "80000000-0000-0000-0000-000000000000",
+            "20020131",
+            "1",
+            "900000000000207008",
+            "900000000000489007",
+            "100",
+            "900000000000482003",
+        ]);
+
+        let member = AttributeValueRefsetMember::from_record(&record).unwrap();
+        assert_eq!(
+            member.refset_id,
+            well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET
+        );
+        assert_eq!(member.referenced_component_id, 100);
+        assert_eq!(
+            member.inactivation_reason(),
+            Some(InactivationReason::Duplicate)
+        );
+    }
+
+    #[test]
+    fn test_inactivation_reason() {
+        let mut index = AttributeValueIndex::new();
+        index.apply_members([
+            make_member(
+                100,
+                well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET,
+                InactivationReason::AMBIGUOUS_ID,
+            ),
+            make_member(
+                1011,
+                well_known::DESCRIPTION_INACTIVATION_INDICATOR_REFSET,
+                InactivationReason::CONCEPT_NON_CURRENT_ID,
+            ),
+        ]);
+
+        assert_eq!(
+            index.inactivation_reason(100),
+            Some(InactivationReason::Ambiguous)
+        );
+        assert_eq!(
+            index.inactivation_reason(1011),
+            Some(InactivationReason::ConceptNonCurrent)
+        );
+
+        // A later row replaces the value; reactivation removes it
+        let stats = index.apply_members([
+            AttributeValueRefsetMember {
+                effective_time: 20250201,
+                ..make_member(
+                    100,
+                    well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET,
+                    InactivationReason::ERRONEOUS_ID,
+                )
+            },
+            AttributeValueRefsetMember {
+                effective_time: 20250201,
+                active: false,
+                ..make_member(
+                    1011,
+                    well_known::DESCRIPTION_INACTIVATION_INDICATOR_REFSET,
+                    InactivationReason::CONCEPT_NON_CURRENT_ID,
+                )
+            },
+        ]);
+
+        assert_eq!(stats.upserted, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(
+            index.inactivation_reason(100),
+            Some(InactivationReason::Erroneous)
+        );
+        assert_eq!(index.inactivation_reason(1011), None);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let retired = AttributeValueRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member(
+                100,
+                well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET,
+                InactivationReason::DUPLICATE_ID,
+            )
+        };
+        let original = make_member(
+            100,
+            well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET,
+            InactivationReason::DUPLICATE_ID,
+        );
+
+        let mut index = AttributeValueIndex::new();
+        let stats = index.apply_members([retired, original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(index.inactivation_reason(100), None);
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+//! Extended map reference set parser and index.
+//!
+//! Parses files matching pattern: `der2_iisssccRefset_ExtendedMapSnapshot_*.txt`
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use snomed_types::{ExtendedMapRefsetMember, RefsetFieldType, RefsetMember, SctId};
+
+use super::generic::{parse_refset_view_file, MemberValues, RefsetParser, RefsetView};
+use super::map_rule::{evaluate_map, MapContext};
+use super::versions::{MemberRow, MemberVersions};
+use crate::source::Rf2Reader;
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+impl RefsetView for ExtendedMapRefsetMember {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] = &[
+        ("mapGroup", RefsetFieldType::Integer),
+        ("mapPriority", RefsetFieldType::Integer),
+        ("mapRule", RefsetFieldType::String),
+        ("mapAdvice", RefsetFieldType::String),
+        ("mapTarget", RefsetFieldType::String),
+        ("correlationId", RefsetFieldType::Component),
+        ("mapCategoryId", RefsetFieldType::Component),
+    ];
+
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        let mut values = MemberValues::new::<Self>(member.values);
+        Ok(ExtendedMapRefsetMember {
+            id: member.id,
+            effective_time: member.effective_time,
+            active: member.active,
+            module_id: member.module_id,
+            refset_id: member.refset_id,
+            referenced_component_id: member.referenced_component_id,
+            map_group: values.unsigned()?,
+            map_priority: values.unsigned()?,
+            map_rule: values.string()?,
+            map_advice: values.string()?,
+            map_target: values.string()?,
+            correlation_id: values.component()?,
+            map_category_id: values.component()?,
+        })
+    }
+}
+
+impl MemberRow for ExtendedMapRefsetMember {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Parses an extended map reference set from a file.
+///
+/// # Arguments
+/// * `path` - Path to the extended map reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `ExtendedMapRefsetMember` records.
+pub fn parse_extended_map_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<Rf2Reader, ExtendedMapRefsetMember>> {
+    parse_refset_view_file(path, config)
+}
+
+/// Index of active extended map rows per mapped concept.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::{ExtendedMapIndex, MapContext, Sex};
+/// use snomed_types::well_known;
+///
+/// let index = ExtendedMapIndex::load("der2_iisssccRefset_ExtendedMapSnapshot_INT_20250101.txt")?;
+/// let patient = MapContext::new().with_age(67.0).with_sex(Sex::Male);
+///
+/// for row in index.evaluate(well_known::ICD10_EXTENDED_MAP_REFSET, 44054006, &patient) {
+///     println!("group {}: {:?} ({})", row.map_group, row.target(), row.map_advice);
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedMapIndex {
+    by_concept: HashMap<SctId, Vec<ExtendedMapRefsetMember>>,
+    versions: MemberVersions,
+}
+
+impl ExtendedMapIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of an extended map reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_extended_map_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Members are keyed by their row id. Active members are upserted and
+    /// inactive ones retire their row; versions older than the stored or
+    /// retired version of the same member are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = ExtendedMapRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let concept_id = member.referenced_component_id;
+            let id = member.id.clone();
+            let effective_time = member.effective_time;
+            let rows = self.by_concept.entry(concept_id).or_default();
+            let row = member.active.then_some(member);
+            self.versions
+                .apply(rows, &id, effective_time, row, &mut stats);
+
+            if rows.is_empty() {
+                self.by_concept.remove(&concept_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets all active map rows for a concept, across map reference sets.
+    pub fn get(&self, concept_id: SctId) -> &[ExtendedMapRefsetMember] {
+        self.by_concept
+            .get(&concept_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets the active rows of one map reference set for a concept.
+    pub fn rows(
+        &self,
+        refset_id: SctId,
+        concept_id: SctId,
+    ) -> impl Iterator<Item = &ExtendedMapRefsetMember> {
+        self.get(concept_id)
+            .iter()
+            .filter(move |row| row.refset_id == refset_id)
+    }
+
+    /// Chooses one row per map group of a map reference set for a patient.
+    ///
+    /// See [`evaluate_map`].
+    pub fn evaluate(
+        &self,
+        refset_id: SctId,
+        concept_id: SctId,
+        context: &MapContext,
+    ) -> Vec<&ExtendedMapRefsetMember> {
+        evaluate_map(self.rows(refset_id, concept_id), context)
+    }
+
+    /// Returns the number of active map rows.
+    pub fn len(&self) -> usize {
+        self.by_concept.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_concept.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let row_size: usize = self
+            .by_concept
+            .values()
+            .flatten()
+            .map(|row| {
+                size_of::<ExtendedMapRefsetMember>()
+                    + row.id.len()
+                    + row.map_rule.len()
+                    + row.map_advice.len()
+                    + row.map_target.len()
+            })
+            .sum();
+
+        self.by_concept.len() * size_of::<SctId>()
+            + row_size
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refset::{parse_refset_view_reader, RefsetPattern, Sex};
+    use crate::types::Rf2Error;
+    use snomed_types::well_known;
+
+    #[test]
+    fn test_parse_extended_map_member() {
+        let contents = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tmapGroup\tmapPriority\tmapRule\tmapAdvice\tmapTarget\tcorrelationId\tmapCategoryId
+80000000-0000-0000-0000-000000000000\t20250101\t1\t449080006\t447562003\t44054006\t1\t2\tIFA 248153007 | Male (finding) |\tIF MALE CHOOSE E11.9 | MAP OF SOURCE CONCEPT IS CONTEXT DEPENDENT\tE11.9\t447561005\t447639009
+";
+        let pattern = RefsetPattern::from_file_name(
+            "der2_iisssccRefset_ExtendedMapSnapshot_INT_20250101.txt",
+        )
+        .unwrap();
+        let members: Vec<ExtendedMapRefsetMember> =
+            parse_refset_view_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect::<Rf2Result<_>>()
+                .unwrap();
+
+        let member = &members[0];
+        assert_eq!(member.refset_id, well_known::ICD10_EXTENDED_MAP_REFSET);
+        assert_eq!(member.map_group, 1);
+        assert_eq!(member.map_priority, 2);
+        assert_eq!(member.target(), Some("E11.9"));
+
+        // A file whose name gives other column types is not read as an extended map
+        let pattern = RefsetPattern::from_file_name(
+            "der2_sssssssRefset_ExtendedMapSnapshot_INT_20250101.txt",
+        )
+        .unwrap();
+        let result = parse_refset_view_reader::<ExtendedMapRefsetMember, _>(
+            contents.as_bytes(),
+            pattern,
+            Rf2Config::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(Rf2Error::UnexpectedRefsetFields { .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_index() {
+        let row = |id: &str, priority: u32, rule: &str, target: &str| ExtendedMapRefsetMember {
+            id: id.to_string(),
+            effective_time: 20250101,
+            active: true,
+            module_id: 449080006,
+            refset_id: well_known::ICD10_EXTENDED_MAP_REFSET,
+            referenced_component_id: 100,
+            map_group: 1,
+            map_priority: priority,
+            map_rule: rule.to_string(),
+            map_advice: String::new(),
+            map_target: target.to_string(),
+            correlation_id: 447561005,
+            map_category_id: 447637006,
+        };
+
+        let mut index = ExtendedMapIndex::new();
+        index.apply_members([
+            row("a", 1, "IFA 248152002 | Female (finding) |", "N94.9"),
+            row("b", 2, "OTHERWISE TRUE", "N50.9"),
+        ]);
+        assert_eq!(index.len(), 2);
+
+        let female = MapContext::new().with_sex(Sex::Female);
+        let chosen = index.evaluate(well_known::ICD10_EXTENDED_MAP_REFSET, 100, &female);
+        assert_eq!(chosen[0].target(), Some("N94.9"));
+
+        let chosen = index.evaluate(
+            well_known::ICD10_EXTENDED_MAP_REFSET,
+            100,
+            &MapContext::new(),
+        );
+        assert_eq!(chosen[0].target(), Some("N50.9"));
+
+        assert!(index.evaluate(1, 100, &female).is_empty());
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let original = ExtendedMapRefsetMember {
+            id: "a".to_string(),
+            effective_time: 20020131,
+            active: true,
+            module_id: 449080006,
+            refset_id: well_known::ICD10_EXTENDED_MAP_REFSET,
+            referenced_component_id: 100,
+            map_group: 1,
+            map_priority: 1,
+            map_rule: "TRUE".to_string(),
+            map_advice: String::new(),
+            map_target: "N94.9".to_string(),
+            correlation_id: 447561005,
+            map_category_id: 447637006,
+        };
+        let retired = ExtendedMapRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..original.clone()
+        };
+
+        let mut index = ExtendedMapIndex::new();
+        let stats = index.apply_members([retired, original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(index.get(100).is_empty());
+        assert!(index.is_empty());
+    }
+}
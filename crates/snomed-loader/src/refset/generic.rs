@@ -0,0 +1,862 @@
+//! Generic reference set parser and index.
+//!
+//! Loads any reference set, typing its additional columns from the file name
+//! pattern: `der2_<columns>Refset_<Name><ReleaseType>_*.txt`, where
+//! `<columns>` has one letter per additional column (`c` component,
+//! `i` integer, `s` string). For example `der2_Refset_SimpleSnapshot` has no
+//! additional columns and `der2_iisssccRefset_ExtendedMapSnapshot` has seven.
+//!
+//! The names and meaning of the columns come from the reference set
+//! descriptor (`der2_cciRefset_RefsetDescriptorSnapshot_*.txt`), which is
+//! itself a reference set and is loaded into the same [`RefsetIndex`].
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use csv::StringRecord;
+use snomed_types::{well_known, RefsetFieldType, RefsetMember, RefsetValue, SctId};
+
+use super::versions::{MemberRow, MemberVersions};
+use crate::parser::{check_header, field, parse, Rf2Parser, Rf2Record};
+use crate::source::Rf2Reader;
+use crate::types::{DeltaStats, ReleaseType, Rf2Config, Rf2Error, Rf2Result, RowError};
+
+/// Columns shared by every reference set.
+///
+/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId
+const REFSET_COLUMNS: &[&str] = &[
+    "id",
+    "effectiveTime",
+    "active",
+    "moduleId",
+    "refsetId",
+    "referencedComponentId",
+];
+
+/// Layout of a reference set file, decoded from its file name.
+///
+/// # Example
+///
+/// ```
+/// use snomed_loader::refset::RefsetPattern;
+/// use snomed_loader::ReleaseType;
+/// use snomed_types::RefsetFieldType;
+///
+/// let pattern =
+///     RefsetPattern::from_file_name("der2_cRefset_LanguageSnapshot-en_INT_20250101.txt").unwrap();
+/// assert_eq!(pattern.name, "Language");
+/// assert_eq!(pattern.fields, vec![RefsetFieldType::Component]);
+/// assert_eq!(pattern.release_type, ReleaseType::Snapshot);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefsetPattern {
+    /// Reference set name from the file name (e.g. "Language", "SimpleMap").
+    pub name: String,
+    /// Types of the additional columns, in file order.
+    pub fields: Vec<RefsetFieldType>,
+    /// Release type from the file name.
+    pub release_type: ReleaseType,
+}
+
+impl RefsetPattern {
+    /// Decodes a reference set file name.
+    ///
+    /// Returns `None` if the name does not follow the RF2 reference set
+    /// naming convention.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let rest = file_name
+            .strip_prefix("der2_")
+            .or_else(|| file_name.strip_prefix("sct2_"))?;
+        let (codes, rest) = rest.split_once("Refset_")?;
+        let fields = codes
+            .chars()
+            .map(RefsetFieldType::from_code)
+            .collect::<Option<Vec<_>>>()?;
+
+        // "LanguageSnapshot-en" -> ("Language", Snapshot)
+        let content_type = rest.split('_').next()?;
+        [ReleaseType::Snapshot, ReleaseType::Delta, ReleaseType::Full]
+            .into_iter()
+            .find_map(|release_type| {
+                let index = content_type.rfind(release_type.as_str())?;
+                let suffix = &content_type[index + release_type.as_str().len()..];
+                if index == 0 || !(suffix.is_empty() || suffix.starts_with('-')) {
+                    return None;
+                }
+                Some(Self {
+                    name: content_type[..index].to_string(),
+                    fields: fields.clone(),
+                    release_type,
+                })
+            })
+    }
+
+    /// Returns the total number of columns in files with this pattern.
+    pub fn column_count(&self) -> usize {
+        REFSET_COLUMNS.len() + self.fields.len()
+    }
+
+    /// Parses a record into a member, typing the additional columns.
+    ///
+    /// Errors in an additional column are tagged with its name from `columns`.
+    fn parse_member(
+        &self,
+        record: &StringRecord,
+        columns: &[&'static str],
+    ) -> Rf2Result<RefsetMember> {
+        if record.len() != self.column_count() {
+            return Err(Rf2Error::InvalidHeader {
+                expected: self.column_count(),
+                found: record.len(),
+            });
+        }
+
+        let values = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(offset, field_type)| {
+                let index = REFSET_COLUMNS.len() + offset;
+                let column = columns.get(index).copied().unwrap_or("");
+                field(record, index, column, |value| match field_type {
+                    RefsetFieldType::Component => parse::sctid(value).map(RefsetValue::Component),
+                    RefsetFieldType::Integer => parse::integer(value).map(RefsetValue::Integer),
+                    RefsetFieldType::String => Ok(RefsetValue::String(value.to_string())),
+                })
+            })
+            .collect::<Rf2Result<Vec<_>>>()?;
+
+        Ok(RefsetMember {
+            id: field(record, 0, "id", Ok)?.to_string(),
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            refset_id: field(record, 4, "refsetId", parse::sctid)?,
+            referenced_component_id: field(record, 5, "referencedComponentId", parse::sctid)?,
+            values,
+        })
+    }
+}
+
+/// A raw row; typing the additional columns needs the file's [`RefsetPattern`].
+struct RefsetRow {
+    active: bool,
+    record: StringRecord,
+}
+
+impl Rf2Record for RefsetRow {
+    const EXPECTED_COLUMNS: &'static [&'static str] = REFSET_COLUMNS;
+
+    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
+        Ok(RefsetRow {
+            active: field(record, 2, "active", parse::boolean)?,
+            record: record.clone(),
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+/// A typed view of the members of one kind of reference set.
+///
+/// Views are read through the generic loader: the file name gives the types
+/// of the additional columns (see [`RefsetPattern`]), which must begin with
+/// the view's [`FIELDS`](Self::FIELDS), and each row is parsed into a
+/// [`RefsetMember`] before the view takes its values from it.
+pub trait RefsetView: Sized {
+    /// Names and types of the additional columns the view reads, in file order.
+    ///
+    /// Files may have further columns after these.
+    const FIELDS: &'static [(&'static str, RefsetFieldType)];
+
+    /// Builds the view from a member whose values start with [`FIELDS`](Self::FIELDS).
+    fn from_member(member: RefsetMember) -> Rf2Result<Self>;
+}
+
+impl RefsetView for RefsetMember {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] = &[];
+
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        Ok(member)
+    }
+}
+
+/// The additional values of a member, taken in column order by a [`RefsetView`].
+pub(crate) struct MemberValues {
+    values: std::vec::IntoIter<RefsetValue>,
+    fields: std::slice::Iter<'static, (&'static str, RefsetFieldType)>,
+}
+
+impl MemberValues {
+    pub(crate) fn new<V: RefsetView>(values: Vec<RefsetValue>) -> Self {
+        Self {
+            values: values.into_iter(),
+            fields: V::FIELDS.iter(),
+        }
+    }
+
+    /// Takes the next value and converts it, naming the column in any error.
+    ///
+    /// A missing value, or one that `get` does not accept because it is of
+    /// another type, is reported as [`Rf2Error::MissingColumn`].
+    fn take<V, T>(
+        &mut self,
+        get: impl FnOnce(RefsetValue) -> Option<V>,
+        convert: impl FnOnce(V) -> Rf2Result<T>,
+    ) -> Rf2Result<T> {
+        let column = self.fields.next().map_or("", |(name, _)| *name);
+        self.values
+            .next()
+            .and_then(get)
+            .ok_or_else(|| Rf2Error::MissingColumn {
+                column: column.to_string(),
+            })
+            .and_then(convert)
+            .map_err(|error| error.in_column(column))
+    }
+
+    /// Takes a component column.
+    pub(crate) fn component(&mut self) -> Rf2Result<SctId> {
+        self.take(|value| value.as_component(), Ok)
+    }
+
+    /// Takes an integer column and converts it with `convert`.
+    pub(crate) fn integer_with<T>(
+        &mut self,
+        convert: impl FnOnce(i64) -> Rf2Result<T>,
+    ) -> Rf2Result<T> {
+        self.take(|value| value.as_integer(), convert)
+    }
+
+    /// Takes an integer column that must fit in a `u32`.
+    pub(crate) fn unsigned(&mut self) -> Rf2Result<u32> {
+        self.integer_with(|value| {
+            u32::try_from(value).map_err(|_| Rf2Error::InvalidInteger {
+                value: value.to_string(),
+            })
+        })
+    }
+
+    /// Takes a string column and converts it with `convert`.
+    pub(crate) fn string_with<T>(
+        &mut self,
+        convert: impl FnOnce(String) -> Rf2Result<T>,
+    ) -> Rf2Result<T> {
+        self.take(
+            |value| match value {
+                RefsetValue::String(text) => Some(text),
+                _ => None,
+            },
+            convert,
+        )
+    }
+
+    /// Takes a string column.
+    pub(crate) fn string(&mut self) -> Rf2Result<String> {
+        self.string_with(Ok)
+    }
+
+    /// Takes a string column that may be empty.
+    pub(crate) fn optional_string(&mut self) -> Rf2Result<Option<String>> {
+        self.string_with(|text| Ok((!text.is_empty()).then_some(text)))
+    }
+}
+
+/// Streaming parser for reference set files, yielding typed views.
+///
+/// Created by [`parse_refset_file`], [`parse_refset_view_file`] and their
+/// reader counterparts. Rows that fail to parse are yielded as
+/// [`Rf2Error::Row`], like [`Rf2Parser`].
+pub struct RefsetParser<R: Read, V = RefsetMember> {
+    parser: Rf2Parser<R, RefsetRow>,
+    pattern: RefsetPattern,
+    /// Names of the columns the view reads, for row errors.
+    columns: Vec<&'static str>,
+    _marker: PhantomData<V>,
+}
+
+impl<R: Read, V: RefsetView> RefsetParser<R, V> {
+    /// Checks the header against `pattern` and the view's columns.
+    fn new(parser: Rf2Parser<R, RefsetRow>, pattern: RefsetPattern) -> Rf2Result<Self> {
+        let headers = parser.headers();
+        if headers.len() != pattern.column_count() {
+            return Err(Rf2Error::InvalidHeader {
+                expected: pattern.column_count(),
+                found: headers.len(),
+            });
+        }
+
+        let expected: Vec<RefsetFieldType> = V::FIELDS.iter().map(|(_, kind)| *kind).collect();
+        if !pattern.fields.starts_with(&expected) {
+            return Err(Rf2Error::UnexpectedRefsetFields {
+                path: parser.path().display().to_string(),
+                expected: expected.iter().map(|kind| kind.code()).collect(),
+                found: pattern.fields.iter().map(|kind| kind.code()).collect(),
+            });
+        }
+
+        let columns: Vec<&'static str> = REFSET_COLUMNS
+            .iter()
+            .copied()
+            .chain(V::FIELDS.iter().map(|(name, _)| *name))
+            .collect();
+        check_header(headers.iter(), &columns)?;
+
+        Ok(Self {
+            parser,
+            pattern,
+            columns,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the file being parsed (empty when parsing from a reader).
+    pub fn path(&self) -> &Path {
+        self.parser.path()
+    }
+
+    /// Returns the number of records read so far, including malformed ones.
+    pub fn records_read(&self) -> usize {
+        self.parser.records_read()
+    }
+}
+
+impl<R: Read, V: RefsetView> Iterator for RefsetParser<R, V> {
+    type Item = Rf2Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = match self.parser.next()? {
+            Ok(row) => row,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let parsed = self
+            .pattern
+            .parse_member(&row.record, &self.columns)
+            .and_then(V::from_member);
+        Some(parsed.map_err(|error| {
+            Rf2Error::Row(Box::new(RowError::new(
+                self.parser.path(),
+                self.parser.line(),
+                error,
+            )))
+        }))
+    }
+}
+
+/// Decodes the [`RefsetPattern`] of a reference set file from its name.
+fn pattern_of(path: &Path) -> Rf2Result<RefsetPattern> {
+    path.file_name()
+        .and_then(|name| RefsetPattern::from_file_name(&name.to_string_lossy()))
+        .ok_or_else(|| Rf2Error::InvalidFileName {
+            path: path.display().to_string(),
+        })
+}
+
+/// Parses any reference set file, typing its columns from the file name.
+///
+/// # Arguments
+/// * `path` - Path to the reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `RefsetMember` records.
+///
+/// # Errors
+/// Returns [`Rf2Error::InvalidFileName`] if the file name does not follow
+/// the reference set naming pattern.
+pub fn parse_refset_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<Rf2Reader>> {
+    parse_refset_view_file(path, config)
+}
+
+/// Parses reference set rows from a reader with a known pattern.
+pub fn parse_refset_reader<R: Read>(
+    reader: R,
+    pattern: RefsetPattern,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<R>> {
+    parse_refset_view_reader(reader, pattern, config)
+}
+
+/// Parses a reference set file into typed views.
+///
+/// # Errors
+/// Returns [`Rf2Error::InvalidFileName`] if the file name does not follow
+/// the reference set naming pattern, and
+/// [`Rf2Error::UnexpectedRefsetFields`] if its columns are not those of `V`.
+pub fn parse_refset_view_file<V: RefsetView, P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<Rf2Reader, V>> {
+    let path = path.as_ref();
+    let pattern = pattern_of(path)?;
+    RefsetParser::new(Rf2Parser::from_path(path, config)?, pattern)
+}
+
+/// Parses reference set rows from a reader with a known pattern into typed views.
+pub fn parse_refset_view_reader<V: RefsetView, R: Read>(
+    reader: R,
+    pattern: RefsetPattern,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<R, V>> {
+    RefsetParser::new(Rf2Parser::from_reader(reader, config)?, pattern)
+}
+
+impl MemberRow for RefsetMember {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// An additional column of a reference set, from the reference set descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefsetAttribute {
+    /// Concept naming the column (e.g. "map target").
+    pub attribute_id: SctId,
+    /// Concept giving the column's type (e.g. "component type").
+    pub attribute_type_id: SctId,
+    /// Column position; 0 is the referenced component.
+    pub order: i64,
+}
+
+/// Index of active reference set members by refset id and referenced component.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::{parse_refset_file, RefsetIndex};
+/// use snomed_loader::Rf2Config;
+///
+/// let mut index = RefsetIndex::new();
+/// for path in [descriptor_path, simple_map_path] {
+///     index.apply_members(parse_refset_file(path, Rf2Config::default())?.flatten());
+/// }
+///
+/// for member in index.get(simple_map_refset_id, 73211009) {
+///     let target = index.attribute_value(member, map_target_attribute_id);
+///     println!("{:?}", target);
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefsetIndex {
+    by_refset: HashMap<SctId, HashMap<SctId, Vec<RefsetMember>>>,
+    /// Reverse index: the reference sets each component belongs to.
+    by_component: HashMap<SctId, Vec<SctId>>,
+    versions: MemberVersions,
+}
+
+impl RefsetIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of a reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_refset_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Members are keyed by their row id. Active members are upserted and
+    /// inactive ones retire their row; versions older than the stored or
+    /// retired version of the same member are skipped.
+    pub fn apply_members(&mut self, members: impl IntoIterator<Item = RefsetMember>) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let refset_id = member.refset_id;
+            let component_id = member.referenced_component_id;
+            let components = self.by_refset.entry(refset_id).or_default();
+            let entries = components.entry(component_id).or_default();
+            let was_member = !entries.is_empty();
+            let id = member.id.clone();
+            let effective_time = member.effective_time;
+            let row = member.active.then_some(member);
+            self.versions
+                .apply(entries, &id, effective_time, row, &mut stats);
+
+            let is_member = !entries.is_empty();
+            if !is_member {
+                components.remove(&component_id);
+            }
+            if components.is_empty() {
+                self.by_refset.remove(&refset_id);
+            }
+
+            if is_member && !was_member {
+                self.by_component
+                    .entry(component_id)
+                    .or_default()
+                    .push(refset_id);
+            } else if was_member && !is_member {
+                if let Some(refset_ids) = self.by_component.get_mut(&component_id) {
+                    refset_ids.retain(|&id| id != refset_id);
+                    if refset_ids.is_empty() {
+                        self.by_component.remove(&component_id);
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Gets the members of a reference set that refer to a component.
+    pub fn get(&self, refset_id: SctId, component_id: SctId) -> &[RefsetMember] {
+        self.by_refset
+            .get(&refset_id)
+            .and_then(|components| components.get(&component_id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns true if a component is an active member of a reference set.
+    pub fn contains(&self, refset_id: SctId, component_id: SctId) -> bool {
+        !self.get(refset_id, component_id).is_empty()
+    }
+
+    /// Returns an iterator over the components referenced by a reference set.
+    pub fn component_ids(&self, refset_id: SctId) -> impl Iterator<Item = SctId> + '_ {
+        self.by_refset
+            .get(&refset_id)
+            .into_iter()
+            .flat_map(|components| components.keys().copied())
+    }
+
+    /// Gets the reference sets a component is an active member of.
+    pub fn refsets_for(&self, component_id: SctId) -> &[SctId] {
+        self.by_component
+            .get(&component_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over all members of a reference set.
+    pub fn members(&self, refset_id: SctId) -> impl Iterator<Item = &RefsetMember> {
+        self.by_refset
+            .get(&refset_id)
+            .into_iter()
+            .flat_map(|components| components.values().flatten())
+    }
+
+    /// Returns an iterator over the ids of all loaded reference sets.
+    pub fn refset_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        self.by_refset.keys().copied()
+    }
+
+    /// Returns the number of members in a reference set.
+    pub fn member_count(&self, refset_id: SctId) -> usize {
+        self.by_refset
+            .get(&refset_id)
+            .map(|components| components.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Gets the columns of a reference set from the loaded descriptor, sorted by order.
+    ///
+    /// Returns an empty list if the descriptor is not loaded or does not
+    /// describe the reference set.
+    pub fn descriptor(&self, refset_id: SctId) -> Vec<RefsetAttribute> {
+        let mut attributes: Vec<RefsetAttribute> = self
+            .get(well_known::REFSET_DESCRIPTOR_REFSET, refset_id)
+            .iter()
+            .filter_map(|member| {
+                Some(RefsetAttribute {
+                    attribute_id: member.value(0)?.as_component()?,
+                    attribute_type_id: member.value(1)?.as_component()?,
+                    order: member.value(2)?.as_integer()?,
+                })
+            })
+            .collect();
+        attributes.sort_by_key(|attribute| attribute.order);
+        attributes
+    }
+
+    /// Gets a member's value for a descriptor attribute (e.g. "map target").
+    ///
+    /// Returns `None` if the descriptor does not list the attribute as one of
+    /// the member's additional columns.
+    pub fn attribute_value<'a>(
+        &self,
+        member: &'a RefsetMember,
+        attribute_id: SctId,
+    ) -> Option<&'a RefsetValue> {
+        let attribute = self
+            .descriptor(member.refset_id)
+            .into_iter()
+            .find(|attribute| attribute.attribute_id == attribute_id)?;
+        let index = usize::try_from(attribute.order).ok()?.checked_sub(1)?;
+        member.value(index)
+    }
+
+    /// Returns the number of active members.
+    pub fn len(&self) -> usize {
+        self.by_refset
+            .values()
+            .flat_map(HashMap::values)
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_refset.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let member_size: usize = self
+            .by_refset
+            .values()
+            .flat_map(HashMap::values)
+            .flatten()
+            .map(|member| {
+                let strings: usize = member
+                    .values
+                    .iter()
+                    .filter_map(RefsetValue::as_str)
+                    .map(str::len)
+                    .sum();
+                size_of::<RefsetMember>()
+                    + member.id.len()
+                    + member.values.len() * size_of::<RefsetValue>()
+                    + strings
+            })
+            .sum();
+        let reverse_size: usize = self
+            .by_component
+            .values()
+            .map(|refset_ids| size_of::<SctId>() * (1 + refset_ids.len()))
+            .sum();
+
+        member_size + reverse_size + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTOR: &str = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tattributeDescription\tattributeType\tattributeOrder
+d1\t20020131\t1\t900000000000012004\t900000000000456007\t447562003\t449608002\t900000000000461009\t0
+d2\t20020131\t1\t900000000000012004\t900000000000456007\t447562003\t900000000000505001\t900000000000465000\t1
+d3\t20020131\t1\t900000000000012004\t900000000000456007\t447562003\t900000000000501005\t900000000000465000\t2
+";
+
+    const SIMPLE_MAP: &str = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tmapTarget\tmapAdvice
+m1\t20020131\t1\t900000000000207008\t447562003\t73211009\tE14\tALWAYS E14
+m2\t20020131\t0\t900000000000207008\t447562003\t22298006\tI21\t
+";
+
+    fn load(index: &mut RefsetIndex, contents: &str, file_name: &str) -> DeltaStats {
+        let pattern = RefsetPattern::from_file_name(file_name).unwrap();
+        let members = parse_refset_reader(contents.as_bytes(), pattern, Rf2Config::default())
+            .unwrap()
+            .collect::<Rf2Result<Vec<_>>>()
+            .unwrap();
+        index.apply_members(members)
+    }
+
+    #[test]
+    fn test_pattern_from_file_name() {
+        let simple = RefsetPattern::from_file_name("der2_Refset_SimpleDelta_INT_20250101.txt");
+        assert_eq!(
+            simple,
+            Some(RefsetPattern {
+                name: "Simple".to_string(),
+                fields: vec![],
+                release_type: ReleaseType::Delta,
+            })
+        );
+
+        let extended =
+            RefsetPattern::from_file_name("der2_iisssccRefset_ExtendedMapFull_INT_20250101.txt")
+                .unwrap();
+        assert_eq!(extended.name, "ExtendedMap");
+        assert_eq!(extended.fields.len(), 7);
+        assert_eq!(extended.column_count(), 13);
+        assert_eq!(extended.release_type, ReleaseType::Full);
+
+        let owl =
+            RefsetPattern::from_file_name("sct2_sRefset_OWLExpressionSnapshot_INT_20250101.txt")
+                .unwrap();
+        assert_eq!(owl.name, "OWLExpression");
+
+        assert!(RefsetPattern::from_file_name("sct2_Concept_Snapshot_INT_20250101.txt").is_none());
+        assert!(
+            RefsetPattern::from_file_name("der2_xRefset_SimpleSnapshot_INT_20250101.txt").is_none()
+        );
+        assert!(RefsetPattern::from_file_name("der2_Refset_Simple_INT_20250101.txt").is_none());
+    }
+
+    #[test]
+    fn test_load_with_descriptor() {
+        let mut index = RefsetIndex::new();
+        load(
+            &mut index,
+            DESCRIPTOR,
+            "der2_cciRefset_RefsetDescriptorSnapshot_INT_20250101.txt",
+        );
+        let stats = load(
+            &mut index,
+            SIMPLE_MAP,
+            "der2_ssRefset_SimpleMapSnapshot_INT_20250101.txt",
+        );
+
+        // The inactive row is filtered out by the default config
+        assert_eq!(stats.upserted, 1);
+        assert_eq!(index.member_count(447562003), 1);
+        assert_eq!(index.refset_ids().count(), 2);
+
+        let member = &index.get(447562003, 73211009)[0];
+        assert_eq!(
+            member.values,
+            vec![
+                RefsetValue::String("E14".to_string()),
+                RefsetValue::String("ALWAYS E14".to_string()),
+            ]
+        );
+
+        let descriptor = index.descriptor(447562003);
+        assert_eq!(descriptor.len(), 3);
+        assert_eq!(descriptor[0].order, 0);
+        assert_eq!(
+            index
+                .attribute_value(member, 900000000000505001)
+                .and_then(RefsetValue::as_str),
+            Some("E14")
+        );
+        assert_eq!(index.attribute_value(member, 449608002), None);
+    }
+
+    #[test]
+    fn test_membership() {
+        let simple = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId
+a\t20020131\t1\t900000000000207008\t1000\t73211009
+b\t20020131\t1\t900000000000207008\t1000\t22298006
+c\t20020131\t1\t900000000000207008\t2000\t73211009
+";
+        let mut index = RefsetIndex::new();
+        load(
+            &mut index,
+            simple,
+            "der2_Refset_SimpleSnapshot_INT_20250101.txt",
+        );
+
+        assert!(index.contains(1000, 73211009));
+        assert!(!index.contains(2000, 22298006));
+        let mut members: Vec<_> = index.component_ids(1000).collect();
+        members.sort_unstable();
+        assert_eq!(members, vec![22298006, 73211009]);
+        let mut refsets = index.refsets_for(73211009).to_vec();
+        refsets.sort_unstable();
+        assert_eq!(refsets, vec![1000, 2000]);
+
+        // Inactivating the only member row drops the reverse entry
+        let delta = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId
+c\t20250201\t0\t900000000000207008\t2000\t73211009
+";
+        let pattern =
+            RefsetPattern::from_file_name("der2_Refset_SimpleDelta_INT_20250201.txt").unwrap();
+        let rows = parse_refset_reader(
+            delta.as_bytes(),
+            pattern,
+            Rf2Config {
+                active_only: false,
+                ..Rf2Config::default()
+            },
+        )
+        .unwrap()
+        .flatten();
+        assert_eq!(index.apply_members(rows).removed, 1);
+        assert_eq!(index.refsets_for(73211009), &[1000]);
+        assert_eq!(index.member_count(2000), 0);
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let full = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId
+a\t20250201\t0\t900000000000207008\t1000\t73211009
+a\t20020131\t1\t900000000000207008\t1000\t73211009
+";
+        let pattern =
+            RefsetPattern::from_file_name("der2_Refset_SimpleFull_INT_20250201.txt").unwrap();
+        let rows = parse_refset_reader(
+            full.as_bytes(),
+            pattern,
+            Rf2Config {
+                active_only: false,
+                ..Rf2Config::default()
+            },
+        )
+        .unwrap()
+        .flatten();
+
+        let mut index = RefsetIndex::new();
+        let stats = index.apply_members(rows);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(!index.contains(1000, 73211009));
+        assert!(index.refsets_for(73211009).is_empty());
+        assert_eq!(index.member_count(1000), 0);
+    }
+
+    #[test]
+    fn test_typed_columns() {
+        let contents = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\ta\tb\tc
+x\t20020131\t1\t900000000000207008\t100\t200\t300\t-5\ttext
+";
+        let pattern =
+            RefsetPattern::from_file_name("der2_cisRefset_TestSnapshot_INT_20250101.txt").unwrap();
+        let members: Vec<_> =
+            parse_refset_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect::<Rf2Result<_>>()
+                .unwrap();
+        assert_eq!(members[0].value(0), Some(&RefsetValue::Component(300)));
+        assert_eq!(members[0].value(1), Some(&RefsetValue::Integer(-5)));
+        assert_eq!(
+            members[0].value(2).and_then(RefsetValue::as_str),
+            Some("text")
+        );
+
+        // A file whose columns don't match its name is rejected
+        let pattern =
+            RefsetPattern::from_file_name("der2_cRefset_TestSnapshot_INT_20250101.txt").unwrap();
+        let result = parse_refset_reader(contents.as_bytes(), pattern, Rf2Config::default());
+        assert!(matches!(
+            result,
+            Err(Rf2Error::InvalidHeader {
+                expected: 7,
+                found: 9
+            })
+        ));
+    }
+}
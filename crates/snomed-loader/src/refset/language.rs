@@ -0,0 +1,350 @@
+//! Language reference set parser and acceptability index.
+//!
+//! Parses files matching pattern: `der2_cRefset_LanguageSnapshot-*_*.txt`
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use csv::StringRecord;
+use snomed_types::{Acceptability, LanguageRefsetMember, SctId};
+
+use super::versions::{MemberRow, MemberVersions};
+use crate::parser::{field, parse, Rf2Parser, Rf2Record};
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+/// Expected columns for language reference sets.
+///
+/// Order: id, effectiveTime, active, moduleId, refsetId, referencedComponentId,
+/// acceptabilityId
+const LANGUAGE_COLUMNS: &[&str] = &[
+    "id",
+    "effectiveTime",
+    "active",
+    "moduleId",
+    "refsetId",
+    "referencedComponentId",
+    "acceptabilityId",
+];
+
+impl Rf2Record for LanguageRefsetMember {
+    const EXPECTED_COLUMNS: &'static [&'static str] = LANGUAGE_COLUMNS;
+
+    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
+        Ok(LanguageRefsetMember {
+            id: field(record, 0, "id", Ok)?.to_string(),
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            refset_id: field(record, 4, "refsetId", parse::sctid)?,
+            referenced_component_id: field(record, 5, "referencedComponentId", parse::sctid)?,
+            acceptability_id: field(record, 6, "acceptabilityId", parse::sctid)?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+/// Parses a language reference set from a file.
+///
+/// # Arguments
+/// * `path` - Path to the language reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `LanguageRefsetMember` records.
+pub fn parse_language_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<impl Iterator<Item = Rf2Result<LanguageRefsetMember>>> {
+    let parser = Rf2Parser::<_, LanguageRefsetMember>::from_path(path, config)?;
+    Ok(parser)
+}
+
+/// Acceptability of a description given by one language reference set member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+struct LanguageEntry {
+    id: String,
+    refset_id: SctId,
+    acceptability: Acceptability,
+    effective_time: u32,
+}
+
+impl MemberRow for LanguageEntry {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Index of description acceptability per language reference set.
+///
+/// Rows are keyed by member id, so a retired member and the member replacing
+/// it resolve the same way whatever order they are applied in. A description
+/// has at most one active acceptability per language refset.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::LanguageIndex;
+/// use snomed_types::{well_known, Acceptability};
+///
+/// let index = LanguageIndex::load("der2_cRefset_LanguageSnapshot-en_INT_20250101.txt")?;
+/// let acceptability = index.acceptability(description_id, well_known::GB_ENGLISH_LANGUAGE_REFSET);
+/// assert_eq!(acceptability, Some(Acceptability::Preferred));
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanguageIndex {
+    by_description: HashMap<SctId, Vec<LanguageEntry>>,
+    versions: MemberVersions,
+}
+
+impl LanguageIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of a language reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_language_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Active members with a known acceptability are upserted; inactive members
+    /// retire their row. Versions older than the stored or retired version of
+    /// the same member are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = LanguageRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let description_id = member.referenced_component_id;
+            let entry = member
+                .acceptability()
+                .filter(|_| member.active)
+                .map(|acceptability| LanguageEntry {
+                    id: member.id.clone(),
+                    refset_id: member.refset_id,
+                    acceptability,
+                    effective_time: member.effective_time,
+                });
+            let entries = self.by_description.entry(description_id).or_default();
+            self.versions.apply(
+                entries,
+                &member.id,
+                member.effective_time,
+                entry,
+                &mut stats,
+            );
+            if entries.is_empty() {
+                self.by_description.remove(&description_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets the acceptability of a description in a language refset.
+    pub fn acceptability(&self, description_id: SctId, refset_id: SctId) -> Option<Acceptability> {
+        self.by_description
+            .get(&description_id)?
+            .iter()
+            .find(|e| e.refset_id == refset_id)
+            .map(|e| e.acceptability)
+    }
+
+    /// Returns true if the description is preferred in the language refset.
+    pub fn is_preferred(&self, description_id: SctId, refset_id: SctId) -> bool {
+        self.acceptability(description_id, refset_id) == Some(Acceptability::Preferred)
+    }
+
+    /// Returns `(refset_id, acceptability)` for every language refset the description is in.
+    pub fn get(&self, description_id: SctId) -> impl Iterator<Item = (SctId, Acceptability)> + '_ {
+        self.by_description
+            .get(&description_id)
+            .into_iter()
+            .flatten()
+            .map(|e| (e.refset_id, e.acceptability))
+    }
+
+    /// Returns the ids of all language refsets in the index.
+    pub fn refset_ids(&self) -> BTreeSet<SctId> {
+        self.by_description
+            .values()
+            .flatten()
+            .map(|e| e.refset_id)
+            .collect()
+    }
+
+    /// Returns the number of active members.
+    pub fn len(&self) -> usize {
+        self.by_description.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let ids: usize = self
+            .by_description
+            .values()
+            .flatten()
+            .map(|e| e.id.len())
+            .sum();
+        self.by_description.len() * size_of::<SctId>()
+            + self.len() * size_of::<LanguageEntry>()
+            + ids
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const US: SctId = 900000000000509007;
+    const GB: SctId = 900000000000508004;
+
+    fn make_member(
+        description_id: SctId,
+        refset_id: SctId,
+        acceptability_id: SctId,
+    ) -> LanguageRefsetMember {
+        LanguageRefsetMember {
+            id: format!("{}-{}", description_id, refset_id),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id,
+            referenced_component_id: description_id,
+            acceptability_id,
+        }
+    }
+
+    #[test]
+    fn test_parse_language_member() {
+        let record = StringRecord::from(vec![
+            "80000000-0000-0000-0000-000000000000",
+            "20020131",
+            "1",
+            "900000000000207008",
+            "900000000000509007",
+            "1234567011",
+            "900000000000548007",
+        ]);
+
+        let member = LanguageRefsetMember::from_record(&record).unwrap();
+        assert_eq!(member.refset_id, US);
+        assert_eq!(member.referenced_component_id, 1234567011);
+        assert!(member.is_preferred());
+    }
+
+    #[test]
+    fn test_apply_members() {
+        let mut index = LanguageIndex::new();
+        let stats = index.apply_members([
+            make_member(1, US, Acceptability::PREFERRED_ID),
+            make_member(1, GB, Acceptability::ACCEPTABLE_ID),
+            make_member(2, GB, Acceptability::PREFERRED_ID),
+        ]);
+
+        assert_eq!(stats.upserted, 3);
+        assert_eq!(index.len(), 3);
+        assert!(index.is_preferred(1, US));
+        assert_eq!(index.acceptability(1, GB), Some(Acceptability::Acceptable));
+        assert_eq!(index.refset_ids(), BTreeSet::from([US, GB]));
+
+        // Newer inactivation removes the entry; older rows are ignored
+        let inactivated = LanguageRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member(2, GB, Acceptability::PREFERRED_ID)
+        };
+        let stale = LanguageRefsetMember {
+            effective_time: 20010131,
+            ..make_member(1, US, Acceptability::ACCEPTABLE_ID)
+        };
+        let stats = index.apply_members([inactivated, stale]);
+
+        assert_eq!(stats.removed, 1);
+        assert_eq!(stats.skipped, 1);
+        assert!(index.acceptability(2, GB).is_none());
+        assert!(index.is_preferred(1, US));
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let retired = LanguageRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member(1, GB, Acceptability::PREFERRED_ID)
+        };
+        let original = make_member(1, GB, Acceptability::PREFERRED_ID);
+
+        let mut index = LanguageIndex::new();
+        let stats = index.apply_members([retired, original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(index.acceptability(1, GB).is_none());
+        assert_eq!(index.get(1).count(), 0);
+        assert!(index.refset_ids().is_empty());
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_replacement_member_is_order_independent() {
+        // A release retires one member and adds another for the same
+        // (description, refset) with the same effective time
+        let retired = LanguageRefsetMember {
+            id: "old".to_string(),
+            effective_time: 20250201,
+            active: false,
+            ..make_member(1, GB, Acceptability::ACCEPTABLE_ID)
+        };
+        let replacement = LanguageRefsetMember {
+            id: "new".to_string(),
+            effective_time: 20250201,
+            ..make_member(1, GB, Acceptability::PREFERRED_ID)
+        };
+        let original = LanguageRefsetMember {
+            id: "old".to_string(),
+            ..make_member(1, GB, Acceptability::ACCEPTABLE_ID)
+        };
+
+        let orders = [
+            [original.clone(), retired.clone(), replacement.clone()],
+            [replacement.clone(), retired.clone(), original.clone()],
+            [retired.clone(), original.clone(), replacement.clone()],
+            [replacement, original, retired],
+        ];
+        for members in orders {
+            let mut index = LanguageIndex::new();
+            index.apply_members(members);
+
+            assert!(index.is_preferred(1, GB));
+            assert_eq!(index.len(), 1);
+        }
+    }
+}
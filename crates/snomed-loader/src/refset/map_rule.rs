@@ -0,0 +1,345 @@
+//! Map rule parsing and evaluation for extended map reference sets.
+//!
+//! Rules in the ICD-10 map are written in a small language, e.g.
+//!
+//! ```text
+//! TRUE
+//! OTHERWISE TRUE
+//! IFA 248153007 | Male (finding) |
+//! IFA 38341003 | Hypertensive disorder, systemic arterial (disorder) |
+//! IFA 445518008 | Age at onset of clinical finding (observable entity) | < 15.0 years
+//! IFA 445518008 | Age at onset ... | >= 15.0 years AND IFA 445518008 | Age at onset ... | < 60.0 years
+//! ```
+//!
+//! A rule is evaluated against a [`MapContext`] describing the patient.
+
+use std::collections::HashSet;
+
+use snomed_types::{well_known, ExtendedMapRefsetMember, SctId};
+
+/// Patient sex, as tested by map rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sex {
+    /// Male (248153007).
+    Male,
+    /// Female (248152002).
+    Female,
+}
+
+/// Patient context that map rules are evaluated against.
+///
+/// # Example
+///
+/// ```
+/// use snomed_loader::refset::{MapContext, Sex};
+///
+/// let context = MapContext::new()
+///     .with_age(52.0)
+///     .with_sex(Sex::Female)
+///     .with_finding(38341003); // Hypertensive disorder
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MapContext {
+    /// Patient age in years, if known.
+    pub age_years: Option<f64>,
+    /// Patient sex, if known.
+    pub sex: Option<Sex>,
+    /// Co-existing SNOMED CT findings. A rule on a finding holds if it is in this set.
+    pub findings: HashSet<SctId>,
+}
+
+impl MapContext {
+    /// Creates an empty context (age, sex and findings unknown).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the patient's age in years.
+    pub fn with_age(mut self, years: f64) -> Self {
+        self.age_years = Some(years);
+        self
+    }
+
+    /// Sets the patient's sex.
+    pub fn with_sex(mut self, sex: Sex) -> Self {
+        self.sex = Some(sex);
+        self
+    }
+
+    /// Adds a co-existing finding.
+    pub fn with_finding(mut self, concept_id: SctId) -> Self {
+        self.findings.insert(concept_id);
+        self
+    }
+}
+
+/// Comparison operator in an age condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+    /// `=`
+    Equal,
+}
+
+impl Comparison {
+    fn parse(operator: &str) -> Option<Self> {
+        match operator {
+            "<" => Some(Self::Less),
+            "<=" => Some(Self::LessOrEqual),
+            ">" => Some(Self::Greater),
+            ">=" => Some(Self::GreaterOrEqual),
+            "=" => Some(Self::Equal),
+            _ => None,
+        }
+    }
+
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Self::Less => left < right,
+            Self::LessOrEqual => left <= right,
+            Self::Greater => left > right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Equal => left == right,
+        }
+    }
+}
+
+/// A single `IFA` condition of a map rule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapCondition {
+    /// The patient has the given sex.
+    Sex(Sex),
+    /// The patient has the given co-existing finding.
+    Finding(SctId),
+    /// The patient's age compares to a threshold in years.
+    Age {
+        /// Comparison applied as `age <comparison> years`.
+        comparison: Comparison,
+        /// Threshold in years.
+        years: f64,
+    },
+}
+
+impl MapCondition {
+    /// Parses `IFA <id> | <term> | [<operator> <value> <unit>]`.
+    fn parse(condition: &str) -> Option<Self> {
+        let rest = condition.trim().strip_prefix("IFA")?.trim_start();
+        let id_end = rest
+            .find(|c: char| c.is_whitespace() || c == '|')
+            .unwrap_or(rest.len());
+        let id: SctId = rest[..id_end].parse().ok()?;
+
+        // Skip the optional "| term |"
+        let mut rest = rest[id_end..].trim_start();
+        if let Some(after_bar) = rest.strip_prefix('|') {
+            let term_end = after_bar.find('|')?;
+            rest = after_bar[term_end + 1..].trim();
+        }
+
+        match id {
+            well_known::AGE_AT_ONSET | well_known::CURRENT_CHRONOLOGICAL_AGE => {
+                let mut tokens = rest.split_whitespace();
+                let comparison = Comparison::parse(tokens.next()?)?;
+                let value: f64 = tokens.next()?.parse().ok()?;
+                let years = match tokens.next()? {
+                    "year" | "years" => value,
+                    "month" | "months" => value / 12.0,
+                    "day" | "days" => value / 365.25,
+                    _ => return None,
+                };
+                tokens
+                    .next()
+                    .is_none()
+                    .then_some(Self::Age { comparison, years })
+            }
+            _ if !rest.is_empty() => None,
+            well_known::MALE => Some(Self::Sex(Sex::Male)),
+            well_known::FEMALE => Some(Self::Sex(Sex::Female)),
+            _ => Some(Self::Finding(id)),
+        }
+    }
+
+    /// Returns true if the condition holds; unknown patient data never satisfies it.
+    fn evaluate(&self, context: &MapContext) -> bool {
+        match self {
+            Self::Sex(sex) => context.sex == Some(*sex),
+            Self::Finding(id) => context.findings.contains(id),
+            Self::Age { comparison, years } => context
+                .age_years
+                .is_some_and(|age| comparison.holds(age, *years)),
+        }
+    }
+}
+
+/// A parsed map rule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapRule {
+    /// `TRUE` (or an empty rule) - always applies.
+    Always,
+    /// `OTHERWISE TRUE` - applies when no earlier rule in the group did.
+    Otherwise,
+    /// `IFA ... AND IFA ...` - applies when every condition holds.
+    All(Vec<MapCondition>),
+    /// A rule this evaluator does not understand; never applies.
+    Unsupported(String),
+}
+
+impl MapRule {
+    /// Parses a map rule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use snomed_loader::refset::{MapContext, MapRule, Sex};
+    ///
+    /// let rule = MapRule::parse("IFA 248153007 | Male (finding) |");
+    /// assert!(rule.evaluate(&MapContext::new().with_sex(Sex::Male)));
+    /// assert!(!rule.evaluate(&MapContext::new()));
+    /// ```
+    pub fn parse(rule: &str) -> Self {
+        let rule = rule.trim();
+        match rule {
+            "" | "TRUE" => return Self::Always,
+            "OTHERWISE TRUE" => return Self::Otherwise,
+            _ => {}
+        }
+
+        rule.split(" AND ")
+            .map(MapCondition::parse)
+            .collect::<Option<Vec<_>>>()
+            .map_or_else(|| Self::Unsupported(rule.to_string()), Self::All)
+    }
+
+    /// Returns true if the rule applies to the patient.
+    pub fn evaluate(&self, context: &MapContext) -> bool {
+        match self {
+            Self::Always | Self::Otherwise => true,
+            Self::All(conditions) => conditions.iter().all(|c| c.evaluate(context)),
+            Self::Unsupported(_) => false,
+        }
+    }
+}
+
+/// Chooses one map row per map group for a patient.
+///
+/// Within each group, rows are tried in `map_priority` order and the first
+/// whose rule applies is chosen. The result is sorted by map group. A chosen
+/// row may have no target (see [`ExtendedMapRefsetMember::target`]); its
+/// advice then explains why.
+pub fn evaluate_map<'a>(
+    rows: impl IntoIterator<Item = &'a ExtendedMapRefsetMember>,
+    context: &MapContext,
+) -> Vec<&'a ExtendedMapRefsetMember> {
+    let mut rows: Vec<_> = rows.into_iter().collect();
+    rows.sort_by_key(|row| (row.map_group, row.map_priority));
+
+    let mut chosen: Vec<&ExtendedMapRefsetMember> = Vec::new();
+    for row in rows {
+        if chosen.last().is_some_and(|c| c.map_group == row.map_group) {
+            continue;
+        }
+        if MapRule::parse(&row.map_rule).evaluate(context) {
+            chosen.push(row);
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_row(group: u32, priority: u32, rule: &str, target: &str) -> ExtendedMapRefsetMember {
+        ExtendedMapRefsetMember {
+            id: format!("{}-{}", group, priority),
+            effective_time: 20250101,
+            active: true,
+            module_id: 449080006,
+            refset_id: well_known::ICD10_EXTENDED_MAP_REFSET,
+            referenced_component_id: 100,
+            map_group: group,
+            map_priority: priority,
+            map_rule: rule.to_string(),
+            map_advice: String::new(),
+            map_target: target.to_string(),
+            correlation_id: 447561005,
+            map_category_id: 447637006,
+        }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(MapRule::parse("TRUE"), MapRule::Always);
+        assert_eq!(MapRule::parse("OTHERWISE TRUE"), MapRule::Otherwise);
+        assert_eq!(
+            MapRule::parse("IFA 248152002 | Female (finding) |"),
+            MapRule::All(vec![MapCondition::Sex(Sex::Female)])
+        );
+        assert_eq!(
+            MapRule::parse(
+                "IFA 445518008 | Age at onset of clinical finding (observable entity) | >= 15.0 years \
+                 AND IFA 445518008 | Age at onset of clinical finding (observable entity) | < 6 months"
+            ),
+            MapRule::All(vec![
+                MapCondition::Age {
+                    comparison: Comparison::GreaterOrEqual,
+                    years: 15.0
+                },
+                MapCondition::Age {
+                    comparison: Comparison::Less,
+                    years: 0.5
+                },
+            ])
+        );
+        assert_eq!(
+            MapRule::parse("IFA 38341003 | Hypertensive disorder |"),
+            MapRule::All(vec![MapCondition::Finding(38341003)])
+        );
+        assert!(matches!(
+            MapRule::parse("IFA 445518008 | Age | ~ 15 years"),
+            MapRule::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_map_per_group() {
+        let rows = [
+            // Group 1: age split with fallback
+            make_row(
+                1,
+                1,
+                "IFA 445518008 | Age at onset of clinical finding (observable entity) | < 15.0 years",
+                "P00.0",
+            ),
+            make_row(1, 2, "OTHERWISE TRUE", "O10.0"),
+            // Group 2: co-existing condition, otherwise no target
+            make_row(2, 2, "OTHERWISE TRUE", ""),
+            make_row(2, 1, "IFA 38341003 | Hypertensive disorder |", "I10"),
+        ];
+
+        let adult = MapContext::new().with_age(40.0);
+        let targets: Vec<_> = evaluate_map(&rows, &adult)
+            .iter()
+            .map(|row| row.target())
+            .collect();
+        assert_eq!(targets, vec![Some("O10.0"), None]);
+
+        let child = MapContext::new().with_age(3.0).with_finding(38341003);
+        let targets: Vec<_> = evaluate_map(&rows, &child)
+            .iter()
+            .map(|row| row.target())
+            .collect();
+        assert_eq!(targets, vec![Some("P00.0"), Some("I10")]);
+
+        // Unknown age never satisfies an age rule
+        let unknown = evaluate_map(&rows, &MapContext::new());
+        assert_eq!(unknown[0].target(), Some("O10.0"));
+    }
+}
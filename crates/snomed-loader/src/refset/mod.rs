@@ -0,0 +1,63 @@
+//! Reference set parsers and indexes.
+//!
+//! This module provides parsers for SNOMED CT reference sets that refine
+//! how core components are used:
+//!
+//! - **Language** - Preferred and acceptable descriptions per language/dialect
+//! - **Association** - Historical links from inactive components to their replacements
+//! - **Attribute value** - Coded values on components, e.g. inactivation reasons
+//! - **Extended map** - Rule-based maps to other code systems (SNOMED CT to ICD-10),
+//!   with a map rule evaluator
+//! - **Simple map** - One-to-one codes in other code systems (CTV3, ICD-O),
+//!   with reverse lookup
+//! - **OWL expression** - Stated definitions as OWL axioms, parsed into a
+//!   structured model
+//! - **Generic** - Any other reference set, typed from its file name and
+//!   described by the reference set descriptor
+//!
+//! # RF2 File Locations
+//!
+//! ```text
+//! Snapshot/
+//! ├── Terminology/
+//! │   └── sct2_sRefset_OWLExpressionSnapshot_*.txt
+//! └── Refset/
+//!     ├── Content/
+//!     │   ├── der2_cRefset_AssociationSnapshot_*.txt
+//!     │   ├── der2_cRefset_AttributeValueSnapshot_*.txt
+//!     │   └── der2_Refset_SimpleSnapshot_*.txt
+//!     ├── Language/
+//!     │   └── der2_cRefset_LanguageSnapshot-en_*.txt
+//!     ├── Map/
+//!     │   ├── der2_iisssccRefset_ExtendedMapSnapshot_*.txt
+//!     │   └── der2_sRefset_SimpleMapSnapshot_*.txt
+//!     └── Metadata/
+//!         └── der2_cciRefset_RefsetDescriptorSnapshot_*.txt
+//! ```
+
+mod association;
+mod attribute_value;
+mod extended_map;
+mod generic;
+mod language;
+mod map_rule;
+mod owl_axiom;
+mod owl_expression;
+mod simple_map;
+mod versions;
+
+pub use association::{
+    parse_association_file, Association, AssociationIndex, REPLACEMENT_ASSOCIATIONS,
+};
+pub use attribute_value::{parse_attribute_value_file, AttributeValue, AttributeValueIndex};
+pub use extended_map::{parse_extended_map_file, ExtendedMapIndex};
+pub(crate) use generic::MemberValues;
+pub use generic::{
+    parse_refset_file, parse_refset_reader, parse_refset_view_file, parse_refset_view_reader,
+    RefsetAttribute, RefsetIndex, RefsetParser, RefsetPattern, RefsetView,
+};
+pub use language::{parse_language_file, LanguageIndex};
+pub use map_rule::{evaluate_map, Comparison, MapCondition, MapContext, MapRule, Sex};
+pub use owl_axiom::{Axiom, AxiomAttribute, AxiomTarget, ClassExpression};
+pub use owl_expression::{parse_owl_expression_file, OwlAxiom, OwlAxiomIndex};
+pub use simple_map::{parse_simple_map_file, SimpleMapIndex};
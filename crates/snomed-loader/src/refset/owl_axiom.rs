@@ -0,0 +1,576 @@
+//! OWL axiom model and parser for the OWL expression reference set.
+//!
+//! Stated definitions are written in OWL functional syntax, e.g.
+//!
+//! ```text
+//! SubClassOf(:10002003 ObjectIntersectionOf(:116175006
+//!     ObjectSomeValuesFrom(:609096000 ObjectIntersectionOf(
+//!         ObjectSomeValuesFrom(:260686004 :129304002)
+//!         ObjectSomeValuesFrom(:405813007 :414003)))))
+//! EquivalentClasses(:322236009 ObjectIntersectionOf(:763158003 ...))
+//! SubObjectPropertyOf(ObjectPropertyChain(:246093002 :738774007) :246093002)
+//! TransitiveObjectProperty(:774081006)
+//! ```
+//!
+//! [`Axiom::parse`] turns one such string into an [`Axiom`]; the helpers on
+//! [`Axiom`] flatten a class axiom into parents and grouped attributes, the
+//! same shape as stated relationships.
+
+use snomed_types::{well_known, ConcreteValue, SctId};
+
+/// An OWL class expression.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClassExpression {
+    /// A named concept, e.g. `:73211009`.
+    Class(SctId),
+    /// `ObjectIntersectionOf(...)` - every operand holds.
+    ObjectIntersectionOf(Vec<ClassExpression>),
+    /// `ObjectSomeValuesFrom(:property filler)` - an attribute, or a role
+    /// group when `property` is [`well_known::ROLE_GROUP`].
+    ObjectSomeValuesFrom {
+        /// The attribute (object property) concept.
+        property: SctId,
+        /// The attribute value.
+        filler: Box<ClassExpression>,
+    },
+    /// `DataHasValue(:property "value"^^xsd:type)` - a concrete attribute.
+    DataHasValue {
+        /// The attribute (data property) concept.
+        property: SctId,
+        /// The concrete value.
+        value: ConcreteValue,
+    },
+}
+
+impl ClassExpression {
+    /// Returns the operands of an intersection, or the expression itself.
+    pub fn conjuncts(&self) -> &[ClassExpression] {
+        match self {
+            Self::ObjectIntersectionOf(operands) => operands,
+            other => std::slice::from_ref(other),
+        }
+    }
+}
+
+/// The value of an attribute in an axiom.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxiomTarget {
+    /// A named concept.
+    Concept(SctId),
+    /// A concrete value.
+    Value(ConcreteValue),
+    /// A nested expression (e.g. a refined body structure).
+    Expression(ClassExpression),
+}
+
+/// One attribute of a class axiom, flattened like a stated relationship.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxiomAttribute {
+    /// Role group number (0 = ungrouped, then 1, 2, ... in axiom order).
+    pub group: u16,
+    /// The attribute type.
+    pub type_id: SctId,
+    /// The attribute value.
+    pub target: AxiomTarget,
+}
+
+/// A parsed OWL axiom.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axiom {
+    /// `SubClassOf(sub sup)` - necessary conditions, or a general concept
+    /// inclusion (GCI) when `sub` is not a named concept.
+    SubClassOf {
+        /// The subclass expression.
+        sub: ClassExpression,
+        /// The superclass expression.
+        sup: ClassExpression,
+    },
+    /// `EquivalentClasses(...)` - necessary and sufficient conditions.
+    EquivalentClasses(Vec<ClassExpression>),
+    /// `SubObjectPropertyOf(:sub :sup)` - attribute hierarchy.
+    SubObjectPropertyOf {
+        /// The sub-property.
+        sub: SctId,
+        /// The super-property.
+        sup: SctId,
+    },
+    /// `SubObjectPropertyOf(ObjectPropertyChain(...) :sup)` - a property chain.
+    PropertyChain {
+        /// The chained properties, in order.
+        chain: Vec<SctId>,
+        /// The property the chain implies.
+        sup: SctId,
+    },
+    /// `TransitiveObjectProperty(:property)`.
+    TransitiveObjectProperty(SctId),
+    /// `ReflexiveObjectProperty(:property)`.
+    ReflexiveObjectProperty(SctId),
+    /// `SubDataPropertyOf(:sub :sup)` - concrete attribute hierarchy.
+    SubDataPropertyOf {
+        /// The sub-property.
+        sub: SctId,
+        /// The super-property.
+        sup: SctId,
+    },
+    /// An expression this parser does not understand (e.g. ontology
+    /// declarations); kept verbatim.
+    Unsupported(String),
+}
+
+impl Axiom {
+    /// Parses an axiom in OWL functional syntax.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use snomed_loader::refset::{Axiom, ClassExpression};
+    ///
+    /// let axiom = Axiom::parse("SubClassOf(:73211009 :362969004)");
+    /// assert_eq!(
+    ///     axiom,
+    ///     Axiom::SubClassOf {
+    ///         sub: ClassExpression::Class(73211009),
+    ///         sup: ClassExpression::Class(362969004),
+    ///     }
+    /// );
+    /// assert_eq!(axiom.parents(), vec![362969004]);
+    /// ```
+    pub fn parse(expression: &str) -> Self {
+        tokenize(expression)
+            .and_then(|tokens| {
+                let mut parser = Parser { tokens, pos: 0 };
+                let axiom = parser.axiom()?;
+                (parser.pos == parser.tokens.len()).then_some(axiom)
+            })
+            .unwrap_or_else(|| Self::Unsupported(expression.to_string()))
+    }
+
+    /// Returns the named concept a class axiom defines.
+    ///
+    /// For a GCI this is the named superclass.
+    pub fn concept_id(&self) -> Option<SctId> {
+        match self {
+            Self::SubClassOf {
+                sub: ClassExpression::Class(id),
+                ..
+            }
+            | Self::SubClassOf {
+                sup: ClassExpression::Class(id),
+                ..
+            } => Some(*id),
+            Self::EquivalentClasses(operands) => operands.iter().find_map(|c| match c {
+                ClassExpression::Class(id) => Some(*id),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns true for `EquivalentClasses` (a sufficiently defined concept).
+    pub fn is_sufficient(&self) -> bool {
+        matches!(self, Self::EquivalentClasses(_))
+    }
+
+    /// Returns true for a general concept inclusion (`SubClassOf` with an
+    /// anonymous subclass).
+    pub fn is_gci(&self) -> bool {
+        matches!(self, Self::SubClassOf { sub, .. } if !matches!(sub, ClassExpression::Class(_)))
+    }
+
+    /// Returns the expression defining the concept.
+    ///
+    /// This is the superclass of `SubClassOf`, the anonymous subclass of a
+    /// GCI, or the anonymous operand of `EquivalentClasses`.
+    pub fn definition(&self) -> Option<&ClassExpression> {
+        match self {
+            Self::SubClassOf {
+                sub: ClassExpression::Class(_),
+                sup,
+            } => Some(sup),
+            Self::SubClassOf { sub, .. } => Some(sub),
+            Self::EquivalentClasses(operands) => operands
+                .iter()
+                .find(|c| !matches!(c, ClassExpression::Class(_)))
+                .or_else(|| operands.get(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns the named concepts in the definition (the stated parents).
+    ///
+    /// For a GCI these are the named concepts of its condition.
+    pub fn parents(&self) -> Vec<SctId> {
+        self.definition()
+            .map(ClassExpression::conjuncts)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|c| match c {
+                ClassExpression::Class(id) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the attributes in the definition, with role groups numbered
+    /// from 1 in axiom order.
+    pub fn attributes(&self) -> Vec<AxiomAttribute> {
+        let mut attributes = Vec::new();
+        let mut group = 0;
+
+        for conjunct in self
+            .definition()
+            .map(ClassExpression::conjuncts)
+            .unwrap_or_default()
+        {
+            match conjunct {
+                ClassExpression::ObjectSomeValuesFrom { property, filler }
+                    if *property == well_known::ROLE_GROUP =>
+                {
+                    group += 1;
+                    attributes.extend(
+                        filler
+                            .conjuncts()
+                            .iter()
+                            .filter_map(|c| attribute(c, group)),
+                    );
+                }
+                other => attributes.extend(attribute(other, 0)),
+            }
+        }
+
+        attributes
+    }
+}
+
+/// Converts an attribute expression into an [`AxiomAttribute`].
+fn attribute(expression: &ClassExpression, group: u16) -> Option<AxiomAttribute> {
+    let (type_id, target) = match expression {
+        ClassExpression::ObjectSomeValuesFrom { property, filler } => {
+            let target = match filler.as_ref() {
+                ClassExpression::Class(id) => AxiomTarget::Concept(*id),
+                nested => AxiomTarget::Expression(nested.clone()),
+            };
+            (*property, target)
+        }
+        ClassExpression::DataHasValue { property, value } => {
+            (*property, AxiomTarget::Value(value.clone()))
+        }
+        _ => return None,
+    };
+
+    Some(AxiomAttribute {
+        group,
+        type_id,
+        target,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Open,
+    Close,
+    /// A keyword or prefixed name, e.g. `SubClassOf` or `:73211009`.
+    Word(&'a str),
+    /// A full IRI between angle brackets.
+    Iri(&'a str),
+    /// A quoted literal with its optional `^^datatype`.
+    Literal(String, Option<&'a str>),
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token<'_>>> {
+    let is_word_char = |c: char| !c.is_whitespace() && !matches!(c, '(' | ')' | '"' | '<');
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let consumed = match c {
+            '(' => {
+                tokens.push(Token::Open);
+                1
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                1
+            }
+            '<' => {
+                let end = rest.find('>')?;
+                tokens.push(Token::Iri(&rest[1..end]));
+                end + 1
+            }
+            '"' => {
+                let mut value = String::new();
+                let mut chars = rest.char_indices().skip(1);
+                let end = loop {
+                    match chars.next()? {
+                        (_, '\\') => value.push(chars.next()?.1),
+                        (i, '"') => break i + 1,
+                        (_, other) => value.push(other),
+                    }
+                };
+                let mut datatype = None;
+                let mut consumed = end;
+                if let Some(after) = rest[end..].strip_prefix("^^") {
+                    let len = after
+                        .find(|c: char| !is_word_char(c))
+                        .unwrap_or(after.len());
+                    datatype = Some(&after[..len]);
+                    consumed += 2 + len;
+                }
+                tokens.push(Token::Literal(value, datatype));
+                consumed
+            }
+            _ => {
+                let len = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+                tokens.push(Token::Word(&rest[..len]));
+                len
+            }
+        };
+        rest = rest[consumed..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token<'a>) -> Option<()> {
+        (self.next()? == expected).then_some(())
+    }
+
+    /// Parses `Keyword(` and returns the keyword.
+    fn open(&mut self) -> Option<&'a str> {
+        let Token::Word(keyword) = self.next()? else {
+            return None;
+        };
+        self.expect(Token::Open)?;
+        Some(keyword)
+    }
+
+    fn close(&mut self) -> Option<()> {
+        self.expect(Token::Close)
+    }
+
+    /// Parses `:123` or `<http://snomed.info/id/123>`.
+    fn entity(&mut self) -> Option<SctId> {
+        match self.next()? {
+            Token::Word(name) => name.strip_prefix(':')?.parse().ok(),
+            Token::Iri(iri) => iri.rsplit('/').next()?.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn axiom(&mut self) -> Option<Axiom> {
+        let axiom = match self.open()? {
+            "SubClassOf" => Axiom::SubClassOf {
+                sub: self.class_expression()?,
+                sup: self.class_expression()?,
+            },
+            "EquivalentClasses" => {
+                let mut operands = vec![self.class_expression()?];
+                while self.peek() != Some(&Token::Close) {
+                    operands.push(self.class_expression()?);
+                }
+                Axiom::EquivalentClasses(operands)
+            }
+            "SubObjectPropertyOf" => {
+                if matches!(self.peek(), Some(Token::Word("ObjectPropertyChain"))) {
+                    self.open()?;
+                    let mut chain = vec![self.entity()?];
+                    while self.peek() != Some(&Token::Close) {
+                        chain.push(self.entity()?);
+                    }
+                    self.close()?;
+                    Axiom::PropertyChain {
+                        chain,
+                        sup: self.entity()?,
+                    }
+                } else {
+                    Axiom::SubObjectPropertyOf {
+                        sub: self.entity()?,
+                        sup: self.entity()?,
+                    }
+                }
+            }
+            "TransitiveObjectProperty" => Axiom::TransitiveObjectProperty(self.entity()?),
+            "ReflexiveObjectProperty" => Axiom::ReflexiveObjectProperty(self.entity()?),
+            "SubDataPropertyOf" => Axiom::SubDataPropertyOf {
+                sub: self.entity()?,
+                sup: self.entity()?,
+            },
+            _ => return None,
+        };
+        self.close()?;
+        Some(axiom)
+    }
+
+    fn class_expression(&mut self) -> Option<ClassExpression> {
+        if !matches!(self.tokens.get(self.pos + 1), Some(Token::Open)) {
+            return self.entity().map(ClassExpression::Class);
+        }
+
+        let expression = match self.open()? {
+            "ObjectIntersectionOf" => {
+                let mut operands = vec![self.class_expression()?];
+                while self.peek() != Some(&Token::Close) {
+                    operands.push(self.class_expression()?);
+                }
+                ClassExpression::ObjectIntersectionOf(operands)
+            }
+            "ObjectSomeValuesFrom" => ClassExpression::ObjectSomeValuesFrom {
+                property: self.entity()?,
+                filler: Box::new(self.class_expression()?),
+            },
+            "DataHasValue" => ClassExpression::DataHasValue {
+                property: self.entity()?,
+                value: self.literal()?,
+            },
+            _ => return None,
+        };
+        self.close()?;
+        Some(expression)
+    }
+
+    /// Parses `"500"^^xsd:decimal`, `"2"^^xsd:integer` or `"text"`.
+    fn literal(&mut self) -> Option<ConcreteValue> {
+        let Token::Literal(value, datatype) = self.next()? else {
+            return None;
+        };
+        let datatype = datatype.map(|d| d.rsplit([':', '#']).next().unwrap_or(d));
+
+        match datatype {
+            Some("integer" | "int" | "long") => value.parse().ok().map(ConcreteValue::Integer),
+            Some("decimal" | "float" | "double") => value.parse().ok().map(ConcreteValue::Decimal),
+            _ => Some(ConcreteValue::String(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_class_axiom_with_role_groups() {
+        let axiom = Axiom::parse(
+            "SubClassOf(:10002003 ObjectIntersectionOf(:116175006 \
+             ObjectSomeValuesFrom(:609096000 ObjectIntersectionOf(\
+             ObjectSomeValuesFrom(:260686004 :129304002) \
+             ObjectSomeValuesFrom(:405813007 :414003))) \
+             ObjectSomeValuesFrom(:609096000 ObjectSomeValuesFrom(:260686004 :281615006))))",
+        );
+
+        assert_eq!(axiom.concept_id(), Some(10002003));
+        assert!(!axiom.is_sufficient());
+        assert!(!axiom.is_gci());
+        assert_eq!(axiom.parents(), vec![116175006]);
+
+        let attributes = axiom.attributes();
+        let groups: Vec<_> = attributes.iter().map(|a| (a.group, a.type_id)).collect();
+        assert_eq!(groups, vec![(1, 260686004), (1, 405813007), (2, 260686004)]);
+        assert_eq!(attributes[1].target, AxiomTarget::Concept(414003));
+    }
+
+    #[test]
+    fn test_parse_equivalent_classes_with_concrete_values() {
+        let axiom = Axiom::parse(
+            "EquivalentClasses(:322236009 ObjectIntersectionOf(:763158003 \
+             ObjectSomeValuesFrom(:411116001 :421026006) \
+             DataHasValue(:1142139005 \"1\"^^xsd:integer) \
+             ObjectSomeValuesFrom(:609096000 ObjectIntersectionOf(\
+             ObjectSomeValuesFrom(:762949000 :387517004) \
+             DataHasValue(:1142135004 \"500\"^^xsd:decimal)))))",
+        );
+
+        assert_eq!(axiom.concept_id(), Some(322236009));
+        assert!(axiom.is_sufficient());
+        assert_eq!(axiom.parents(), vec![763158003]);
+
+        let attributes = axiom.attributes();
+        assert_eq!(attributes.len(), 4);
+        assert_eq!(attributes[1].group, 0);
+        assert_eq!(
+            attributes[1].target,
+            AxiomTarget::Value(ConcreteValue::Integer(1))
+        );
+        assert_eq!(attributes[3].group, 1);
+        assert_eq!(
+            attributes[3].target,
+            AxiomTarget::Value(ConcreteValue::Decimal(500.0))
+        );
+    }
+
+    #[test]
+    fn test_parse_property_axioms() {
+        assert_eq!(
+            Axiom::parse("SubObjectPropertyOf(:363701004 :762705008)"),
+            Axiom::SubObjectPropertyOf {
+                sub: 363701004,
+                sup: 762705008
+            }
+        );
+        assert_eq!(
+            Axiom::parse(
+                "SubObjectPropertyOf(ObjectPropertyChain(:246093002 :738774007) :246093002)"
+            ),
+            Axiom::PropertyChain {
+                chain: vec![246093002, 738774007],
+                sup: 246093002
+            }
+        );
+        assert_eq!(
+            Axiom::parse("TransitiveObjectProperty(<http://snomed.info/id/774081006>)"),
+            Axiom::TransitiveObjectProperty(774081006)
+        );
+        assert_eq!(
+            Axiom::parse("ReflexiveObjectProperty(:733930001)"),
+            Axiom::ReflexiveObjectProperty(733930001)
+        );
+        assert_eq!(
+            Axiom::parse("SubDataPropertyOf(:1142135004 :762706009)"),
+            Axiom::SubDataPropertyOf {
+                sub: 1142135004,
+                sup: 762706009
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_gci_and_unsupported() {
+        let gci = Axiom::parse(
+            "SubClassOf(ObjectIntersectionOf(:73211009 \
+             ObjectSomeValuesFrom(:363698007 :113331007)) :362969004)",
+        );
+        assert!(gci.is_gci());
+        assert_eq!(gci.concept_id(), Some(362969004));
+        assert_eq!(gci.parents(), vec![73211009]);
+
+        let prefix = "Prefix(:=<http://snomed.info/id/>)";
+        assert_eq!(Axiom::parse(prefix), Axiom::Unsupported(prefix.to_string()));
+        assert!(matches!(
+            Axiom::parse("SubClassOf(:73211009 :362969004"),
+            Axiom::Unsupported(_)
+        ));
+        assert!(matches!(
+            Axiom::parse("SubClassOf(:73211009 :362969004) trailing"),
+            Axiom::Unsupported(_)
+        ));
+    }
+}
@@ -0,0 +1,263 @@
+//! OWL expression reference set parser and index.
+//!
+//! Parses files matching pattern: `sct2_sRefset_OWLExpressionSnapshot_*.txt`
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use snomed_types::{well_known, OwlExpressionRefsetMember, RefsetFieldType, RefsetMember, SctId};
+
+use super::generic::{parse_refset_view_file, MemberValues, RefsetParser, RefsetView};
+use super::owl_axiom::Axiom;
+use super::versions::{MemberRow, MemberVersions};
+use crate::source::Rf2Reader;
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+impl RefsetView for OwlExpressionRefsetMember {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] =
+        &[("owlExpression", RefsetFieldType::String)];
+
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        let mut values = MemberValues::new::<Self>(member.values);
+        Ok(OwlExpressionRefsetMember {
+            id: member.id,
+            effective_time: member.effective_time,
+            active: member.active,
+            module_id: member.module_id,
+            refset_id: member.refset_id,
+            referenced_component_id: member.referenced_component_id,
+            owl_expression: values.string()?,
+        })
+    }
+}
+
+/// Parses an OWL expression reference set from a file.
+///
+/// # Arguments
+/// * `path` - Path to the OWL expression reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `OwlExpressionRefsetMember` records.
+pub fn parse_owl_expression_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<Rf2Reader, OwlExpressionRefsetMember>> {
+    parse_refset_view_file(path, config)
+}
+
+/// An active OWL axiom about a concept.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwlAxiom {
+    /// Row id of the reference set member.
+    pub id: String,
+    /// Effective time of the member row.
+    pub effective_time: u32,
+    /// The parsed axiom.
+    pub axiom: Axiom,
+}
+
+impl MemberRow for OwlAxiom {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Index of active OWL axioms per referenced concept.
+///
+/// Only members of the OWL axiom reference set are indexed; ontology
+/// declarations are skipped.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::OwlAxiomIndex;
+///
+/// let index = OwlAxiomIndex::load("sct2_sRefset_OWLExpressionSnapshot_INT_20250101.txt")?;
+/// for owl in index.get(73211009) {
+///     println!("parents {:?}, sufficient: {}", owl.axiom.parents(), owl.axiom.is_sufficient());
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwlAxiomIndex {
+    by_concept: HashMap<SctId, Vec<OwlAxiom>>,
+    versions: MemberVersions,
+}
+
+impl OwlAxiomIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of an OWL expression reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_owl_expression_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Members are keyed by their row id. Active members are parsed and
+    /// upserted and inactive ones retire their row; versions older than the
+    /// stored or retired version of the same member are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = OwlExpressionRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            if member.refset_id != well_known::OWL_AXIOM_REFSET {
+                continue;
+            }
+
+            let concept_id = member.referenced_component_id;
+            let axioms = self.by_concept.entry(concept_id).or_default();
+            let axiom = member.active.then(|| OwlAxiom {
+                id: member.id.clone(),
+                effective_time: member.effective_time,
+                axiom: Axiom::parse(&member.owl_expression),
+            });
+            self.versions
+                .apply(axioms, &member.id, member.effective_time, axiom, &mut stats);
+
+            if axioms.is_empty() {
+                self.by_concept.remove(&concept_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets the active axioms about a concept (class or attribute).
+    pub fn get(&self, concept_id: SctId) -> &[OwlAxiom] {
+        self.by_concept
+            .get(&concept_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the concepts that have axioms.
+    pub fn concept_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        self.by_concept.keys().copied()
+    }
+
+    /// Returns the number of active axioms.
+    pub fn len(&self) -> usize {
+        self.by_concept.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_concept.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    ///
+    /// Axiom trees are approximated by the size of the top-level node.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let axiom_size: usize = self
+            .by_concept
+            .values()
+            .flatten()
+            .map(|a| size_of::<OwlAxiom>() + a.id.len())
+            .sum();
+
+        self.by_concept.len() * size_of::<SctId>()
+            + axiom_size
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refset::{parse_refset_view_reader, RefsetPattern};
+
+    fn make_member(id: &str, concept_id: SctId, expression: &str) -> OwlExpressionRefsetMember {
+        OwlExpressionRefsetMember {
+            id: id.to_string(),
+            effective_time: 20200131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id: well_known::OWL_AXIOM_REFSET,
+            referenced_component_id: concept_id,
+            owl_expression: expression.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_owl_expression_member() {
+        let contents = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\towlExpression
+80000000-0000-0000-0000-000000000000\t20200131\t1\t900000000000207008\t733073007\t73211009\tSubClassOf(:73211009 :362969004)
+";
+        let pattern =
+            RefsetPattern::from_file_name("sct2_sRefset_OWLExpressionSnapshot_INT_20250101.txt")
+                .unwrap();
+        let members: Vec<OwlExpressionRefsetMember> =
+            parse_refset_view_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect::<Rf2Result<_>>()
+                .unwrap();
+
+        let member = &members[0];
+        assert_eq!(member.refset_id, well_known::OWL_AXIOM_REFSET);
+        assert_eq!(member.referenced_component_id, 73211009);
+        assert_eq!(member.owl_expression, "SubClassOf(:73211009 :362969004)");
+    }
+
+    #[test]
+    fn test_apply_members() {
+        let mut index = OwlAxiomIndex::new();
+        let stats = index.apply_members([
+            make_member("a", 73211009, "SubClassOf(:73211009 :362969004)"),
+            make_member("b", 774081006, "TransitiveObjectProperty(:774081006)"),
+            OwlExpressionRefsetMember {
+                refset_id: well_known::OWL_ONTOLOGY_REFSET,
+                ..make_member("c", 734147008, "Prefix(:=<http://snomed.info/id/>)")
+            },
+        ]);
+
+        // Ontology declarations are not indexed
+        assert_eq!(stats.upserted, 2);
+        assert_eq!(index.get(73211009)[0].axiom.parents(), vec![362969004]);
+        assert!(index.get(734147008).is_empty());
+
+        // A later version replaces the axiom
+        index.apply_members([OwlExpressionRefsetMember {
+            effective_time: 20250201,
+            ..make_member("a", 73211009, "SubClassOf(:73211009 :126877002)")
+        }]);
+        assert_eq!(index.get(73211009)[0].axiom.parents(), vec![126877002]);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let retired = OwlExpressionRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member("a", 73211009, "SubClassOf(:73211009 :362969004)")
+        };
+        let original = make_member("a", 73211009, "SubClassOf(:73211009 :362969004)");
+
+        let mut index = OwlAxiomIndex::new();
+        let stats = index.apply_members([retired, original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(index.get(73211009).is_empty());
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,340 @@
+//! Simple map reference set parser and index.
+//!
+//! Parses files matching pattern: `der2_sRefset_SimpleMapSnapshot_*.txt`
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use snomed_types::{RefsetFieldType, RefsetMember, SctId, SimpleMapRefsetMember};
+
+use super::generic::{parse_refset_view_file, MemberValues, RefsetParser, RefsetView};
+use super::versions::{Applied, MemberRow, MemberVersions};
+use crate::source::Rf2Reader;
+use crate::types::{DeltaStats, Rf2Config, Rf2Result};
+
+impl RefsetView for SimpleMapRefsetMember {
+    const FIELDS: &'static [(&'static str, RefsetFieldType)] =
+        &[("mapTarget", RefsetFieldType::String)];
+
+    fn from_member(member: RefsetMember) -> Rf2Result<Self> {
+        let mut values = MemberValues::new::<Self>(member.values);
+        Ok(SimpleMapRefsetMember {
+            id: member.id,
+            effective_time: member.effective_time,
+            active: member.active,
+            module_id: member.module_id,
+            refset_id: member.refset_id,
+            referenced_component_id: member.referenced_component_id,
+            map_target: values.string()?,
+        })
+    }
+}
+
+impl MemberRow for SimpleMapRefsetMember {
+    fn member_id(&self) -> &str {
+        &self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Parses a simple map reference set from a file.
+///
+/// Files with extra columns after `mapTarget` (e.g. `mapAdvice` in
+/// `der2_ssRefset_SimpleMap*`) are read too.
+///
+/// # Arguments
+/// * `path` - Path to the simple map reference set file
+/// * `config` - Parser configuration
+///
+/// # Returns
+/// Iterator over parsed `SimpleMapRefsetMember` records.
+pub fn parse_simple_map_file<P: AsRef<Path>>(
+    path: P,
+    config: Rf2Config,
+) -> Rf2Result<RefsetParser<Rf2Reader, SimpleMapRefsetMember>> {
+    parse_refset_view_file(path, config)
+}
+
+/// Index of active simple map rows, searchable in both directions.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::refset::SimpleMapIndex;
+/// use snomed_types::well_known;
+///
+/// let index = SimpleMapIndex::load("der2_sRefset_SimpleMapSnapshot_INT_20250101.txt")?;
+///
+/// // Concept -> CTV3 codes
+/// let codes: Vec<_> = index.targets(well_known::CTV3_SIMPLE_MAP_REFSET, 22298006).collect();
+///
+/// // CTV3 code -> concepts
+/// let concepts = index.concepts_for(Some(well_known::CTV3_SIMPLE_MAP_REFSET), "X200E");
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleMapIndex {
+    /// Rows per mapped component.
+    by_component: HashMap<SctId, Vec<SimpleMapRefsetMember>>,
+    /// Reverse index: target code -> (refset, component) pairs.
+    by_target: HashMap<String, Vec<(SctId, SctId)>>,
+    versions: MemberVersions,
+}
+
+impl SimpleMapIndex {
+    /// Creates a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the active members of a simple map reference set file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Rf2Result<Self> {
+        let mut index = Self::new();
+        index.apply_members(parse_simple_map_file(path, Rf2Config::default())?.flatten());
+        Ok(index)
+    }
+
+    /// Applies members in file order.
+    ///
+    /// Members are keyed by their row id. Active members are upserted and
+    /// inactive ones retire their row; versions older than the stored or
+    /// retired version of the same member are skipped.
+    pub fn apply_members(
+        &mut self,
+        members: impl IntoIterator<Item = SimpleMapRefsetMember>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for member in members {
+            let component_id = member.referenced_component_id;
+            let id = member.id.clone();
+            let effective_time = member.effective_time;
+            let target = member
+                .active
+                .then(|| (member.map_target.clone(), member.refset_id));
+            let rows = self.by_component.entry(component_id).or_default();
+            let row = member.active.then_some(member);
+            let applied = self
+                .versions
+                .apply(rows, &id, effective_time, row, &mut stats);
+            let (target, previous) = match applied {
+                Applied::Stale => (None, None),
+                Applied::Current(previous) => (target, previous),
+            };
+
+            if let Some((map_target, refset_id)) = target {
+                let pairs = self.by_target.entry(map_target).or_default();
+                if !pairs.contains(&(refset_id, component_id)) {
+                    pairs.push((refset_id, component_id));
+                }
+            }
+
+            // Drop the reverse entry once no row maps the component to the old code
+            if let Some(previous) = previous {
+                let still_mapped = rows.iter().any(|r| {
+                    r.refset_id == previous.refset_id && r.map_target == previous.map_target
+                });
+                if !still_mapped {
+                    if let Some(pairs) = self.by_target.get_mut(&previous.map_target) {
+                        pairs.retain(|pair| *pair != (previous.refset_id, component_id));
+                        if pairs.is_empty() {
+                            self.by_target.remove(&previous.map_target);
+                        }
+                    }
+                }
+            }
+
+            if rows.is_empty() {
+                self.by_component.remove(&component_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Gets all active map rows for a component, across map reference sets.
+    pub fn get(&self, component_id: SctId) -> &[SimpleMapRefsetMember] {
+        self.by_component
+            .get(&component_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets the target codes of a component in one map reference set.
+    pub fn targets(&self, refset_id: SctId, component_id: SctId) -> impl Iterator<Item = &str> {
+        self.get(component_id)
+            .iter()
+            .filter(move |row| row.refset_id == refset_id)
+            .map(|row| row.map_target.as_str())
+    }
+
+    /// Gets the components mapped to a target code, sorted by id.
+    ///
+    /// A `refset_id` of `None` searches every simple map reference set.
+    pub fn concepts_for(&self, refset_id: Option<SctId>, map_target: &str) -> Vec<SctId> {
+        let mut component_ids: Vec<SctId> = self
+            .by_target
+            .get(map_target)
+            .into_iter()
+            .flatten()
+            .filter(|(refset, _)| refset_id.is_none() || refset_id == Some(*refset))
+            .map(|(_, component_id)| *component_id)
+            .collect();
+        component_ids.sort_unstable();
+        component_ids.dedup();
+        component_ids
+    }
+
+    /// Returns the number of active map rows.
+    pub fn len(&self) -> usize {
+        self.by_component.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_component.is_empty()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let row_size: usize = self
+            .by_component
+            .values()
+            .flatten()
+            .map(|row| size_of::<SimpleMapRefsetMember>() + row.id.len() + row.map_target.len())
+            .sum();
+        let target_size: usize = self
+            .by_target
+            .iter()
+            .map(|(code, pairs)| code.len() + pairs.len() * size_of::<(SctId, SctId)>())
+            .sum();
+
+        self.by_component.len() * size_of::<SctId>()
+            + row_size
+            + target_size
+            + self.versions.estimated_memory_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refset::{parse_refset_view_reader, RefsetPattern};
+    use snomed_types::well_known;
+
+    fn make_member(id: &str, component_id: SctId, map_target: &str) -> SimpleMapRefsetMember {
+        SimpleMapRefsetMember {
+            id: id.to_string(),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id: well_known::CTV3_SIMPLE_MAP_REFSET,
+            referenced_component_id: component_id,
+            map_target: map_target.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_simple_map_member() {
+        let contents = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tmapTarget
+80000000-0000-0000-0000-000000000000\t20020131\t1\t900000000000207008\t900000000000497000\t22298006\tX200E
+";
+        let pattern =
+            RefsetPattern::from_file_name("der2_sRefset_SimpleMapSnapshot_INT_20250101.txt")
+                .unwrap();
+        let members: Vec<SimpleMapRefsetMember> =
+            parse_refset_view_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect::<Rf2Result<_>>()
+                .unwrap();
+
+        let member = &members[0];
+        assert_eq!(member.refset_id, well_known::CTV3_SIMPLE_MAP_REFSET);
+        assert_eq!(member.referenced_component_id, 22298006);
+        assert_eq!(member.map_target, "X200E");
+    }
+
+    #[test]
+    fn test_parse_simple_map_with_advice() {
+        let contents = "\
+id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tmapTarget\tmapAdvice
+m1\t20020131\t1\t900000000000207008\t447562003\t73211009\tE14\tALWAYS E14
+";
+        let pattern =
+            RefsetPattern::from_file_name("der2_ssRefset_SimpleMapSnapshot_INT_20250101.txt")
+                .unwrap();
+        let members: Vec<SimpleMapRefsetMember> =
+            parse_refset_view_reader(contents.as_bytes(), pattern, Rf2Config::default())
+                .unwrap()
+                .collect::<Rf2Result<_>>()
+                .unwrap();
+        assert_eq!(members[0].map_target, "E14");
+    }
+
+    #[test]
+    fn test_forward_and_reverse_lookup() {
+        let mut index = SimpleMapIndex::new();
+        index.apply_members([
+            make_member("a", 100, "X200E"),
+            make_member("b", 200, "X200E"),
+            make_member("c", 100, "G30.."),
+        ]);
+
+        let mut targets: Vec<_> = index
+            .targets(well_known::CTV3_SIMPLE_MAP_REFSET, 100)
+            .collect();
+        targets.sort_unstable();
+        assert_eq!(targets, vec!["G30..", "X200E"]);
+        assert_eq!(
+            index.concepts_for(Some(well_known::CTV3_SIMPLE_MAP_REFSET), "X200E"),
+            vec![100, 200]
+        );
+        assert!(index
+            .concepts_for(Some(well_known::ICDO_SIMPLE_MAP_REFSET), "X200E")
+            .is_empty());
+
+        // Retargeting a row and inactivating another update the reverse index
+        let stats = index.apply_members([
+            SimpleMapRefsetMember {
+                effective_time: 20250201,
+                ..make_member("a", 100, "XE0Uc")
+            },
+            SimpleMapRefsetMember {
+                effective_time: 20250201,
+                active: false,
+                ..make_member("b", 200, "X200E")
+            },
+        ]);
+
+        assert_eq!(stats.upserted, 1);
+        assert_eq!(stats.removed, 1);
+        assert!(index.concepts_for(None, "X200E").is_empty());
+        assert_eq!(index.concepts_for(None, "XE0Uc"), vec![100]);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_inactivation_outlives_older_active_row() {
+        // Full files are not ordered by effective time
+        let retired = SimpleMapRefsetMember {
+            effective_time: 20250201,
+            active: false,
+            ..make_member("a", 100, "X200E")
+        };
+        let original = make_member("a", 100, "X200E");
+
+        let mut index = SimpleMapIndex::new();
+        let stats = index.apply_members([retired, original]);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(index.get(100).is_empty());
+        assert!(index.concepts_for(None, "X200E").is_empty());
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Member version tracking shared by the reference set indexes.
+//!
+//! RF2 identifies a reference set member by its UUID; every row of a member
+//! is one version of it. Full files and multi-edition loads are not in
+//! effective-time order, so an inactive row can be applied before an older
+//! active version of the same member. [`MemberVersions`] remembers the
+//! effective time of retired members so that older row can be skipped.
+
+use std::collections::HashMap;
+
+use crate::types::DeltaStats;
+
+/// A row stored in a reference set index, identified by its member id.
+pub(crate) trait MemberRow {
+    /// The reference set member UUID.
+    fn member_id(&self) -> &str;
+
+    /// Effective time of the stored version.
+    fn effective_time(&self) -> u32;
+}
+
+/// Outcome of applying one version of a member.
+#[derive(Debug)]
+pub(crate) enum Applied<T> {
+    /// The index already holds a newer version; nothing changed.
+    Stale,
+    /// The version was applied, replacing or removing the member's previous
+    /// active row, if it had one.
+    Current(Option<T>),
+}
+
+/// Effective times of retired reference set members, by member id.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct MemberVersions {
+    retired: HashMap<String, u32>,
+}
+
+impl MemberVersions {
+    /// Applies one version of a member to the rows of its referenced component.
+    ///
+    /// `row` is the member's new row, or `None` when the version is inactive
+    /// or filtered out. The version is skipped if the stored row or the
+    /// member's retirement is newer; otherwise it replaces the stored row in
+    /// place, or retires it. `stats` counts the upsert, removal or skip.
+    pub(crate) fn apply<T: MemberRow>(
+        &mut self,
+        rows: &mut Vec<T>,
+        member_id: &str,
+        effective_time: u32,
+        row: Option<T>,
+        stats: &mut DeltaStats,
+    ) -> Applied<T> {
+        let position = rows.iter().position(|r| r.member_id() == member_id);
+        let latest = match position {
+            Some(i) => Some(rows[i].effective_time()),
+            None => self.retired.get(member_id).copied(),
+        };
+        if latest.is_some_and(|time| time > effective_time) {
+            stats.skipped += 1;
+            return Applied::Stale;
+        }
+
+        let upserted = row.is_some();
+        let previous = match (position, row) {
+            (Some(i), Some(row)) => Some(std::mem::replace(&mut rows[i], row)),
+            (None, Some(row)) => {
+                self.retired.remove(member_id);
+                rows.push(row);
+                None
+            }
+            (position, None) => {
+                self.retired.insert(member_id.to_string(), effective_time);
+                position.map(|i| rows.remove(i))
+            }
+        };
+
+        if upserted {
+            stats.upserted += 1;
+        } else if previous.is_some() {
+            stats.removed += 1;
+        }
+        Applied::Current(previous)
+    }
+
+    /// Estimates memory usage in bytes.
+    pub(crate) fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        self.retired
+            .keys()
+            .map(|id| size_of::<String>() + size_of::<u32>() + id.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Row {
+        id: &'static str,
+        effective_time: u32,
+    }
+
+    impl MemberRow for Row {
+        fn member_id(&self) -> &str {
+            self.id
+        }
+
+        fn effective_time(&self) -> u32 {
+            self.effective_time
+        }
+    }
+
+    fn row(id: &'static str, effective_time: u32) -> Row {
+        Row { id, effective_time }
+    }
+
+    #[test]
+    fn test_newer_version_replaces_row() {
+        let mut versions = MemberVersions::default();
+        let mut rows = Vec::new();
+        let mut stats = DeltaStats::default();
+
+        versions.apply(
+            &mut rows,
+            "a",
+            20020131,
+            Some(row("a", 20020131)),
+            &mut stats,
+        );
+        let applied = versions.apply(
+            &mut rows,
+            "a",
+            20250201,
+            Some(row("a", 20250201)),
+            &mut stats,
+        );
+
+        assert!(matches!(applied, Applied::Current(Some(_))));
+        assert_eq!(rows, vec![row("a", 20250201)]);
+        assert_eq!(stats.upserted, 2);
+    }
+
+    #[test]
+    fn test_retirement_outlives_older_active_version() {
+        let mut versions = MemberVersions::default();
+        let mut rows = Vec::new();
+        let mut stats = DeltaStats::default();
+
+        versions.apply(&mut rows, "a", 20250201, None, &mut stats);
+        let applied = versions.apply(
+            &mut rows,
+            "a",
+            20020131,
+            Some(row("a", 20020131)),
+            &mut stats,
+        );
+
+        assert!(matches!(applied, Applied::Stale));
+        assert!(rows.is_empty());
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(versions.retired.get("a"), Some(&20250201));
+
+        // A newer active version reinstates the member
+        versions.apply(
+            &mut rows,
+            "a",
+            20260201,
+            Some(row("a", 20260201)),
+            &mut stats,
+        );
+        assert_eq!(rows, vec![row("a", 20260201)]);
+        assert!(versions.retired.is_empty());
+    }
+
+    #[test]
+    fn test_retirement_removes_row() {
+        let mut versions = MemberVersions::default();
+        let mut rows = vec![row("a", 20020131), row("b", 20020131)];
+        let mut stats = DeltaStats::default();
+
+        let applied = versions.apply(&mut rows, "a", 20250201, None, &mut stats);
+
+        assert!(matches!(applied, Applied::Current(Some(_))));
+        assert_eq!(rows, vec![row("b", 20020131)]);
+        assert_eq!(stats.removed, 1);
+    }
+}
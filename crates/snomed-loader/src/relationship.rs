@@ -1,11 +1,11 @@
 //! SNOMED CT Relationship file parser.
 //!
-//! Parses sct2_Relationship_*.txt RF2 files.
+//! Parses sct2_Relationship_*.txt and sct2_RelationshipConcreteValues_*.txt RF2 files.
 
 use csv::StringRecord;
-use snomed_types::Rf2Relationship;
+use snomed_types::{ConcreteValue, Rf2ConcreteRelationship, Rf2Relationship, SctId};
 
-use crate::parser::{parse, Rf2Record};
+use crate::parser::{field, parse, Rf2Component, Rf2Record};
 use crate::types::{RelationshipConfig, Rf2Config, Rf2Result};
 
 /// Expected columns in a relationship file.
@@ -27,16 +27,16 @@ impl Rf2Record for Rf2Relationship {
 
     fn from_record(record: &StringRecord) -> Rf2Result<Self> {
         Ok(Rf2Relationship {
-            id: parse::sctid(record.get(0).unwrap_or(""))?,
-            effective_time: parse::effective_time(record.get(1).unwrap_or(""))?,
-            active: parse::boolean(record.get(2).unwrap_or(""))?,
-            module_id: parse::sctid(record.get(3).unwrap_or(""))?,
-            source_id: parse::sctid(record.get(4).unwrap_or(""))?,
-            destination_id: parse::sctid(record.get(5).unwrap_or(""))?,
-            relationship_group: parse::integer(record.get(6).unwrap_or(""))?,
-            type_id: parse::sctid(record.get(7).unwrap_or(""))?,
-            characteristic_type_id: parse::sctid(record.get(8).unwrap_or(""))?,
-            modifier_id: parse::sctid(record.get(9).unwrap_or(""))?,
+            id: field(record, 0, "id", parse::sctid)?,
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            source_id: field(record, 4, "sourceId", parse::sctid)?,
+            destination_id: field(record, 5, "destinationId", parse::sctid)?,
+            relationship_group: field(record, 6, "relationshipGroup", parse::integer)?,
+            type_id: field(record, 7, "typeId", parse::sctid)?,
+            characteristic_type_id: field(record, 8, "characteristicTypeId", parse::sctid)?,
+            modifier_id: field(record, 9, "modifierId", parse::sctid)?,
         })
     }
 
@@ -48,6 +48,119 @@ impl Rf2Record for Rf2Relationship {
     }
 }
 
+impl Rf2Component for Rf2Relationship {
+    fn component_id(&self) -> SctId {
+        self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// Expected columns in a concrete value relationship file.
+const CONCRETE_RELATIONSHIP_COLUMNS: &[&str] = &[
+    "id",
+    "effectiveTime",
+    "active",
+    "moduleId",
+    "sourceId",
+    "value",
+    "relationshipGroup",
+    "typeId",
+    "characteristicTypeId",
+    "modifierId",
+];
+
+impl Rf2Record for Rf2ConcreteRelationship {
+    const EXPECTED_COLUMNS: &'static [&'static str] = CONCRETE_RELATIONSHIP_COLUMNS;
+
+    fn from_record(record: &StringRecord) -> Rf2Result<Self> {
+        Ok(Rf2ConcreteRelationship {
+            id: field(record, 0, "id", parse::sctid)?,
+            effective_time: field(record, 1, "effectiveTime", parse::effective_time)?,
+            active: field(record, 2, "active", parse::boolean)?,
+            module_id: field(record, 3, "moduleId", parse::sctid)?,
+            source_id: field(record, 4, "sourceId", parse::sctid)?,
+            value: field(record, 5, "value", parse::concrete_value)?,
+            relationship_group: field(record, 6, "relationshipGroup", parse::integer)?,
+            type_id: field(record, 7, "typeId", parse::sctid)?,
+            characteristic_type_id: field(record, 8, "characteristicTypeId", parse::sctid)?,
+            modifier_id: field(record, 9, "modifierId", parse::sctid)?,
+        })
+    }
+
+    fn passes_filter(&self, config: &Rf2Config) -> bool {
+        if config.active_only && !self.active {
+            return false;
+        }
+        true
+    }
+}
+
+impl Rf2Component for Rf2ConcreteRelationship {
+    fn component_id(&self) -> SctId {
+        self.id
+    }
+
+    fn effective_time(&self) -> u32 {
+        self.effective_time
+    }
+}
+
+/// An outgoing relationship of a concept, to either a concept or a concrete value.
+///
+/// Returned by [`SnomedStore::get_outgoing`](crate::SnomedStore::get_outgoing).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutgoingRelationship<'a> {
+    /// A relationship whose target is a concept.
+    Concept(&'a Rf2Relationship),
+    /// A relationship whose target is a concrete value.
+    Concrete(&'a Rf2ConcreteRelationship),
+}
+
+impl<'a> OutgoingRelationship<'a> {
+    /// Returns the relationship id.
+    pub fn id(&self) -> SctId {
+        match self {
+            Self::Concept(r) => r.id,
+            Self::Concrete(r) => r.id,
+        }
+    }
+
+    /// Returns the relationship type.
+    pub fn type_id(&self) -> SctId {
+        match self {
+            Self::Concept(r) => r.type_id,
+            Self::Concrete(r) => r.type_id,
+        }
+    }
+
+    /// Returns the role group number (0 = ungrouped).
+    pub fn relationship_group(&self) -> u16 {
+        match self {
+            Self::Concept(r) => r.relationship_group,
+            Self::Concrete(r) => r.relationship_group,
+        }
+    }
+
+    /// Returns the destination concept, if the target is a concept.
+    pub fn destination_id(&self) -> Option<SctId> {
+        match self {
+            Self::Concept(r) => Some(r.destination_id),
+            Self::Concrete(_) => None,
+        }
+    }
+
+    /// Returns the concrete value, if the target is a value.
+    pub fn value(&self) -> Option<&'a ConcreteValue> {
+        match self {
+            Self::Concept(_) => None,
+            Self::Concrete(r) => Some(&r.value),
+        }
+    }
+}
+
 /// Extended filter for relationships with type and characteristic filtering.
 pub trait RelationshipFilter {
     /// Returns true if the relationship passes the extended filter.
@@ -77,6 +190,28 @@ impl RelationshipFilter for Rf2Relationship {
     }
 }
 
+impl RelationshipFilter for Rf2ConcreteRelationship {
+    fn passes_relationship_filter(&self, config: &RelationshipConfig) -> bool {
+        if !self.passes_filter(&config.base) {
+            return false;
+        }
+
+        if !config.type_ids.is_empty() && !config.type_ids.contains(&self.type_id) {
+            return false;
+        }
+
+        if !config.characteristic_type_ids.is_empty()
+            && !config
+                .characteristic_type_ids
+                .contains(&self.characteristic_type_id)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +300,39 @@ mod tests {
         assert!(is_a.passes_relationship_filter(&is_a_only));
         assert!(!finding_site.passes_relationship_filter(&is_a_only));
     }
+
+    #[test]
+    fn test_parse_concrete_relationship_record() {
+        let mut fields = [
+            "3552454029",
+            "20210731",
+            "1",
+            "900000000000207008",
+            "322236009",
+            "#500",
+            "1",
+            "1142135004",
+            "900000000000011006",
+            "900000000000451002",
+        ];
+
+        let rel = Rf2ConcreteRelationship::from_record(&make_record(&fields)).unwrap();
+        assert_eq!(rel.source_id, 322236009);
+        assert_eq!(rel.value, ConcreteValue::Integer(500));
+        assert_eq!(rel.relationship_group, 1);
+        assert!(rel.passes_relationship_filter(&RelationshipConfig::inferred_only()));
+
+        // Numbers must carry the RF2 `#` prefix
+        fields[5] = "500";
+        match Rf2ConcreteRelationship::from_record(&make_record(&fields)) {
+            Err(crate::Rf2Error::Field { column, error }) => {
+                assert_eq!(column, "value");
+                assert!(matches!(
+                    *error,
+                    crate::Rf2Error::InvalidConcreteValue { .. }
+                ));
+            }
+            other => panic!("expected a field error, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,328 @@
+//! Binary store snapshots.
+//!
+//! A snapshot is a serialized image of a loaded [`SnomedStore`]: concepts,
+//! descriptions, both relationship indexes, MRCM, reference set indexes
+//! and edition provenance. Loading one skips RF2 parsing and indexing
+//! entirely.
+//!
+//! File layout (integers little-endian):
+//!
+//! | Offset | Size | Field                                           |
+//! |--------|------|-------------------------------------------------|
+//! | 0      | 8    | Magic `SNOMEDSS`                                |
+//! | 8      | 4    | Format version                                  |
+//! | 12     | 8    | Payload length in bytes                         |
+//! | 20     | 4    | CRC-32 of the payload                           |
+//! | 24     | 4    | Payload: source length `n`                      |
+//! | 28     | n    | Payload: the [`SnapshotSource`], bincode-encoded |
+//! | 28 + n | …    | Payload: the store, bincode-encoded             |
+//!
+//! Snapshots are only read back by a build with the same format version,
+//! and only for the release and load options they were built from; they
+//! are a cache of an RF2 release, not an interchange format.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::store::SnomedStore;
+use crate::types::{Rf2Error, Rf2Result, SnapshotSource};
+
+/// Marks the start of a snapshot file.
+const MAGIC: &[u8; 8] = b"SNOMEDSS";
+
+/// Snapshot format version.
+///
+/// Bump whenever a serialized type changes shape, so older images are
+/// rejected instead of misread.
+pub const SNAPSHOT_VERSION: u32 = 5;
+
+/// Size of the fixed header before the payload.
+const HEADER_LEN: usize = 24;
+
+impl SnomedStore {
+    /// Writes the store to a binary snapshot file.
+    ///
+    /// `source` records what the store was loaded from; see
+    /// [`load_snapshot`](Self::load_snapshot). The image is written to a
+    /// temporary file beside `path` and renamed into place, so a reader never
+    /// sees a partial snapshot.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let files = discover_rf2_files(data_path)?;
+    /// let source = SnapshotSource::new(data_path, &files, "default");
+    /// let mut store = SnomedStore::new();
+    /// store.load(&files, &LoadOptions::default())?;
+    /// store.save_snapshot("snomed.snapshot", &source)?;
+    ///
+    /// // Later, e.g. on the next restart
+    /// let store = SnomedStore::load_snapshot("snomed.snapshot", &source)?;
+    /// ```
+    pub fn save_snapshot<P: AsRef<Path>>(&self, path: P, source: &SnapshotSource) -> Rf2Result<()> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+
+        let result = self.write_snapshot(Path::new(&temp), source);
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+            return result;
+        }
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Reads a store from a snapshot written by [`save_snapshot`](Self::save_snapshot).
+    ///
+    /// # Errors
+    /// Returns [`Rf2Error::InvalidSnapshot`] if the file is not a snapshot,
+    /// was written with another format version, fails its checksum, or was
+    /// built from another source than `expected`.
+    pub fn load_snapshot<P: AsRef<Path>>(path: P, expected: &SnapshotSource) -> Rf2Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Rf2Error::FileNotFound {
+                path: path.display().to_string(),
+            });
+        }
+
+        let data = fs::read(path)?;
+        let payload = check_image(&data).map_err(|message| invalid(path, message))?;
+        let (source, store) = split_source(payload).map_err(|message| invalid(path, message))?;
+        let source: SnapshotSource =
+            bincode::deserialize(source).map_err(|e| invalid(path, e.to_string()))?;
+        if source != *expected {
+            let message = format!("built from {}, expected {}", source, expected);
+            return Err(invalid(path, message));
+        }
+        bincode::deserialize(store).map_err(|e| invalid(path, e.to_string()))
+    }
+
+    fn write_snapshot(&self, path: &Path, source: &SnapshotSource) -> Rf2Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        // Length and checksum are filled in once the payload is written
+        file.write_all(&[0; HEADER_LEN - 12])?;
+
+        let mut payload = ChecksumWriter {
+            inner: &mut file,
+            hasher: crc32fast::Hasher::new(),
+            len: 0,
+        };
+        let source = bincode::serialize(source).map_err(|e| invalid(path, e.to_string()))?;
+        let source_len = u32::try_from(source.len())
+            .map_err(|_| invalid(path, "snapshot source is too large".to_string()))?;
+        payload.write_all(&source_len.to_le_bytes())?;
+        payload.write_all(&source)?;
+        bincode::serialize_into(&mut payload, self).map_err(|e| match *e {
+            bincode::ErrorKind::Io(e) => Rf2Error::Io(e),
+            e => invalid(path, e.to_string()),
+        })?;
+        let (len, checksum) = (payload.len, payload.hasher.finalize());
+
+        file.seek(SeekFrom::Start(12))?;
+        file.write_all(&len.to_le_bytes())?;
+        file.write_all(&checksum.to_le_bytes())?;
+        file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Validates the header and checksum of a snapshot image, returning its payload.
+fn check_image(data: &[u8]) -> Result<&[u8], String> {
+    if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+        return Err("not a store snapshot".to_string());
+    }
+
+    let version = u32::from_le_bytes(data[8..12].try_into().unwrap());
+    if version != SNAPSHOT_VERSION {
+        return Err(format!(
+            "format version {} is not supported (expected {})",
+            version, SNAPSHOT_VERSION
+        ));
+    }
+
+    let len = u64::from_le_bytes(data[12..20].try_into().unwrap());
+    let payload = &data[HEADER_LEN..];
+    if payload.len() as u64 != len {
+        return Err(format!(
+            "payload is {} bytes, header says {}",
+            payload.len(),
+            len
+        ));
+    }
+
+    let checksum = u32::from_le_bytes(data[20..24].try_into().unwrap());
+    if crc32fast::hash(payload) != checksum {
+        return Err("checksum mismatch".to_string());
+    }
+    Ok(payload)
+}
+
+/// Splits a checked payload into the encoded source and the encoded store.
+fn split_source(payload: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    if payload.len() < 4 {
+        return Err("payload has no snapshot source".to_string());
+    }
+    let (len, rest) = payload.split_at(4);
+    let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+    if rest.len() < len {
+        return Err("payload has no snapshot source".to_string());
+    }
+    Ok(rest.split_at(len))
+}
+
+fn invalid(path: &Path, message: String) -> Rf2Error {
+    Rf2Error::InvalidSnapshot {
+        path: path.display().to_string(),
+        message,
+    }
+}
+
+/// Counts and checksums the bytes passing through to `inner`.
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: crc32fast::Hasher,
+    len: u64,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_types::{Rf2Concept, Rf2Description, Rf2Relationship};
+
+    fn make_store() -> SnomedStore {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([100, 138875005].map(|id| Rf2Concept {
+            id,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }));
+        store.insert_descriptions([Rf2Description {
+            id: 1,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            concept_id: 100,
+            language_code: "en".to_string(),
+            type_id: 900000000000003001,
+            term: "Example (finding)".to_string(),
+            case_significance_id: 900000000000448009,
+        }]);
+        store.insert_relationships([Rf2Relationship {
+            id: 2,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            source_id: 100,
+            destination_id: 138875005,
+            relationship_group: 0,
+            type_id: 116680003,
+            characteristic_type_id: 900000000000011006,
+            modifier_id: 900000000000451002,
+        }]);
+        store
+    }
+
+    fn make_source() -> SnapshotSource {
+        SnapshotSource {
+            data_path: "/releases/international".to_string(),
+            release_date: Some("20250101".to_string()),
+            options: "profile=default strict=false".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.snapshot");
+        make_store().save_snapshot(&path, &make_source()).unwrap();
+
+        let store = SnomedStore::load_snapshot(&path, &make_source()).unwrap();
+        assert_eq!(store.concept_count(), 2);
+        assert_eq!(store.get_fsn(100).unwrap().term, "Example (finding)");
+        assert_eq!(store.get_parents(100), vec![138875005]);
+        assert_eq!(store.get_children(138875005), vec![100]);
+
+        // No temporary file is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_rejects_damaged_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.snapshot");
+        make_store().save_snapshot(&path, &make_source()).unwrap();
+        let image = fs::read(&path).unwrap();
+
+        let mut flipped = image.clone();
+        *flipped.last_mut().unwrap() ^= 0xff;
+        let mut version = image.clone();
+        version[8] = 0;
+        let damaged = [
+            (flipped, "checksum mismatch"),
+            (version, "format version 0"),
+            (image[..image.len() - 1].to_vec(), "header says"),
+            (b"id\teffectiveTime\n".to_vec(), "not a store snapshot"),
+        ];
+
+        for (bytes, reason) in damaged {
+            fs::write(&path, bytes).unwrap();
+            match SnomedStore::load_snapshot(&path, &make_source()) {
+                Err(Rf2Error::InvalidSnapshot { message, .. }) => {
+                    assert!(message.contains(reason), "{}", message)
+                }
+                other => panic!("expected InvalidSnapshot, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_rejects_other_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.snapshot");
+        make_store().save_snapshot(&path, &make_source()).unwrap();
+
+        let others = [
+            SnapshotSource {
+                data_path: "/releases/uk".to_string(),
+                ..make_source()
+            },
+            SnapshotSource {
+                release_date: Some("20250201".to_string()),
+                ..make_source()
+            },
+            SnapshotSource {
+                options: "profile=full strict=false".to_string(),
+                ..make_source()
+            },
+        ];
+
+        for expected in others {
+            match SnomedStore::load_snapshot(&path, &expected) {
+                Err(Rf2Error::InvalidSnapshot { message, .. }) => {
+                    assert!(message.contains("built from"), "{}", message)
+                }
+                other => panic!("expected InvalidSnapshot, got {:?}", other.map(|_| ())),
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Opening RF2 files from disk or from inside a release archive.
+//!
+//! All loaders open files through [`open_rf2_file`], so a path may point
+//! either at a plain file or, with the `zip` feature, at an entry inside a
+//! distributed release ZIP, e.g.
+//! `SnomedCT_InternationalRF2_PRODUCTION_20250101T120000Z.zip/SnomedCT_.../Snapshot/Terminology/sct2_Concept_Snapshot_INT_20250101.txt`.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::types::{Rf2Error, Rf2Result};
+
+/// A buffered reader over an RF2 file, wherever it is stored.
+pub struct Rf2Reader {
+    inner: Box<dyn BufRead + Send>,
+}
+
+impl Rf2Reader {
+    /// Wraps any buffered reader.
+    pub fn new<R: BufRead + Send + 'static>(reader: R) -> Self {
+        Self {
+            inner: Box::new(reader),
+        }
+    }
+}
+
+impl std::fmt::Debug for Rf2Reader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rf2Reader").finish_non_exhaustive()
+    }
+}
+
+impl Read for Rf2Reader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl BufRead for Rf2Reader {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+/// Opens an RF2 file for streaming.
+///
+/// Paths that pass through a `.zip` file are read from inside the archive
+/// (requires the `zip` feature); anything else is opened from disk.
+///
+/// # Errors
+/// Returns [`Rf2Error::FileNotFound`] if the file or archive entry does not exist.
+pub fn open_rf2_file<P: AsRef<Path>>(path: P) -> Rf2Result<Rf2Reader> {
+    let path = path.as_ref();
+
+    #[cfg(feature = "zip")]
+    if let Some((archive, entry)) = crate::archive::split_archive_path(path) {
+        return crate::archive::open_entry(&archive, &entry);
+    }
+
+    if !path.is_file() {
+        return Err(Rf2Error::FileNotFound {
+            path: path.display().to_string(),
+        });
+    }
+
+    let file = File::open(path)?;
+    Ok(Rf2Reader::new(BufReader::new(file)))
+}
+
+/// Returns true if the path has a `.zip` extension.
+pub(crate) fn is_zip_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_missing_file() {
+        let result = open_rf2_file("/nonexistent/sct2_Concept_Snapshot_INT_20250101.txt");
+        assert!(matches!(result, Err(Rf2Error::FileNotFound { .. })));
+    }
+
+    #[test]
+    fn test_open_plain_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        std::fs::write(&path, "id\n1\n").unwrap();
+
+        let lines: Vec<String> = open_rf2_file(&path)
+            .unwrap()
+            .lines()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(lines, vec!["id", "1"]);
+    }
+}
@@ -3,21 +3,46 @@
 //! Provides efficient storage and lookup for parsed RF2 data.
 //! Includes parallel parsing support via rayon for maximum performance.
 
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::Path;
-
 #[cfg(feature = "parallel")]
-use rayon::prelude::*;
+use std::sync::mpsc::{sync_channel, SyncSender};
+#[cfg(feature = "parallel")]
+use std::thread::ScopedJoinHandle;
+use std::time::Instant;
 
-use snomed_types::{Rf2Concept, Rf2Description, Rf2Relationship, SctId};
+use snomed_types::{
+    Acceptability, AssociationRefsetMember, AttributeValueRefsetMember, ExtendedMapRefsetMember,
+    InactivationReason, LanguageRefsetMember, OwlExpressionRefsetMember, RefsetMember, Rf2Concept,
+    Rf2ConcreteRelationship, Rf2Description, Rf2Relationship, SctId, SimpleMapRefsetMember,
+};
 
+#[cfg(feature = "parallel")]
+use crate::chunked::{parse_chunked, ChunkConfig};
 use crate::description::DescriptionFilter;
+#[cfg(feature = "mmap")]
+use crate::description::DescriptionView;
+use crate::edition::{Edition, EditionId, EditionView};
+#[cfg(feature = "mmap")]
+use crate::mapped::{self, MappedFile};
 use crate::mrcm::MrcmStore;
-use crate::parser::{parse, Rf2Parser};
-use crate::relationship::RelationshipFilter;
-use crate::types::{DescriptionConfig, Rf2Config, Rf2Files, Rf2Result, RelationshipConfig};
+#[cfg(feature = "parallel")]
+use crate::parser::parse;
+use crate::parser::{Rf2Component, Rf2Parser, Rf2Record};
+use crate::refset::{
+    parse_association_file, parse_attribute_value_file, parse_extended_map_file,
+    parse_language_file, parse_owl_expression_file, parse_refset_file, parse_refset_view_file,
+    parse_simple_map_file, Association, AssociationIndex, AttributeValueIndex, ExtendedMapIndex,
+    LanguageIndex, MapContext, OwlAxiom, OwlAxiomIndex, RefsetIndex, RefsetView, SimpleMapIndex,
+    REPLACEMENT_ASSOCIATIONS,
+};
+use crate::relationship::{OutgoingRelationship, RelationshipFilter};
+#[cfg(feature = "parallel")]
+use crate::tokenizer::{Fields, Rf2Line};
+use crate::types::{
+    DeltaStats, DescriptionConfig, Hierarchy, LoadOptions, LoadReport, ParseStats,
+    RelationshipConfig, Rf2Config, Rf2Error, Rf2Files, Rf2Result,
+};
 
 /// In-memory store for SNOMED CT data.
 ///
@@ -37,6 +62,7 @@ use crate::types::{DescriptionConfig, Rf2Config, Rf2Files, Rf2Result, Relationsh
 /// }
 /// ```
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
 pub struct SnomedStore {
     /// Concepts indexed by SCTID.
     concepts: HashMap<SctId, Rf2Concept>,
@@ -46,8 +72,35 @@ pub struct SnomedStore {
     relationships_by_source: HashMap<SctId, Vec<Rf2Relationship>>,
     /// Relationships indexed by destination concept ID (for reverse lookup).
     relationships_by_destination: HashMap<SctId, Vec<Rf2Relationship>>,
+    /// Stated relationships indexed by source concept ID.
+    stated_by_source: HashMap<SctId, Vec<Rf2Relationship>>,
+    /// Stated relationships indexed by destination concept ID.
+    stated_by_destination: HashMap<SctId, Vec<Rf2Relationship>>,
+    /// Concrete value relationships (e.g. drug strengths) indexed by source concept ID.
+    concrete_relationships_by_source: HashMap<SctId, Vec<Rf2ConcreteRelationship>>,
     /// MRCM data (optional).
     mrcm: Option<MrcmStore>,
+    /// Description acceptability per language reference set.
+    language: LanguageIndex,
+    /// Historical associations indexed by source component.
+    associations: AssociationIndex,
+    /// Attribute values (inactivation reasons) indexed by component.
+    attribute_values: AttributeValueIndex,
+    /// Extended map rows (e.g. SNOMED CT to ICD-10) indexed by concept.
+    extended_maps: ExtendedMapIndex,
+    /// Simple map rows (e.g. CTV3, ICD-O), searchable by concept and by code.
+    simple_maps: SimpleMapIndex,
+    /// Stated OWL axioms indexed by concept.
+    owl_axioms: OwlAxiomIndex,
+    /// Other reference sets (and the refset descriptor), loaded generically.
+    refsets: RefsetIndex,
+    /// Release packages merged with `load_edition`, in load order.
+    editions: Vec<Edition>,
+    /// Edition each component's current version came from.
+    component_editions: HashMap<SctId, EditionId>,
+    /// Effective time of the row that removed each core component, so an older
+    /// active row applied afterwards (e.g. from another edition) stays removed.
+    retired: HashMap<SctId, u32>,
 }
 
 impl SnomedStore {
@@ -69,7 +122,20 @@ impl SnomedStore {
             descriptions_by_concept: HashMap::with_capacity(concept_count),
             relationships_by_source: HashMap::with_capacity(concept_count),
             relationships_by_destination: HashMap::with_capacity(concept_count),
+            stated_by_source: HashMap::new(),
+            stated_by_destination: HashMap::new(),
+            concrete_relationships_by_source: HashMap::new(),
             mrcm: None,
+            language: LanguageIndex::new(),
+            associations: AssociationIndex::new(),
+            attribute_values: AttributeValueIndex::new(),
+            extended_maps: ExtendedMapIndex::new(),
+            simple_maps: SimpleMapIndex::new(),
+            owl_axioms: OwlAxiomIndex::new(),
+            refsets: RefsetIndex::new(),
+            editions: Vec::new(),
+            component_editions: HashMap::new(),
+            retired: HashMap::new(),
         }
     }
 
@@ -109,29 +175,39 @@ impl SnomedStore {
     }
 
     /// Loads relationships from an RF2 file.
+    ///
+    /// Stated relationships go to the stated view and all others to the
+    /// inferred view, see [`Hierarchy`].
     pub fn load_relationships<P: AsRef<Path>>(
         &mut self,
         path: P,
         config: RelationshipConfig,
     ) -> Rf2Result<usize> {
         let parser = Rf2Parser::<_, Rf2Relationship>::from_path(path, config.base.clone())?;
-        let mut count = 0;
+        let relationships: Vec<_> = parser
+            .flatten()
+            .filter(|r| r.passes_relationship_filter(&config))
+            .collect();
 
-        for rel in parser.flatten() {
-            if rel.passes_relationship_filter(&config) {
-                let rel_clone = rel.clone();
-                self.relationships_by_source
-                    .entry(rel.source_id)
-                    .or_default()
-                    .push(rel);
-                self.relationships_by_destination
-                    .entry(rel_clone.destination_id)
-                    .or_default()
-                    .push(rel_clone);
-                count += 1;
-            }
-        }
+        let count = relationships.len();
+        self.insert_relationships(relationships);
+        Ok(count)
+    }
 
+    /// Loads concrete value relationships from an RF2 file.
+    pub fn load_concrete_relationships<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: RelationshipConfig,
+    ) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2ConcreteRelationship>::from_path(path, config.base.clone())?;
+        let relationships: Vec<_> = parser
+            .flatten()
+            .filter(|r| r.passes_relationship_filter(&config))
+            .collect();
+
+        let count = relationships.len();
+        self.insert_concrete_relationships(relationships);
         Ok(count)
     }
 
@@ -141,26 +217,26 @@ impl SnomedStore {
 
     /// Loads concepts from an RF2 file using parallel parsing.
     ///
-    /// This method reads all lines into memory, then parses them in parallel
-    /// using rayon. Significantly faster for large files on multi-core systems.
+    /// The file is read in blocks of whole lines that are parsed in parallel
+    /// using rayon, so memory use stays bounded however large the file is.
+    /// Significantly faster for large files on multi-core systems.
     #[cfg(feature = "parallel")]
     pub fn load_concepts_parallel<P: AsRef<Path>>(
         &mut self,
         path: P,
         config: Rf2Config,
     ) -> Rf2Result<usize> {
-        let lines = read_lines_skip_header(path)?;
-
-        let concepts: Vec<Rf2Concept> = lines
-            .par_iter()
-            .filter_map(|line| parse_concept_line(line, &config))
-            .collect();
-
-        let count = concepts.len();
-        for concept in concepts {
-            self.concepts.insert(concept.id, concept);
-        }
-
+        let mut count = 0;
+        parse_chunked(
+            path.as_ref(),
+            &config,
+            parse_concept_line,
+            ChunkConfig::default(),
+            |concepts| {
+                count += concepts.len();
+                self.insert_concepts(concepts);
+            },
+        )?;
         Ok(count)
     }
 
@@ -171,21 +247,17 @@ impl SnomedStore {
         path: P,
         config: DescriptionConfig,
     ) -> Rf2Result<usize> {
-        let lines = read_lines_skip_header(path)?;
-
-        let descriptions: Vec<Rf2Description> = lines
-            .par_iter()
-            .filter_map(|line| parse_description_line(line, &config))
-            .collect();
-
-        let count = descriptions.len();
-        for desc in descriptions {
-            self.descriptions_by_concept
-                .entry(desc.concept_id)
-                .or_default()
-                .push(desc);
-        }
-
+        let mut count = 0;
+        parse_chunked(
+            path.as_ref(),
+            &config,
+            parse_description_line,
+            ChunkConfig::default(),
+            |descriptions| {
+                count += descriptions.len();
+                self.insert_descriptions(descriptions);
+            },
+        )?;
         Ok(count)
     }
 
@@ -196,26 +268,17 @@ impl SnomedStore {
         path: P,
         config: RelationshipConfig,
     ) -> Rf2Result<usize> {
-        let lines = read_lines_skip_header(path)?;
-
-        let relationships: Vec<Rf2Relationship> = lines
-            .par_iter()
-            .filter_map(|line| parse_relationship_line(line, &config))
-            .collect();
-
-        let count = relationships.len();
-        for rel in relationships {
-            let rel_clone = rel.clone();
-            self.relationships_by_source
-                .entry(rel.source_id)
-                .or_default()
-                .push(rel);
-            self.relationships_by_destination
-                .entry(rel_clone.destination_id)
-                .or_default()
-                .push(rel_clone);
-        }
-
+        let mut count = 0;
+        parse_chunked(
+            path.as_ref(),
+            &config,
+            parse_relationship_line,
+            ChunkConfig::default(),
+            |relationships| {
+                count += relationships.len();
+                self.insert_relationships(relationships);
+            },
+        )?;
         Ok(count)
     }
 
@@ -223,251 +286,1496 @@ impl SnomedStore {
     ///
     /// This is the fastest way to load a complete SNOMED CT release.
     /// Each file is parsed using parallel line processing, and all three
-    /// file types are loaded concurrently.
+    /// file types are loaded concurrently. Uses the default [`LoadOptions`].
     #[cfg(feature = "parallel")]
-    pub fn load_all_parallel(&mut self, files: &Rf2Files) -> Rf2Result<(usize, usize, usize)> {
-        let concept_path = files.concept_file.clone();
-        let description_path = files.description_file.clone();
-        let relationship_path = files.relationship_file.clone();
-
-        // Parse all files in parallel using nested rayon::join
-        let ((concepts, descriptions), relationships) = rayon::join(
-            || {
-                rayon::join(
-                    || {
-                        concept_path.as_ref().map(|p| {
-                            let lines = read_lines_skip_header(p).unwrap_or_default();
-                            let config = Rf2Config::default();
-                            lines
-                                .par_iter()
-                                .filter_map(|line| parse_concept_line(line, &config))
-                                .collect::<Vec<_>>()
-                        })
-                    },
-                    || {
-                        description_path.as_ref().map(|p| {
-                            let lines = read_lines_skip_header(p).unwrap_or_default();
-                            let config = DescriptionConfig::english_terms();
-                            lines
-                                .par_iter()
-                                .filter_map(|line| parse_description_line(line, &config))
-                                .collect::<Vec<_>>()
-                        })
-                    },
-                )
-            },
-            || {
-                relationship_path.as_ref().map(|p| {
-                    let lines = read_lines_skip_header(p).unwrap_or_default();
-                    let config = RelationshipConfig::inferred_only();
-                    lines
-                        .par_iter()
-                        .filter_map(|line| parse_relationship_line(line, &config))
-                        .collect::<Vec<_>>()
-                })
-            },
-        );
-
-        // Insert into store
-        let concept_count = if let Some(concepts) = concepts {
-            let count = concepts.len();
-            for concept in concepts {
-                self.concepts.insert(concept.id, concept);
-            }
-            count
-        } else {
-            0
-        };
-
-        let desc_count = if let Some(descriptions) = descriptions {
-            let count = descriptions.len();
-            for desc in descriptions {
-                self.descriptions_by_concept
-                    .entry(desc.concept_id)
-                    .or_default()
-                    .push(desc);
-            }
-            count
-        } else {
-            0
-        };
-
-        let rel_count = if let Some(relationships) = relationships {
-            let count = relationships.len();
-            for rel in relationships {
-                let rel_clone = rel.clone();
-                self.relationships_by_source
-                    .entry(rel.source_id)
-                    .or_default()
-                    .push(rel);
-                self.relationships_by_destination
-                    .entry(rel_clone.destination_id)
-                    .or_default()
-                    .push(rel_clone);
-            }
-            count
-        } else {
-            0
+    pub fn load_all_parallel(&mut self, files: &Rf2Files) -> Rf2Result<LoadReport> {
+        let options = LoadOptions {
+            parallel: true,
+            ..LoadOptions::default()
         };
-
-        Ok((concept_count, desc_count, rel_count))
+        self.load(files, &options)
     }
 
     /// Loads all RF2 files from a discovered file set.
+    ///
+    /// Uses the default [`LoadOptions`], parsing sequentially.
     pub fn load_all(&mut self, files: &Rf2Files) -> Rf2Result<()> {
-        if let Some(ref concept_path) = files.concept_file {
-            self.load_concepts(concept_path, Rf2Config::default())?;
-        }
-
-        if let Some(ref description_path) = files.description_file {
-            self.load_descriptions(description_path, DescriptionConfig::english_terms())?;
-        }
-
-        if let Some(ref relationship_path) = files.relationship_file {
-            self.load_relationships(relationship_path, RelationshipConfig::inferred_only())?;
-        }
-
-        Ok(())
+        let options = LoadOptions {
+            parallel: false,
+            ..LoadOptions::default()
+        };
+        self.load(files, &options).map(|_| ())
     }
 
-    /// Loads MRCM reference set data from discovered files.
+    /// Loads a discovered file set with the given filters and optional files.
     ///
-    /// This loads the MRCM domain, attribute domain, and attribute range
-    /// reference sets if present in the file set.
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`],
+    /// unless `options.strict` is set, in which case the first one fails the
+    /// load with [`Rf2Error::Row`].
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use snomed_loader::{SnomedStore, discover_rf2_files};
+    /// use snomed_loader::{discover_rf2_files, LoadOptions, SnomedStore};
     ///
-    /// let files = discover_rf2_files("/path/to/snomed")?;
+    /// let files = discover_rf2_files("/path/to/snomed/release")?;
     /// let mut store = SnomedStore::new();
-    /// store.load_all(&files)?;
-    /// store.load_mrcm(&files)?;
-    ///
-    /// if let Some(mrcm) = store.get_mrcm() {
-    ///     println!("MRCM domains: {}", mrcm.domain_count());
-    /// }
+    /// let report = store.load(&files, &LoadOptions::full_content())?;
+    /// println!("{} rows loaded, {} skipped", report.records_loaded(), report.errors.len());
     /// ```
-    pub fn load_mrcm(&mut self, files: &Rf2Files) -> Rf2Result<()> {
-        // Only load if at least one MRCM file is present
-        if files.mrcm_domain.is_none()
-            && files.mrcm_attribute_domain.is_none()
-            && files.mrcm_attribute_range.is_none()
-        {
-            return Ok(());
+    pub fn load(&mut self, files: &Rf2Files, options: &LoadOptions) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let strict = options.strict;
+
+        // Every file is read before the store changes, so a strict failure
+        // leaves it as it was; the core files check theirs before inserting
+        let concrete_relationships = match (
+            &files.concrete_relationship_file,
+            &options.concrete_relationships,
+        ) {
+            (Some(path), Some(config)) => read_file::<Rf2ConcreteRelationship>(
+                path,
+                config.base.clone(),
+                |r| r.passes_relationship_filter(config),
+                &mut report,
+                strict,
+            )?,
+            _ => Vec::new(),
+        };
+
+        let text_definitions = match (&files.text_definition_file, &options.text_definitions) {
+            (Some(path), Some(config)) => read_file::<Rf2Description>(
+                path,
+                config.base.clone(),
+                |d| d.passes_description_filter(config),
+                &mut report,
+                strict,
+            )?,
+            _ => Vec::new(),
+        };
+
+        let stated_relationships = match (
+            &files.stated_relationship_file,
+            &options.stated_relationships,
+        ) {
+            (Some(path), Some(config)) => read_file::<Rf2Relationship>(
+                path,
+                config.base.clone(),
+                |r| r.passes_relationship_filter(config),
+                &mut report,
+                strict,
+            )?,
+            _ => Vec::new(),
+        };
+
+        let refsets = if options.include_refsets {
+            read_refset_files(files, |_, _| true, &mut report, strict)?
+        } else {
+            RefsetRows::default()
+        };
+
+        let mrcm = if options.include_mrcm && files.has_mrcm_files() {
+            Some(MrcmStore::from_files(files)?)
+        } else {
+            None
+        };
+
+        match () {
+            #[cfg(feature = "mmap")]
+            _ if options.mmap && core_files_on_disk(files) => {
+                self.load_core_mapped(files, options, &mut report)?
+            }
+            #[cfg(feature = "parallel")]
+            _ if options.parallel => self.load_core_parallel(files, options, &mut report)?,
+            _ => self.load_core(files, options, &mut report)?,
         }
 
-        let mrcm_store = MrcmStore::from_files(files)?;
+        self.insert_concrete_relationships(concrete_relationships);
+        self.insert_descriptions(text_definitions);
+        self.insert_relationships(stated_relationships);
+        report.changes += self.apply_refset_rows(refsets);
+        if mrcm.is_some() {
+            self.mrcm = mrcm;
+        }
 
+        Ok(report)
+    }
 
-        self.mrcm = Some(mrcm_store);
+    /// Loads the concept, description and relationship files one after another.
+    ///
+    /// All three files are read before any row is inserted.
+    fn load_core(
+        &mut self,
+        files: &Rf2Files,
+        options: &LoadOptions,
+        report: &mut LoadReport,
+    ) -> Rf2Result<()> {
+        let strict = options.strict;
+
+        let concepts = match files.concept_file {
+            Some(ref concept_path) => read_file::<Rf2Concept>(
+                concept_path,
+                options.concepts.clone(),
+                |_| true,
+                report,
+                strict,
+            )?,
+            None => Vec::new(),
+        };
+
+        let descriptions = match files.description_file {
+            Some(ref description_path) => {
+                let config = &options.descriptions;
+                read_file::<Rf2Description>(
+                    description_path,
+                    config.base.clone(),
+                    |d| d.passes_description_filter(config),
+                    report,
+                    strict,
+                )?
+            }
+            None => Vec::new(),
+        };
+
+        let relationships = match files.relationship_file {
+            Some(ref relationship_path) => {
+                let config = &options.relationships;
+                read_file::<Rf2Relationship>(
+                    relationship_path,
+                    config.base.clone(),
+                    |r| r.passes_relationship_filter(config),
+                    report,
+                    strict,
+                )?
+            }
+            None => Vec::new(),
+        };
+
+        self.insert_concepts(concepts);
+        self.insert_descriptions(descriptions);
+        self.insert_relationships(relationships);
         Ok(())
     }
 
-    /// Returns a reference to the MRCM store if loaded.
-    pub fn get_mrcm(&self) -> Option<&MrcmStore> {
-        self.mrcm.as_ref()
-    }
+    /// Loads the concept, description and relationship files concurrently.
+    ///
+    /// Each file is parsed on its own thread by the chunked parser, which
+    /// feeds parsed blocks through a bounded channel to this thread for
+    /// insertion, so rows are stored while the files are still being read.
+    /// In strict mode the blocks are held back until every file has been
+    /// checked.
+    #[cfg(feature = "parallel")]
+    fn load_core_parallel(
+        &mut self,
+        files: &Rf2Files,
+        options: &LoadOptions,
+        report: &mut LoadReport,
+    ) -> Rf2Result<()> {
+        let chunks = ChunkConfig::default();
+        let mut pending = Vec::new();
+
+        let (concepts, descriptions, relationships) = std::thread::scope(|scope| {
+            let (sender, receiver) = sync_channel(chunks.queue_depth);
+
+            let concepts = files.concept_file.as_deref().map(|path| {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let parsed = parse_chunked(
+                        path,
+                        &options.concepts,
+                        parse_concept_line,
+                        chunks,
+                        |rows| send_rows(&sender, CoreRows::Concepts(rows)),
+                    );
+                    (path, parsed)
+                })
+            });
+            let descriptions = files.description_file.as_deref().map(|path| {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let parsed = parse_chunked(
+                        path,
+                        &options.descriptions,
+                        parse_description_line,
+                        chunks,
+                        |rows| send_rows(&sender, CoreRows::Descriptions(rows)),
+                    );
+                    (path, parsed)
+                })
+            });
+            let relationships = files.relationship_file.as_deref().map(|path| {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let parsed = parse_chunked(
+                        path,
+                        &options.relationships,
+                        parse_relationship_line,
+                        chunks,
+                        |rows| send_rows(&sender, CoreRows::Relationships(rows)),
+                    );
+                    (path, parsed)
+                })
+            });
+            drop(sender);
+
+            // Insert rows as blocks arrive; the loop ends once every parser is done
+            for rows in receiver {
+                if options.strict {
+                    pending.push(rows);
+                } else {
+                    self.insert_core_rows(rows);
+                }
+            }
 
-    /// Returns true if MRCM data has been loaded.
-    pub fn has_mrcm(&self) -> bool {
-        self.mrcm.is_some()
-    }
+            (
+                join_parser(concepts),
+                join_parser(descriptions),
+                join_parser(relationships),
+            )
+        });
 
-    /// Bulk inserts concepts.
-    pub fn insert_concepts(&mut self, concepts: impl IntoIterator<Item = Rf2Concept>) {
-        for concept in concepts {
-            self.concepts.insert(concept.id, concept);
+        for (path, parsed) in [concepts, descriptions, relationships]
+            .into_iter()
+            .flatten()
+        {
+            let parsed = parsed?;
+            report.record_file(path, parsed.stats, parsed.errors, options.strict)?;
+        }
+
+        for rows in pending {
+            self.insert_core_rows(rows);
         }
+        Ok(())
     }
 
-    /// Bulk inserts descriptions.
-    pub fn insert_descriptions(&mut self, descriptions: impl IntoIterator<Item = Rf2Description>) {
-        for desc in descriptions {
-            self.descriptions_by_concept
-                .entry(desc.concept_id)
-                .or_default()
-                .push(desc);
+    #[cfg(feature = "parallel")]
+    fn insert_core_rows(&mut self, rows: CoreRows) {
+        match rows {
+            CoreRows::Concepts(rows) => self.insert_concepts(rows),
+            CoreRows::Descriptions(rows) => self.insert_descriptions(rows),
+            CoreRows::Relationships(rows) => self.insert_relationships(rows),
         }
     }
 
-    /// Bulk inserts relationships.
-    pub fn insert_relationships(
+    /// Loads the concept, description and relationship files through memory maps.
+    ///
+    /// Rows are parsed in place and only those that pass the filters are
+    /// copied into the store, once all three files have been read.
+    #[cfg(feature = "mmap")]
+    fn load_core_mapped(
         &mut self,
-        relationships: impl IntoIterator<Item = Rf2Relationship>,
-    ) {
-        for rel in relationships {
-            let rel_clone = rel.clone();
-            self.relationships_by_source
-                .entry(rel.source_id)
-                .or_default()
-                .push(rel);
-            self.relationships_by_destination
-                .entry(rel_clone.destination_id)
-                .or_default()
-                .push(rel_clone);
-        }
+        files: &Rf2Files,
+        options: &LoadOptions,
+        report: &mut LoadReport,
+    ) -> Rf2Result<()> {
+        let strict = options.strict;
+
+        let concepts = match files.concept_file {
+            Some(ref concept_path) => read_mapped(
+                &MappedFile::open(concept_path)?,
+                options.concepts.clone(),
+                |c: Rf2Concept| Some(c),
+                report,
+                strict,
+            )?,
+            None => Vec::new(),
+        };
+
+        let descriptions = match files.description_file {
+            Some(ref description_path) => {
+                let config = &options.descriptions;
+                read_mapped(
+                    &MappedFile::open(description_path)?,
+                    config.base.clone(),
+                    |d: DescriptionView| {
+                        d.passes_description_filter(config)
+                            .then(|| d.to_description())
+                    },
+                    report,
+                    strict,
+                )?
+            }
+            None => Vec::new(),
+        };
+
+        let relationships = match files.relationship_file {
+            Some(ref relationship_path) => {
+                let config = &options.relationships;
+                read_mapped(
+                    &MappedFile::open(relationship_path)?,
+                    config.base.clone(),
+                    |r: Rf2Relationship| r.passes_relationship_filter(config).then_some(r),
+                    report,
+                    strict,
+                )?
+            }
+            None => Vec::new(),
+        };
+
+        self.insert_concepts(concepts);
+        self.insert_descriptions(descriptions);
+        self.insert_relationships(relationships);
+        Ok(())
     }
 
-    // Query methods
+    // ═══════════════════════════════════════════════════════════════════════════
+    // DELTA METHODS
+    // ═══════════════════════════════════════════════════════════════════════════
 
-    /// Gets a concept by its ID.
-    pub fn get_concept(&self, id: SctId) -> Option<&Rf2Concept> {
-        self.concepts.get(&id)
+    /// Applies a Delta release on top of the current store contents.
+    ///
+    /// Each Delta row replaces the stored component with the same id. Rows that
+    /// no longer pass the filters in `options` (e.g. a concept that has been
+    /// inactivated) remove the component instead, so the result matches
+    /// loading the new Snapshot from scratch with [`load`](Self::load) and the
+    /// same options. Optional files and reference sets are applied only when
+    /// `options` loads them.
+    ///
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`],
+    /// whose `changes` count the rows applied. In strict mode the first one
+    /// fails the delta before the store changes.
+    pub fn apply_delta(
+        &mut self,
+        files: &Rf2Files,
+        options: &LoadOptions,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_versions(files, options, &mut report)?;
+        let refsets = if options.include_refsets {
+            read_refset_files(files, |_, _| true, &mut report, options.strict)?
+        } else {
+            RefsetRows::default()
+        };
+
+        report.changes += self.apply_versions(rows, options, None);
+        report.changes += self.apply_refset_rows(refsets);
+        Ok(report)
     }
 
-    /// Returns true if a concept exists in the store.
-    pub fn has_concept(&self, id: SctId) -> bool {
-        self.concepts.contains_key(&id)
+    /// Applies a concept Delta file to the store.
+    ///
+    /// Rows passing `config` are upserted; rows failing it remove the concept.
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    pub fn apply_concept_delta<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_file::<Rf2Concept>(
+            path.as_ref(),
+            unfiltered(&config),
+            |_| true,
+            &mut report,
+            false,
+        )?;
+        report.changes = self.apply_concept_rows(rows, &config, None);
+        Ok(report)
     }
 
-    /// Gets all descriptions for a concept.
-    pub fn get_descriptions(&self, concept_id: SctId) -> Option<&Vec<Rf2Description>> {
-        self.descriptions_by_concept.get(&concept_id)
+    /// Applies a description Delta file to the store.
+    ///
+    /// Rows passing `config` are upserted; rows failing it remove the description.
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    pub fn apply_description_delta<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: DescriptionConfig,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_file::<Rf2Description>(
+            path.as_ref(),
+            unfiltered(&config.base),
+            |_| true,
+            &mut report,
+            false,
+        )?;
+        report.changes = self.apply_description_rows(rows, &config, None);
+        Ok(report)
     }
 
-    /// Gets the FSN (Fully Specified Name) for a concept.
-    pub fn get_fsn(&self, concept_id: SctId) -> Option<&Rf2Description> {
-        self.descriptions_by_concept
-            .get(&concept_id)?
-            .iter()
-            .find(|d| d.is_fsn())
+    /// Applies a relationship Delta file to the store.
+    ///
+    /// Rows passing `config` are upserted; rows failing it remove the relationship
+    /// from both the source and destination indexes. Malformed rows are skipped
+    /// and listed in the returned [`LoadReport`].
+    pub fn apply_relationship_delta<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: RelationshipConfig,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_file::<Rf2Relationship>(
+            path.as_ref(),
+            unfiltered(&config.base),
+            |_| true,
+            &mut report,
+            false,
+        )?;
+        report.changes = self.apply_relationship_rows(rows, &config, None);
+        Ok(report)
     }
 
-    /// Gets the preferred term for a concept (first synonym, or FSN if no synonym).
-    pub fn get_preferred_term(&self, concept_id: SctId) -> Option<&str> {
-        let descriptions = self.descriptions_by_concept.get(&concept_id)?;
+    /// Applies a concrete value relationship Delta file to the store.
+    ///
+    /// Rows passing `config` are upserted; rows failing it remove the relationship.
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    pub fn apply_concrete_relationship_delta<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: RelationshipConfig,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_file::<Rf2ConcreteRelationship>(
+            path.as_ref(),
+            unfiltered(&config.base),
+            |_| true,
+            &mut report,
+            false,
+        )?;
+        report.changes = self.apply_concrete_relationship_rows(rows, &config, None);
+        Ok(report)
+    }
 
-        // First try to find a synonym
-        if let Some(synonym) = descriptions.iter().find(|d| d.is_synonym()) {
-            return Some(&synonym.term);
+    /// Applies every version read from a file set, filtering with `options`.
+    fn apply_versions(
+        &mut self,
+        rows: ComponentRows,
+        options: &LoadOptions,
+        edition: Option<EditionId>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+        stats += self.apply_concept_rows(rows.concepts, &options.concepts, edition);
+        stats += self.apply_description_rows(rows.descriptions, &options.descriptions, edition);
+        stats += self.apply_relationship_rows(rows.relationships, &options.relationships, edition);
+
+        if let Some(ref config) = options.concrete_relationships {
+            stats +=
+                self.apply_concrete_relationship_rows(rows.concrete_relationships, config, edition);
         }
+        if let Some(ref config) = options.text_definitions {
+            stats += self.apply_description_rows(rows.text_definitions, config, edition);
+        }
+        if let Some(ref config) = options.stated_relationships {
+            stats += self.apply_relationship_rows(rows.stated_relationships, config, edition);
+        }
+        stats
+    }
 
-        // Fall back to FSN
-        if let Some(fsn) = descriptions.iter().find(|d| d.is_fsn()) {
-            return Some(&fsn.term);
+    fn apply_concept_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = Rf2Concept>,
+        config: &Rf2Config,
+        edition: Option<EditionId>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for concept in rows {
+            let stored = self.concepts.get(&concept.id).map(|c| c.effective_time);
+
+            if is_stale(&self.retired, concept.id, stored, concept.effective_time) {
+                stats.skipped += 1;
+            } else if concept.passes_filter(config) {
+                self.retired.remove(&concept.id);
+                self.record_edition(concept.id, edition);
+                self.concepts.insert(concept.id, concept);
+                stats.upserted += 1;
+            } else {
+                self.retired.insert(concept.id, concept.effective_time);
+                if self.concepts.remove(&concept.id).is_some() {
+                    self.component_editions.remove(&concept.id);
+                    stats.removed += 1;
+                }
+            }
         }
 
-        None
+        stats
     }
 
-    /// Gets relationships where this concept is the source.
-    pub fn get_outgoing_relationships(&self, source_id: SctId) -> Option<&Vec<Rf2Relationship>> {
-        self.relationships_by_source.get(&source_id)
+    fn apply_description_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = Rf2Description>,
+        config: &DescriptionConfig,
+        edition: Option<EditionId>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for desc in rows {
+            let (id, concept_id) = (desc.id, desc.concept_id);
+            let descriptions = self.descriptions_by_concept.entry(concept_id).or_default();
+            let position = descriptions.iter().position(|d| d.id == desc.id);
+            let stored = position.map(|i| descriptions[i].effective_time);
+
+            if is_stale(&self.retired, id, stored, desc.effective_time) {
+                stats.skipped += 1;
+            } else if desc.passes_description_filter(config) {
+                self.retired.remove(&id);
+                match position {
+                    Some(i) => descriptions[i] = desc,
+                    None => descriptions.push(desc),
+                }
+                if let Some(edition) = edition {
+                    self.component_editions.insert(id, edition);
+                }
+                stats.upserted += 1;
+            } else {
+                self.retired.insert(id, desc.effective_time);
+                if let Some(i) = position {
+                    descriptions.swap_remove(i);
+                    self.component_editions.remove(&id);
+                    stats.removed += 1;
+                }
+            }
+
+            if descriptions.is_empty() {
+                self.descriptions_by_concept.remove(&concept_id);
+            }
+        }
+
+        stats
     }
 
-    /// Gets relationships where this concept is the destination.
-    pub fn get_incoming_relationships(
-        &self,
+    fn apply_relationship_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = Rf2Relationship>,
+        config: &RelationshipConfig,
+        edition: Option<EditionId>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for rel in rows {
+            let hierarchy = hierarchy_of(&rel);
+            let stored = self
+                .relationship_index(hierarchy)
+                .0
+                .get(&rel.source_id)
+                .and_then(|rels| rels.iter().find(|r| r.id == rel.id))
+                .map(|existing| existing.effective_time);
+
+            if is_stale(&self.retired, rel.id, stored, rel.effective_time) {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let removed = self.remove_relationship(hierarchy, rel.source_id, rel.id);
+
+            if rel.passes_relationship_filter(config) {
+                self.retired.remove(&rel.id);
+                self.record_edition(rel.id, edition);
+                self.insert_relationships([rel]);
+                stats.upserted += 1;
+            } else {
+                self.retired.insert(rel.id, rel.effective_time);
+                if removed {
+                    self.component_editions.remove(&rel.id);
+                    stats.removed += 1;
+                }
+            }
+        }
+
+        stats
+    }
+
+    fn apply_concrete_relationship_rows(
+        &mut self,
+        rows: impl IntoIterator<Item = Rf2ConcreteRelationship>,
+        config: &RelationshipConfig,
+        edition: Option<EditionId>,
+    ) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+
+        for rel in rows {
+            let (id, source_id) = (rel.id, rel.source_id);
+            let relationships = self
+                .concrete_relationships_by_source
+                .entry(source_id)
+                .or_default();
+            let position = relationships.iter().position(|r| r.id == id);
+            let stored = position.map(|i| relationships[i].effective_time);
+
+            if is_stale(&self.retired, id, stored, rel.effective_time) {
+                stats.skipped += 1;
+            } else if rel.passes_relationship_filter(config) {
+                self.retired.remove(&id);
+                match position {
+                    Some(i) => relationships[i] = rel,
+                    None => relationships.push(rel),
+                }
+                if let Some(edition) = edition {
+                    self.component_editions.insert(id, edition);
+                }
+                stats.upserted += 1;
+            } else {
+                self.retired.insert(id, rel.effective_time);
+                if let Some(i) = position {
+                    relationships.swap_remove(i);
+                    self.component_editions.remove(&id);
+                    stats.removed += 1;
+                }
+            }
+
+            if relationships.is_empty() {
+                self.concrete_relationships_by_source.remove(&source_id);
+            }
+        }
+
+        stats
+    }
+
+    /// Removes a relationship from both the source and destination indexes of a view.
+    ///
+    /// Returns true if the relationship was present.
+    fn remove_relationship(
+        &mut self,
+        hierarchy: Hierarchy,
+        source_id: SctId,
+        relationship_id: SctId,
+    ) -> bool {
+        let (by_source, by_destination) = self.relationship_index_mut(hierarchy);
+        let Some(old) = take_by_id(by_source, source_id, |r| r.id == relationship_id) else {
+            return false;
+        };
+
+        take_by_id(by_destination, old.destination_id, |r| {
+            r.id == relationship_id
+        });
+        true
+    }
+
+    /// Returns the (by source, by destination) relationship indexes of a view.
+    fn relationship_index(&self, hierarchy: Hierarchy) -> (&RelationshipMap, &RelationshipMap) {
+        match hierarchy {
+            Hierarchy::Inferred => (
+                &self.relationships_by_source,
+                &self.relationships_by_destination,
+            ),
+            Hierarchy::Stated => (&self.stated_by_source, &self.stated_by_destination),
+        }
+    }
+
+    fn relationship_index_mut(
+        &mut self,
+        hierarchy: Hierarchy,
+    ) -> (&mut RelationshipMap, &mut RelationshipMap) {
+        match hierarchy {
+            Hierarchy::Inferred => (
+                &mut self.relationships_by_source,
+                &mut self.relationships_by_destination,
+            ),
+            Hierarchy::Stated => (&mut self.stated_by_source, &mut self.stated_by_destination),
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // REFERENCE SET METHODS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Applies reference set rows read by [`read_refset_files`] to the refset indexes.
+    fn apply_refset_rows(&mut self, rows: RefsetRows) -> DeltaStats {
+        let mut stats = DeltaStats::default();
+        stats += self.language.apply_members(rows.language);
+        stats += self.associations.apply_members(rows.associations);
+        stats += self.attribute_values.apply_members(rows.attribute_values);
+        stats += self.extended_maps.apply_members(rows.extended_maps);
+        stats += self.simple_maps.apply_members(rows.simple_maps);
+        stats += self.owl_axioms.apply_members(rows.owl_expressions);
+        stats += self.refsets.apply_members(rows.members);
+        stats
+    }
+
+    /// Loads a language reference set file into the acceptability index.
+    ///
+    /// Returns the number of members indexed.
+    pub fn load_language_refset<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_language_file(path, config)?.flatten();
+        Ok(self.language.apply_members(members).upserted)
+    }
+
+    /// Applies a language reference set Delta file to the acceptability index.
+    ///
+    /// Inactive members remove the description's acceptability in their refset.
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    pub fn apply_language_delta<P: AsRef<Path>>(&mut self, path: P) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let members = read_file::<LanguageRefsetMember>(
+            path.as_ref(),
+            unfiltered(&Rf2Config::default()),
+            |_| true,
+            &mut report,
+            false,
+        )?;
+        report.changes = self.language.apply_members(members);
+        Ok(report)
+    }
+
+    /// Returns the description acceptability index.
+    pub fn language_index(&self) -> &LanguageIndex {
+        &self.language
+    }
+
+    /// Gets the acceptability of a description in a language reference set.
+    pub fn get_acceptability(
+        &self,
+        description_id: SctId,
+        refset_id: SctId,
+    ) -> Option<Acceptability> {
+        self.language.acceptability(description_id, refset_id)
+    }
+
+    /// Loads an association reference set file into the association index.
+    ///
+    /// Returns the number of members indexed.
+    pub fn load_association_refset<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_association_file(path, config)?.flatten();
+        Ok(self.associations.apply_members(members).upserted)
+    }
+
+    /// Returns the historical association index.
+    pub fn association_index(&self) -> &AssociationIndex {
+        &self.associations
+    }
+
+    /// Gets the active associations from a component (e.g. REPLACED BY targets).
+    pub fn get_associations(&self, component_id: SctId) -> &[Association] {
+        self.associations.get(component_id)
+    }
+
+    /// Loads an attribute value reference set file into the attribute value index.
+    ///
+    /// Returns the number of members indexed.
+    pub fn load_attribute_value_refset<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_attribute_value_file(path, config)?.flatten();
+        Ok(self.attribute_values.apply_members(members).upserted)
+    }
+
+    /// Returns the attribute value index.
+    pub fn attribute_value_index(&self) -> &AttributeValueIndex {
+        &self.attribute_values
+    }
+
+    /// Loads an extended map reference set file into the extended map index.
+    ///
+    /// Returns the number of map rows indexed.
+    pub fn load_extended_map<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_extended_map_file(path, config)?.flatten();
+        Ok(self.extended_maps.apply_members(members).upserted)
+    }
+
+    /// Returns the extended map index.
+    pub fn extended_map_index(&self) -> &ExtendedMapIndex {
+        &self.extended_maps
+    }
+
+    /// Gets all active extended map rows for a concept.
+    pub fn get_extended_map(&self, concept_id: SctId) -> &[ExtendedMapRefsetMember] {
+        self.extended_maps.get(concept_id)
+    }
+
+    /// Maps a concept through an extended map for a patient, choosing one row per map group.
+    ///
+    /// Co-existing findings in `context` also satisfy rules on their
+    /// ancestors, so a patient with "Essential hypertension" matches
+    /// `IFA 38341003 | Hypertensive disorder |`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use snomed_loader::refset::{MapContext, Sex};
+    /// use snomed_types::well_known::ICD10_EXTENDED_MAP_REFSET;
+    ///
+    /// let patient = MapContext::new().with_age(67.0).with_sex(Sex::Male);
+    /// for row in store.evaluate_extended_map(ICD10_EXTENDED_MAP_REFSET, 44054006, &patient) {
+    ///     println!("group {}: {:?}", row.map_group, row.target());
+    /// }
+    /// ```
+    pub fn evaluate_extended_map(
+        &self,
+        refset_id: SctId,
+        concept_id: SctId,
+        context: &MapContext,
+    ) -> Vec<&ExtendedMapRefsetMember> {
+        let mut expanded = context.clone();
+        let mut queue: Vec<SctId> = context.findings.iter().copied().collect();
+        while let Some(current) = queue.pop() {
+            for parent in self.get_parents(current) {
+                if expanded.findings.insert(parent) {
+                    queue.push(parent);
+                }
+            }
+        }
+
+        self.extended_maps
+            .evaluate(refset_id, concept_id, &expanded)
+    }
+
+    /// Loads a simple map reference set file into the simple map index.
+    ///
+    /// Returns the number of map rows indexed.
+    pub fn load_simple_map<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_simple_map_file(path, config)?.flatten();
+        Ok(self.simple_maps.apply_members(members).upserted)
+    }
+
+    /// Returns the simple map index.
+    pub fn simple_map_index(&self) -> &SimpleMapIndex {
+        &self.simple_maps
+    }
+
+    /// Gets all active simple map rows for a concept (concept to codes).
+    pub fn get_simple_map(&self, concept_id: SctId) -> &[SimpleMapRefsetMember] {
+        self.simple_maps.get(concept_id)
+    }
+
+    /// Gets the concepts mapped to an external code (code to concepts), sorted by id.
+    ///
+    /// A `refset_id` of `None` searches every simple map reference set.
+    pub fn reverse_map(&self, refset_id: Option<SctId>, map_target: &str) -> Vec<SctId> {
+        self.simple_maps.concepts_for(refset_id, map_target)
+    }
+
+    /// Loads an OWL expression reference set file into the axiom index.
+    ///
+    /// Returns the number of axioms indexed.
+    pub fn load_owl_axioms<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+    ) -> Rf2Result<usize> {
+        let members = parse_owl_expression_file(path, config)?.flatten();
+        Ok(self.owl_axioms.apply_members(members).upserted)
+    }
+
+    /// Returns the OWL axiom index.
+    pub fn owl_axiom_index(&self) -> &OwlAxiomIndex {
+        &self.owl_axioms
+    }
+
+    /// Gets the active OWL axioms about a concept (its stated definition).
+    pub fn get_axioms(&self, concept_id: SctId) -> &[OwlAxiom] {
+        self.owl_axioms.get(concept_id)
+    }
+
+    /// Loads any reference set file into the generic reference set index.
+    ///
+    /// Additional columns are typed from the file name (see
+    /// [`RefsetPattern`](crate::refset::RefsetPattern)). Returns the number of
+    /// members indexed.
+    pub fn load_refset<P: AsRef<Path>>(&mut self, path: P, config: Rf2Config) -> Rf2Result<usize> {
+        let members = parse_refset_file(path, config)?.flatten();
+        Ok(self.refsets.apply_members(members).upserted)
+    }
+
+    /// Returns the generic reference set index.
+    pub fn refset_index(&self) -> &RefsetIndex {
+        &self.refsets
+    }
+
+    /// Returns true if a component is an active member of a reference set.
+    ///
+    /// Covers simple refsets (value sets) and any other reference set loaded
+    /// generically; language, association and attribute value refsets have
+    /// their own indexes.
+    pub fn is_member(&self, refset_id: SctId, component_id: SctId) -> bool {
+        self.refsets.contains(refset_id, component_id)
+    }
+
+    /// Gets the components that are active members of a reference set, sorted by id.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for concept_id in store.members(my_value_set_id) {
+    ///     println!("{:?}", store.get_fsn(concept_id).map(|d| &d.term));
+    /// }
+    /// ```
+    pub fn members(&self, refset_id: SctId) -> Vec<SctId> {
+        let mut members: Vec<SctId> = self.refsets.component_ids(refset_id).collect();
+        members.sort_unstable();
+        members
+    }
+
+    /// Gets the reference sets a component is an active member of, sorted by id.
+    pub fn refsets_for(&self, component_id: SctId) -> Vec<SctId> {
+        let mut refset_ids = self.refsets.refsets_for(component_id).to_vec();
+        refset_ids.sort_unstable();
+        refset_ids
+    }
+
+    /// Gets the reason a concept or description was inactivated.
+    ///
+    /// Returns `None` for active concepts and for components without a (known)
+    /// inactivation indicator. Descriptions are not indexed by id, so their
+    /// reason comes from the indicator reference set alone.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(reason) = store.get_inactivation_reason(concept_id) {
+    ///     println!("{} was retired: {}", concept_id, reason.name());
+    /// }
+    /// ```
+    pub fn get_inactivation_reason(&self, component_id: SctId) -> Option<InactivationReason> {
+        if self.get_concept(component_id).is_some_and(|c| c.active) {
+            return None;
+        }
+        self.attribute_values.inactivation_reason(component_id)
+    }
+
+    /// Resolves a concept id to the active concepts that currently carry its meaning.
+    ///
+    /// An active concept resolves to itself. An inactive one is followed through
+    /// its SAME AS, REPLACED BY, POSSIBLY EQUIVALENT TO and POSSIBLY REPLACED BY
+    /// associations (see [`REPLACEMENT_ASSOCIATIONS`]), repeatedly, until active
+    /// concepts are reached. Returns an empty list if no active concept is found.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for id in store.resolve_active(recorded_concept_id) {
+    ///     println!("Now coded as {}", id);
+    /// }
+    /// ```
+    pub fn resolve_active(&self, id: SctId) -> Vec<SctId> {
+        self.resolve_active_via(id, REPLACEMENT_ASSOCIATIONS)
+    }
+
+    /// Resolves a concept id to active concepts following only the given association refsets.
+    ///
+    /// Results are sorted and free of duplicates; association cycles are ignored.
+    pub fn resolve_active_via(&self, id: SctId, refset_ids: &[SctId]) -> Vec<SctId> {
+        let mut resolved = BTreeSet::new();
+        let mut visited = HashSet::new();
+        let mut queue = vec![id];
+
+        while let Some(current) = queue.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if self.get_concept(current).is_some_and(|c| c.active) {
+                resolved.insert(current);
+            } else {
+                queue.extend(self.associations.targets(current, refset_ids));
+            }
+        }
+
+        resolved.into_iter().collect()
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // EDITION METHODS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Merges a release package (International, national or extension edition)
+    /// into the store and records it as a new [`Edition`].
+    ///
+    /// Packages are merged in the order they are loaded. A row overrides the
+    /// stored component with the same id unless the stored version has a later
+    /// effective time; rows that fail the filters in `options` remove the
+    /// component. Every component remembers the edition its current version
+    /// came from, see [`edition_view`](Self::edition_view).
+    ///
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    /// In strict mode the first one fails the load before the store changes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = LoadOptions::default();
+    /// let mut store = SnomedStore::new();
+    /// store.load_edition("INT", &discover_rf2_files("/releases/international")?, &options)?;
+    /// store.load_edition("UK", &discover_rf2_files("/releases/uk")?, &options)?;
+    /// println!("UK modules: {:?}", store.get_edition("UK").unwrap().module_ids);
+    /// ```
+    ///
+    /// Fails with [`Rf2Error::TooManyEditions`] once every [`EditionId`] is taken.
+    pub fn load_edition(
+        &mut self,
+        name: &str,
+        files: &Rf2Files,
+        options: &LoadOptions,
+    ) -> Rf2Result<LoadReport> {
+        let loaded = self.editions.len();
+        let id = EditionId::try_from(loaded).map_err(|_| Rf2Error::TooManyEditions {
+            name: name.to_string(),
+            loaded,
+        })?;
+
+        let mut report = LoadReport::default();
+        let rows = read_versions(files, options, &mut report)?;
+        let refsets = if options.include_refsets {
+            read_refset_files(files, |_, _| true, &mut report, options.strict)?
+        } else {
+            RefsetRows::default()
+        };
+
+        let module_ids: BTreeSet<SctId> = rows.module_ids().chain(refsets.module_ids()).collect();
+        report.changes += self.apply_versions(rows, options, Some(id));
+        report.changes += self.apply_refset_rows(refsets);
+
+        self.editions.push(Edition {
+            id,
+            name: name.to_string(),
+            release_date: files.release_date.clone(),
+            module_ids,
+            stats: report.changes,
+        });
+        Ok(report)
+    }
+
+    /// Returns the editions loaded with [`load_edition`](Self::load_edition), in load order.
+    pub fn editions(&self) -> &[Edition] {
+        &self.editions
+    }
+
+    /// Gets a loaded edition by name.
+    pub fn get_edition(&self, name: &str) -> Option<&Edition> {
+        self.editions.iter().find(|e| e.name == name)
+    }
+
+    /// Gets the edition the current version of a component came from.
+    ///
+    /// Returns `None` for unknown components and for components loaded
+    /// without an edition (e.g. via [`load_all`](Self::load_all)).
+    pub fn get_component_edition(&self, component_id: SctId) -> Option<&Edition> {
+        let id = self.component_edition_id(component_id)?;
+        self.editions.get(usize::from(id))
+    }
+
+    /// Returns a view restricted to components from the named editions.
+    ///
+    /// Returns `None` if any name does not match a loaded edition.
+    pub fn edition_view(&self, names: &[&str]) -> Option<EditionView<'_>> {
+        let ids = names
+            .iter()
+            .map(|name| self.get_edition(name).map(|e| e.id))
+            .collect::<Option<Vec<_>>>()?;
+        Some(EditionView::new(self, ids))
+    }
+
+    pub(crate) fn component_edition_id(&self, component_id: SctId) -> Option<EditionId> {
+        self.component_editions.get(&component_id).copied()
+    }
+
+    fn record_edition(&mut self, component_id: SctId, edition: Option<EditionId>) {
+        if let Some(edition) = edition {
+            self.component_editions.insert(component_id, edition);
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // POINT-IN-TIME METHODS (Full release)
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Reconstructs the snapshot as it was on `as_of` from a Full release.
+    ///
+    /// For every component id only the row with the latest effective time
+    /// on or before `as_of` (YYYYMMDD) is kept; the filters in `options` are
+    /// then applied to those versions, as [`load`](Self::load) would.
+    ///
+    /// Malformed rows are skipped and listed in the returned [`LoadReport`].
+    /// In strict mode the first one fails the load before the store changes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use snomed_loader::{discover_rf2_full_files, LoadOptions, SnomedStore};
+    ///
+    /// let files = discover_rf2_full_files("/path/to/snomed/release")?;
+    /// let mut store = SnomedStore::new();
+    /// store.load_all_as_of(&files, 20210731, &LoadOptions::default())?;
+    /// ```
+    pub fn load_all_as_of(
+        &mut self,
+        files: &Rf2Files,
+        as_of: u32,
+        options: &LoadOptions,
+    ) -> Rf2Result<LoadReport> {
+        let mut report = LoadReport::default();
+        let rows = read_versions(files, options, &mut report)?;
+
+        // Refset indexes keep the newest version of each member, retired ones
+        // included, whatever the row order, so applying every version up to
+        // `as_of` leaves the one current on that date
+        let refsets = if options.include_refsets {
+            read_refset_files(
+                files,
+                |_, effective_time| effective_time <= as_of,
+                &mut report,
+                options.strict,
+            )?
+        } else {
+            RefsetRows::default()
+        };
+
+        self.insert_concepts(
+            latest_versions(rows.concepts, as_of)
+                .into_values()
+                .filter(|c| c.passes_filter(&options.concepts)),
+        );
+        self.insert_descriptions(
+            latest_versions(rows.descriptions, as_of)
+                .into_values()
+                .filter(|d| d.passes_description_filter(&options.descriptions)),
+        );
+        self.insert_relationships(
+            latest_versions(rows.relationships, as_of)
+                .into_values()
+                .filter(|r| r.passes_relationship_filter(&options.relationships)),
+        );
+
+        if let Some(ref config) = options.concrete_relationships {
+            self.insert_concrete_relationships(
+                latest_versions(rows.concrete_relationships, as_of)
+                    .into_values()
+                    .filter(|r| r.passes_relationship_filter(config)),
+            );
+        }
+        if let Some(ref config) = options.text_definitions {
+            self.insert_descriptions(
+                latest_versions(rows.text_definitions, as_of)
+                    .into_values()
+                    .filter(|d| d.passes_description_filter(config)),
+            );
+        }
+        if let Some(ref config) = options.stated_relationships {
+            self.insert_relationships(
+                latest_versions(rows.stated_relationships, as_of)
+                    .into_values()
+                    .filter(|r| r.passes_relationship_filter(config)),
+            );
+        }
+
+        report.changes += self.apply_refset_rows(refsets);
+        Ok(report)
+    }
+
+    /// Loads the concept versions current on `as_of` from a Full concept file.
+    pub fn load_concepts_as_of<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: Rf2Config,
+        as_of: u32,
+    ) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Concept>::from_path(path, unfiltered(&config))?;
+        let concepts: Vec<_> = latest_versions(parser.flatten(), as_of)
+            .into_values()
+            .filter(|c| c.passes_filter(&config))
+            .collect();
+
+        let count = concepts.len();
+        self.insert_concepts(concepts);
+        Ok(count)
+    }
+
+    /// Loads the description versions current on `as_of` from a Full description file.
+    pub fn load_descriptions_as_of<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: DescriptionConfig,
+        as_of: u32,
+    ) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Description>::from_path(path, unfiltered(&config.base))?;
+        let descriptions: Vec<_> = latest_versions(parser.flatten(), as_of)
+            .into_values()
+            .filter(|d| d.passes_description_filter(&config))
+            .collect();
+
+        let count = descriptions.len();
+        self.insert_descriptions(descriptions);
+        Ok(count)
+    }
+
+    /// Loads the relationship versions current on `as_of` from a Full relationship file.
+    pub fn load_relationships_as_of<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: RelationshipConfig,
+        as_of: u32,
+    ) -> Rf2Result<usize> {
+        let parser = Rf2Parser::<_, Rf2Relationship>::from_path(path, unfiltered(&config.base))?;
+        let relationships: Vec<_> = latest_versions(parser.flatten(), as_of)
+            .into_values()
+            .filter(|r| r.passes_relationship_filter(&config))
+            .collect();
+
+        let count = relationships.len();
+        self.insert_relationships(relationships);
+        Ok(count)
+    }
+
+    /// Loads the concrete value relationship versions current on `as_of` from a Full file.
+    pub fn load_concrete_relationships_as_of<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: RelationshipConfig,
+        as_of: u32,
+    ) -> Rf2Result<usize> {
+        let parser =
+            Rf2Parser::<_, Rf2ConcreteRelationship>::from_path(path, unfiltered(&config.base))?;
+        let relationships: Vec<_> = latest_versions(parser.flatten(), as_of)
+            .into_values()
+            .filter(|r| r.passes_relationship_filter(&config))
+            .collect();
+
+        let count = relationships.len();
+        self.insert_concrete_relationships(relationships);
+        Ok(count)
+    }
+
+    /// Loads MRCM reference set data from discovered files.
+    ///
+    /// This loads the MRCM domain, attribute domain, and attribute range
+    /// reference sets if present in the file set.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use snomed_loader::{SnomedStore, discover_rf2_files};
+    ///
+    /// let files = discover_rf2_files("/path/to/snomed")?;
+    /// let mut store = SnomedStore::new();
+    /// store.load_all(&files)?;
+    /// store.load_mrcm(&files)?;
+    ///
+    /// if let Some(mrcm) = store.get_mrcm() {
+    ///     println!("MRCM domains: {}", mrcm.domain_count());
+    /// }
+    /// ```
+    pub fn load_mrcm(&mut self, files: &Rf2Files) -> Rf2Result<()> {
+        // Only load if at least one MRCM file is present
+        if !files.has_mrcm_files() {
+            return Ok(());
+        }
+
+        let mrcm_store = MrcmStore::from_files(files)?;
+
+
+        self.mrcm = Some(mrcm_store);
+        Ok(())
+    }
+
+    /// Returns a reference to the MRCM store if loaded.
+    pub fn get_mrcm(&self) -> Option<&MrcmStore> {
+        self.mrcm.as_ref()
+    }
+
+    /// Returns true if MRCM data has been loaded.
+    pub fn has_mrcm(&self) -> bool {
+        self.mrcm.is_some()
+    }
+
+    /// Bulk inserts concepts.
+    pub fn insert_concepts(&mut self, concepts: impl IntoIterator<Item = Rf2Concept>) {
+        for concept in concepts {
+            self.concepts.insert(concept.id, concept);
+        }
+    }
+
+    /// Bulk inserts descriptions.
+    pub fn insert_descriptions(&mut self, descriptions: impl IntoIterator<Item = Rf2Description>) {
+        for desc in descriptions {
+            self.descriptions_by_concept
+                .entry(desc.concept_id)
+                .or_default()
+                .push(desc);
+        }
+    }
+
+    /// Bulk inserts relationships.
+    ///
+    /// Stated relationships go to the stated view and all others to the
+    /// inferred view.
+    pub fn insert_relationships(
+        &mut self,
+        relationships: impl IntoIterator<Item = Rf2Relationship>,
+    ) {
+        for rel in relationships {
+            let (by_source, by_destination) = self.relationship_index_mut(hierarchy_of(&rel));
+            by_destination
+                .entry(rel.destination_id)
+                .or_default()
+                .push(rel.clone());
+            by_source.entry(rel.source_id).or_default().push(rel);
+        }
+    }
+
+    /// Inserts concrete value relationships directly.
+    pub fn insert_concrete_relationships(
+        &mut self,
+        relationships: impl IntoIterator<Item = Rf2ConcreteRelationship>,
+    ) {
+        for rel in relationships {
+            self.concrete_relationships_by_source
+                .entry(rel.source_id)
+                .or_default()
+                .push(rel);
+        }
+    }
+
+    // Query methods
+
+    /// Gets a concept by its ID.
+    pub fn get_concept(&self, id: SctId) -> Option<&Rf2Concept> {
+        self.concepts.get(&id)
+    }
+
+    /// Returns true if a concept exists in the store.
+    pub fn has_concept(&self, id: SctId) -> bool {
+        self.concepts.contains_key(&id)
+    }
+
+    /// Gets all descriptions for a concept.
+    pub fn get_descriptions(&self, concept_id: SctId) -> Option<&Vec<Rf2Description>> {
+        self.descriptions_by_concept.get(&concept_id)
+    }
+
+    /// Gets the text definitions of a concept.
+    pub fn get_text_definitions(&self, concept_id: SctId) -> Vec<&Rf2Description> {
+        self.descriptions_by_concept
+            .get(&concept_id)
+            .into_iter()
+            .flatten()
+            .filter(|d| d.is_definition())
+            .collect()
+    }
+
+    /// Gets the FSN (Fully Specified Name) for a concept.
+    pub fn get_fsn(&self, concept_id: SctId) -> Option<&Rf2Description> {
+        self.descriptions_by_concept
+            .get(&concept_id)?
+            .iter()
+            .find(|d| d.is_fsn())
+    }
+
+    /// Gets the preferred term for a concept.
+    ///
+    /// Each language refset in `dialect_chain` is tried in order (e.g. en-GB,
+    /// then en-US), returning the synonym preferred in the first one that has
+    /// one. If none does, or no language refsets are loaded, falls back to
+    /// the first synonym, or the FSN if there is no synonym.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use snomed_types::well_known::{GB_ENGLISH_LANGUAGE_REFSET, US_ENGLISH_LANGUAGE_REFSET};
+    ///
+    /// let chain = [GB_ENGLISH_LANGUAGE_REFSET, US_ENGLISH_LANGUAGE_REFSET];
+    /// let term = store.get_preferred_term(22298006, &chain);
+    /// ```
+    pub fn get_preferred_term(&self, concept_id: SctId, dialect_chain: &[SctId]) -> Option<&str> {
+        let descriptions = self.descriptions_by_concept.get(&concept_id)?;
+
+        for &refset_id in dialect_chain {
+            if let Some(preferred) = descriptions
+                .iter()
+                .find(|d| d.is_synonym() && self.language.is_preferred(d.id, refset_id))
+            {
+                return Some(&preferred.term);
+            }
+        }
+
+        // Otherwise take the first synonym
+        if let Some(synonym) = descriptions.iter().find(|d| d.is_synonym()) {
+            return Some(&synonym.term);
+        }
+
+        // Fall back to FSN
+        if let Some(fsn) = descriptions.iter().find(|d| d.is_fsn()) {
+            return Some(&fsn.term);
+        }
+
+        None
+    }
+
+    /// Gets relationships where this concept is the source.
+    pub fn get_outgoing_relationships(&self, source_id: SctId) -> Option<&Vec<Rf2Relationship>> {
+        self.relationships_by_source.get(&source_id)
+    }
+
+    /// Gets concrete value relationships (e.g. drug strengths) where this concept is the source.
+    pub fn get_concrete_relationships(&self, source_id: SctId) -> &[Rf2ConcreteRelationship] {
+        self.concrete_relationships_by_source
+            .get(&source_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets every relationship where this concept is the source, to concepts
+    /// and to concrete values.
+    pub fn get_outgoing(&self, source_id: SctId) -> Vec<OutgoingRelationship<'_>> {
+        let to_concepts = self
+            .relationships_by_source
+            .get(&source_id)
+            .into_iter()
+            .flatten()
+            .map(OutgoingRelationship::Concept);
+        let to_values = self
+            .get_concrete_relationships(source_id)
+            .iter()
+            .map(OutgoingRelationship::Concrete);
+
+        to_concepts.chain(to_values).collect()
+    }
+
+    /// Gets relationships where this concept is the destination.
+    pub fn get_incoming_relationships(
+        &self,
         destination_id: SctId,
     ) -> Option<&Vec<Rf2Relationship>> {
         self.relationships_by_destination.get(&destination_id)
     }
 
-    /// Gets parent concepts (via IS_A relationship).
+    /// Gets stated relationships where this concept is the source.
+    pub fn get_stated_relationships(&self, source_id: SctId) -> &[Rf2Relationship] {
+        self.stated_by_source
+            .get(&source_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the inferred hierarchy.
     pub fn get_parents(&self, concept_id: SctId) -> Vec<SctId> {
-        self.relationships_by_source
+        self.get_parents_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the inferred hierarchy.
+    pub fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_children_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets parent concepts (via IS_A relationship) in the given hierarchy.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use snomed_loader::Hierarchy;
+    ///
+    /// let stated = store.get_parents_in(73211009, Hierarchy::Stated);
+    /// let inferred = store.get_parents_in(73211009, Hierarchy::Inferred);
+    /// ```
+    pub fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.relationship_index(hierarchy)
+            .0
             .get(&concept_id)
             .map(|rels| {
                 rels.iter()
@@ -478,9 +1786,10 @@ impl SnomedStore {
             .unwrap_or_default()
     }
 
-    /// Gets child concepts (via IS_A relationship).
-    pub fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
-        self.relationships_by_destination
+    /// Gets child concepts (via IS_A relationship) in the given hierarchy.
+    pub fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        self.relationship_index(hierarchy)
+            .1
             .get(&concept_id)
             .map(|rels| {
                 rels.iter()
@@ -491,178 +1800,625 @@ impl SnomedStore {
             .unwrap_or_default()
     }
 
-    // Statistics
+    // Statistics
+
+    /// Returns the number of concepts in the store.
+    pub fn concept_count(&self) -> usize {
+        self.concepts.len()
+    }
+
+    /// Returns the number of descriptions in the store.
+    pub fn description_count(&self) -> usize {
+        self.descriptions_by_concept.values().map(|v| v.len()).sum()
+    }
+
+    /// Returns the number of relationships in the store.
+    pub fn relationship_count(&self) -> usize {
+        self.relationships_by_source.values().map(|v| v.len()).sum()
+    }
+
+    /// Returns the number of stated relationships in the store.
+    pub fn stated_relationship_count(&self) -> usize {
+        self.stated_by_source.values().map(Vec::len).sum()
+    }
+
+    /// Returns the number of concrete value relationships in the store.
+    pub fn concrete_relationship_count(&self) -> usize {
+        self.concrete_relationships_by_source
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Returns an iterator over all concepts.
+    pub fn concepts(&self) -> impl Iterator<Item = &Rf2Concept> {
+        self.concepts.values()
+    }
+
+    /// Returns an iterator over all concept IDs.
+    pub fn concept_ids(&self) -> impl Iterator<Item = &SctId> {
+        self.concepts.keys()
+    }
+
+    /// Returns an iterator over all descriptions.
+    pub fn descriptions(&self) -> impl Iterator<Item = &Rf2Description> {
+        self.descriptions_by_concept.values().flatten()
+    }
+
+    /// Returns an iterator over all relationships in the given hierarchy.
+    pub fn relationships_in(&self, hierarchy: Hierarchy) -> impl Iterator<Item = &Rf2Relationship> {
+        self.relationship_index(hierarchy).0.values().flatten()
+    }
+
+    /// Estimates memory usage in bytes.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        use std::mem::size_of;
+
+        let concept_size =
+            self.concepts.capacity() * (size_of::<SctId>() + size_of::<Rf2Concept>());
+
+        let desc_size: usize = self.descriptions_by_concept.capacity()
+            * size_of::<(SctId, Vec<Rf2Description>)>()
+            + self
+                .descriptions_by_concept
+                .values()
+                .flatten()
+                .map(|d| d.term.capacity() + d.language_code.capacity())
+                .sum::<usize>()
+            + self
+                .descriptions_by_concept
+                .values()
+                .map(|descs| descs.capacity() * size_of::<Rf2Description>())
+                .sum::<usize>();
+
+        // Every relationship is held twice, once by source and once by destination
+        let rel_size: usize = [
+            &self.relationships_by_source,
+            &self.relationships_by_destination,
+            &self.stated_by_source,
+            &self.stated_by_destination,
+        ]
+        .iter()
+        .map(|index| {
+            index.capacity() * size_of::<(SctId, Vec<Rf2Relationship>)>()
+                + index
+                    .values()
+                    .map(|rels| rels.capacity() * size_of::<Rf2Relationship>())
+                    .sum::<usize>()
+        })
+        .sum();
+
+        let concrete_size: usize = self
+            .concrete_relationships_by_source
+            .values()
+            .flatten()
+            .map(|r| size_of::<Rf2ConcreteRelationship>() + r.value.as_str().map_or(0, str::len))
+            .sum();
+
+        let refset_size = self.language.estimated_memory_bytes()
+            + self.associations.estimated_memory_bytes()
+            + self.attribute_values.estimated_memory_bytes()
+            + self.extended_maps.estimated_memory_bytes()
+            + self.simple_maps.estimated_memory_bytes()
+            + self.owl_axioms.estimated_memory_bytes()
+            + self.refsets.estimated_memory_bytes();
+
+        let edition_size =
+            self.component_editions.len() * (size_of::<SctId>() + size_of::<EditionId>());
+        let retired_size = self.retired.len() * (size_of::<SctId>() + size_of::<u32>());
+
+        concept_size
+            + desc_size
+            + rel_size
+            + concrete_size
+            + refset_size
+            + edition_size
+            + retired_size
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PARALLEL PARSING HELPER FUNCTIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Relationships keyed by source or destination concept.
+type RelationshipMap = HashMap<SctId, Vec<Rf2Relationship>>;
+
+/// Returns the view a relationship belongs to: stated rows go to the stated
+/// view, inferred and additional rows to the inferred one.
+fn hierarchy_of(relationship: &Rf2Relationship) -> Hierarchy {
+    if relationship.is_stated() {
+        Hierarchy::Stated
+    } else {
+        Hierarchy::Inferred
+    }
+}
+
+/// Returns true if the stored version of a component, or the row that
+/// removed it, is newer than `effective_time`.
+fn is_stale(
+    retired: &HashMap<SctId, u32>,
+    id: SctId,
+    stored: Option<u32>,
+    effective_time: u32,
+) -> bool {
+    stored
+        .or_else(|| retired.get(&id).copied())
+        .is_some_and(|latest| latest > effective_time)
+}
+
+/// Returns a copy of `config` that lets inactive rows through.
+///
+/// Delta rows must be seen regardless of status so that inactivations can
+/// remove components from the store.
+fn unfiltered(config: &Rf2Config) -> Rf2Config {
+    Rf2Config {
+        active_only: false,
+        ..config.clone()
+    }
+}
+
+/// Keeps, per component id, the row with the latest effective time on or before `as_of`.
+///
+/// Rows after `as_of` are discarded. Ties keep the row read last.
+fn latest_versions<T: Rf2Component>(
+    rows: impl IntoIterator<Item = T>,
+    as_of: u32,
+) -> HashMap<SctId, T> {
+    let mut latest: HashMap<SctId, T> = HashMap::new();
+
+    for row in rows {
+        if row.effective_time() > as_of {
+            continue;
+        }
+        match latest.get(&row.component_id()) {
+            Some(current) if current.effective_time() > row.effective_time() => {}
+            _ => {
+                latest.insert(row.component_id(), row);
+            }
+        }
+    }
+
+    latest
+}
+
+/// Removes and returns the first entry matching `pred` under `key`,
+/// dropping the key once its list is empty.
+fn take_by_id<T>(
+    index: &mut HashMap<SctId, Vec<T>>,
+    key: SctId,
+    pred: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let entries = index.get_mut(&key)?;
+    let position = entries.iter().position(pred)?;
+    let taken = entries.swap_remove(position);
+    if entries.is_empty() {
+        index.remove(&key);
+    }
+    Some(taken)
+}
+
+/// Reads a file with the streaming parser, keeping the rows that pass `keep`.
+///
+/// Rows that fail to parse are skipped and recorded in `report` together
+/// with the file's statistics.
+fn read_file<T: Rf2Record>(
+    path: &Path,
+    config: Rf2Config,
+    keep: impl Fn(&T) -> bool,
+    report: &mut LoadReport,
+    strict: bool,
+) -> Rf2Result<Vec<T>> {
+    let start = Instant::now();
+    let mut parser = Rf2Parser::<_, T>::from_path(path, config)?.lenient();
+    let rows: Vec<T> = parser.by_ref().filter(|row| keep(row)).collect();
+
+    let total_records = parser.records_read();
+    let errors = parser.finish()?;
+    let stats = ParseStats {
+        total_records,
+        filtered_records: rows.len(),
+        skipped_records: total_records - rows.len() - errors.len(),
+        error_count: errors.len(),
+        parse_time_ms: start.elapsed().as_millis() as u64,
+    };
+    report.record_file(path, stats, errors, strict)?;
+    Ok(rows)
+}
+
+/// Reads a reference set file as typed views, keeping the rows that pass `keep`.
+///
+/// Rows that fail to parse are skipped and recorded in `report` together
+/// with the file's statistics.
+fn read_refset_file<V: RefsetView>(
+    path: &Path,
+    config: Rf2Config,
+    keep: impl Fn(&V) -> bool,
+    report: &mut LoadReport,
+    strict: bool,
+) -> Rf2Result<Vec<V>> {
+    let start = Instant::now();
+    let mut parser = parse_refset_view_file::<V, _>(path, config)?;
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    for row in parser.by_ref() {
+        match row {
+            Ok(row) if keep(&row) => rows.push(row),
+            Ok(_) => {}
+            Err(Rf2Error::Row(error)) => errors.push(*error),
+            Err(error) => return Err(error),
+        }
+    }
+
+    let total_records = parser.records_read();
+    let stats = ParseStats {
+        total_records,
+        filtered_records: rows.len(),
+        skipped_records: total_records - rows.len() - errors.len(),
+        error_count: errors.len(),
+        parse_time_ms: start.elapsed().as_millis() as u64,
+    };
+    report.record_file(path, stats, errors, strict)?;
+    Ok(rows)
+}
+
+/// Every row of a file set's component files, read but not yet applied.
+#[derive(Default)]
+struct ComponentRows {
+    concepts: Vec<Rf2Concept>,
+    descriptions: Vec<Rf2Description>,
+    relationships: Vec<Rf2Relationship>,
+    concrete_relationships: Vec<Rf2ConcreteRelationship>,
+    text_definitions: Vec<Rf2Description>,
+    stated_relationships: Vec<Rf2Relationship>,
+}
+
+impl ComponentRows {
+    /// Returns the module id of every row.
+    fn module_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        let concepts = self.concepts.iter().map(|c| c.module_id);
+        let descriptions = self
+            .descriptions
+            .iter()
+            .chain(&self.text_definitions)
+            .map(|d| d.module_id);
+        let relationships = self
+            .relationships
+            .iter()
+            .chain(&self.stated_relationships)
+            .map(|r| r.module_id);
+        let concrete = self.concrete_relationships.iter().map(|r| r.module_id);
+        concepts
+            .chain(descriptions)
+            .chain(relationships)
+            .chain(concrete)
+    }
+}
+
+/// Reads every version, active or not, from the component files that
+/// `options` loads.
+fn read_versions(
+    files: &Rf2Files,
+    options: &LoadOptions,
+    report: &mut LoadReport,
+) -> Rf2Result<ComponentRows> {
+    let strict = options.strict;
+    let mut rows = ComponentRows::default();
+
+    if let Some(ref path) = files.concept_file {
+        let config = unfiltered(&options.concepts);
+        rows.concepts = read_file(path, config, |_| true, report, strict)?;
+    }
+    if let Some(ref path) = files.description_file {
+        let config = unfiltered(&options.descriptions.base);
+        rows.descriptions = read_file(path, config, |_| true, report, strict)?;
+    }
+    if let Some(ref path) = files.relationship_file {
+        let config = unfiltered(&options.relationships.base);
+        rows.relationships = read_file(path, config, |_| true, report, strict)?;
+    }
+    if let (Some(path), Some(config)) = (
+        &files.concrete_relationship_file,
+        &options.concrete_relationships,
+    ) {
+        let config = unfiltered(&config.base);
+        rows.concrete_relationships = read_file(path, config, |_| true, report, strict)?;
+    }
+    if let (Some(path), Some(config)) = (&files.text_definition_file, &options.text_definitions) {
+        let config = unfiltered(&config.base);
+        rows.text_definitions = read_file(path, config, |_| true, report, strict)?;
+    }
+    if let (Some(path), Some(config)) = (
+        &files.stated_relationship_file,
+        &options.stated_relationships,
+    ) {
+        let config = unfiltered(&config.base);
+        rows.stated_relationships = read_file(path, config, |_| true, report, strict)?;
+    }
+
+    Ok(rows)
+}
+
+/// Every member row of a file set's reference set files, read but not yet applied.
+#[derive(Default)]
+struct RefsetRows {
+    language: Vec<LanguageRefsetMember>,
+    associations: Vec<AssociationRefsetMember>,
+    attribute_values: Vec<AttributeValueRefsetMember>,
+    extended_maps: Vec<ExtendedMapRefsetMember>,
+    simple_maps: Vec<SimpleMapRefsetMember>,
+    owl_expressions: Vec<OwlExpressionRefsetMember>,
+    members: Vec<RefsetMember>,
+}
+
+impl RefsetRows {
+    /// Returns the module id of every row.
+    fn module_ids(&self) -> impl Iterator<Item = SctId> + '_ {
+        let language = self.language.iter().map(|m| m.module_id);
+        let associations = self.associations.iter().map(|m| m.module_id);
+        let attribute_values = self.attribute_values.iter().map(|m| m.module_id);
+        let extended_maps = self.extended_maps.iter().map(|m| m.module_id);
+        let simple_maps = self.simple_maps.iter().map(|m| m.module_id);
+        let owl_expressions = self.owl_expressions.iter().map(|m| m.module_id);
+        let members = self.members.iter().map(|m| m.module_id);
+        language
+            .chain(associations)
+            .chain(attribute_values)
+            .chain(extended_maps)
+            .chain(simple_maps)
+            .chain(owl_expressions)
+            .chain(members)
+    }
+}
+
+/// Reads every reference set file in `files`.
+///
+/// Inactive rows are included so that Delta and Full files can retire
+/// members. `keep` receives each row's module id and effective time and
+/// decides whether the row is kept.
+fn read_refset_files(
+    files: &Rf2Files,
+    keep: impl Fn(SctId, u32) -> bool,
+    report: &mut LoadReport,
+    strict: bool,
+) -> Rf2Result<RefsetRows> {
+    let config = unfiltered(&Rf2Config::default());
+    let mut rows = RefsetRows::default();
+
+    for path in &files.language_refset_files {
+        let members = read_file::<LanguageRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
+        rows.language.extend(members);
+    }
+
+    if let Some(ref path) = files.association_refset_file {
+        rows.associations = read_file::<AssociationRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
+    }
 
-    /// Returns the number of concepts in the store.
-    pub fn concept_count(&self) -> usize {
-        self.concepts.len()
+    if let Some(ref path) = files.attribute_value_refset_file {
+        rows.attribute_values = read_file::<AttributeValueRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
     }
 
-    /// Returns the number of descriptions in the store.
-    pub fn description_count(&self) -> usize {
-        self.descriptions_by_concept.values().map(|v| v.len()).sum()
+    for path in &files.extended_map_files {
+        let members = read_refset_file::<ExtendedMapRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
+        rows.extended_maps.extend(members);
     }
 
-    /// Returns the number of relationships in the store.
-    pub fn relationship_count(&self) -> usize {
-        self.relationships_by_source.values().map(|v| v.len()).sum()
+    for path in &files.simple_map_files {
+        let members = read_refset_file::<SimpleMapRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
+        rows.simple_maps.extend(members);
     }
 
-    /// Returns an iterator over all concepts.
-    pub fn concepts(&self) -> impl Iterator<Item = &Rf2Concept> {
-        self.concepts.values()
+    if let Some(ref path) = files.owl_expression_file {
+        rows.owl_expressions = read_refset_file::<OwlExpressionRefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
     }
 
-    /// Returns an iterator over all concept IDs.
-    pub fn concept_ids(&self) -> impl Iterator<Item = &SctId> {
-        self.concepts.keys()
+    let generic_paths = files
+        .refset_descriptor_file
+        .iter()
+        .chain(&files.refset_files);
+    for path in generic_paths {
+        let members = read_refset_file::<RefsetMember>(
+            path,
+            config.clone(),
+            |m| keep(m.module_id, m.effective_time),
+            report,
+            strict,
+        )?;
+        rows.members.extend(members);
     }
 
-    /// Estimates memory usage in bytes.
-    pub fn estimated_memory_bytes(&self) -> usize {
-        use std::mem::size_of;
+    Ok(rows)
+}
 
-        let concept_size = self.concepts.len() * (size_of::<SctId>() + size_of::<Rf2Concept>());
+/// Returns true if every discovered core file is a plain file that can be mapped.
+#[cfg(feature = "mmap")]
+fn core_files_on_disk(files: &Rf2Files) -> bool {
+    [
+        &files.concept_file,
+        &files.description_file,
+        &files.relationship_file,
+    ]
+    .into_iter()
+    .flatten()
+    .all(|path| path.is_file())
+}
 
-        let desc_size: usize = self
-            .descriptions_by_concept
-            .values()
-            .map(|descs| {
-                size_of::<SctId>()
-                    + descs
-                        .iter()
-                        .map(|d| size_of::<Rf2Description>() + d.term.len())
-                        .sum::<usize>()
-            })
-            .sum();
+/// Reads the records of a mapped file, keeping those that `convert` maps to a row.
+///
+/// Rows that fail to parse are skipped and recorded in `report` together
+/// with the file's statistics.
+#[cfg(feature = "mmap")]
+fn read_mapped<'a, V: mapped::Rf2View<'a>, T>(
+    file: &'a MappedFile,
+    config: Rf2Config,
+    convert: impl Fn(V) -> Option<T>,
+    report: &mut LoadReport,
+    strict: bool,
+) -> Rf2Result<Vec<T>> {
+    let start = Instant::now();
+    let mut records = file.records::<V>(config)?;
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    for record in records.by_ref() {
+        match record {
+            Ok(view) => rows.extend(convert(view)),
+            Err(Rf2Error::Row(error)) => errors.push(*error),
+            Err(error) => return Err(error),
+        }
+    }
 
-        let rel_size = self.relationships_by_source.len()
-            * 2 // Both source and dest maps
-            * (size_of::<SctId>() + size_of::<Rf2Relationship>());
+    let total_records = records.records_read();
+    let stats = ParseStats {
+        total_records,
+        filtered_records: rows.len(),
+        skipped_records: total_records - rows.len() - errors.len(),
+        error_count: errors.len(),
+        parse_time_ms: start.elapsed().as_millis() as u64,
+    };
+    report.record_file(file.path(), stats, errors, strict)?;
+    Ok(rows)
+}
 
-        concept_size + desc_size + rel_size
-    }
+/// Parsed rows of a core file on their way to the store.
+#[cfg(feature = "parallel")]
+enum CoreRows {
+    Concepts(Vec<Rf2Concept>),
+    Descriptions(Vec<Rf2Description>),
+    Relationships(Vec<Rf2Relationship>),
 }
 
-// ═══════════════════════════════════════════════════════════════════════════════
-// PARALLEL PARSING HELPER FUNCTIONS
-// ═══════════════════════════════════════════════════════════════════════════════
+#[cfg(feature = "parallel")]
+fn send_rows(sender: &SyncSender<CoreRows>, rows: CoreRows) {
+    sender
+        .send(rows)
+        .expect("the store receives until every parser has finished");
+}
 
-/// Reads all lines from a file, skipping the header row.
+/// Waits for a parser thread, re-raising its panic if it had one.
 #[cfg(feature = "parallel")]
-fn read_lines_skip_header<P: AsRef<Path>>(path: P) -> Rf2Result<Vec<String>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .skip(1) // Skip header
-        .filter_map(Result::ok)
-        .filter(|line| !line.is_empty())
-        .collect();
-    Ok(lines)
+fn join_parser<T>(handle: Option<ScopedJoinHandle<'_, T>>) -> Option<T> {
+    handle.map(|handle| {
+        handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
 }
 
 /// Parses a single concept line.
+///
+/// Returns `Ok(None)` if the row is filtered out by `config`.
 #[cfg(feature = "parallel")]
-fn parse_concept_line(line: &str, config: &Rf2Config) -> Option<Rf2Concept> {
-    let fields: Vec<&str> = line.split('\t').collect();
-    if fields.len() < 5 {
-        return None;
-    }
-
-    let active = parse::boolean(fields[2]).ok()?;
-    if config.active_only && !active {
-        return None;
-    }
-
-    Some(Rf2Concept {
-        id: parse::sctid(fields[0]).ok()?,
-        effective_time: parse::effective_time(fields[1]).ok()?,
-        active,
-        module_id: parse::sctid(fields[3]).ok()?,
-        definition_status_id: parse::sctid(fields[4]).ok()?,
-    })
+fn parse_concept_line(line: Rf2Line<'_>, config: &Rf2Config) -> Rf2Result<Option<Rf2Concept>> {
+    let mut fields = Fields::new(line, Rf2Concept::EXPECTED_COLUMNS);
+
+    let concept = Rf2Concept {
+        id: fields.sctid()?,
+        effective_time: fields.parse(parse::effective_time)?,
+        active: fields.parse(parse::boolean)?,
+        module_id: fields.sctid()?,
+        definition_status_id: fields.sctid()?,
+    };
+    Ok(concept.passes_filter(config).then_some(concept))
 }
 
 /// Parses a single description line.
+///
+/// Returns `Ok(None)` if the row is filtered out by `config`.
 #[cfg(feature = "parallel")]
-fn parse_description_line(line: &str, config: &DescriptionConfig) -> Option<Rf2Description> {
-    let fields: Vec<&str> = line.split('\t').collect();
-    if fields.len() < 9 {
-        return None;
-    }
-
-    let active = parse::boolean(fields[2]).ok()?;
-    if config.base.active_only && !active {
-        return None;
-    }
-
-    let language_code = fields[5].to_string();
-    if !config.language_codes.is_empty() && !config.language_codes.contains(&language_code) {
-        return None;
-    }
-
-    let type_id = parse::sctid(fields[6]).ok()?;
-    if !config.type_ids.is_empty() && !config.type_ids.contains(&type_id) {
-        return None;
-    }
-
-    Some(Rf2Description {
-        id: parse::sctid(fields[0]).ok()?,
-        effective_time: parse::effective_time(fields[1]).ok()?,
-        active,
-        module_id: parse::sctid(fields[3]).ok()?,
-        concept_id: parse::sctid(fields[4]).ok()?,
-        language_code,
-        type_id,
-        term: fields[7].to_string(),
-        case_significance_id: parse::sctid(fields[8]).ok()?,
-    })
+fn parse_description_line(
+    line: Rf2Line<'_>,
+    config: &DescriptionConfig,
+) -> Rf2Result<Option<Rf2Description>> {
+    let mut fields = Fields::new(line, Rf2Description::EXPECTED_COLUMNS);
+
+    let description = Rf2Description {
+        id: fields.sctid()?,
+        effective_time: fields.parse(parse::effective_time)?,
+        active: fields.parse(parse::boolean)?,
+        module_id: fields.sctid()?,
+        concept_id: fields.sctid()?,
+        language_code: fields.next_field()?.to_string(),
+        type_id: fields.sctid()?,
+        term: fields.next_field()?.to_string(),
+        case_significance_id: fields.sctid()?,
+    };
+    Ok(description
+        .passes_description_filter(config)
+        .then_some(description))
 }
 
 /// Parses a single relationship line.
+///
+/// Returns `Ok(None)` if the row is filtered out by `config`.
 #[cfg(feature = "parallel")]
-fn parse_relationship_line(line: &str, config: &RelationshipConfig) -> Option<Rf2Relationship> {
-    let fields: Vec<&str> = line.split('\t').collect();
-    if fields.len() < 10 {
-        return None;
-    }
-
-    let active = parse::boolean(fields[2]).ok()?;
-    if config.base.active_only && !active {
-        return None;
-    }
-
-    let type_id = parse::sctid(fields[7]).ok()?;
-    if !config.type_ids.is_empty() && !config.type_ids.contains(&type_id) {
-        return None;
-    }
-
-    let characteristic_type_id = parse::sctid(fields[8]).ok()?;
-    if !config.characteristic_type_ids.is_empty()
-        && !config.characteristic_type_ids.contains(&characteristic_type_id)
-    {
-        return None;
-    }
-
-    Some(Rf2Relationship {
-        id: parse::sctid(fields[0]).ok()?,
-        effective_time: parse::effective_time(fields[1]).ok()?,
-        active,
-        module_id: parse::sctid(fields[3]).ok()?,
-        source_id: parse::sctid(fields[4]).ok()?,
-        destination_id: parse::sctid(fields[5]).ok()?,
-        relationship_group: parse::integer(fields[6]).ok()?,
-        type_id,
-        characteristic_type_id,
-        modifier_id: parse::sctid(fields[9]).ok()?,
-    })
+fn parse_relationship_line(
+    line: Rf2Line<'_>,
+    config: &RelationshipConfig,
+) -> Rf2Result<Option<Rf2Relationship>> {
+    let mut fields = Fields::new(line, Rf2Relationship::EXPECTED_COLUMNS);
+
+    let relationship = Rf2Relationship {
+        id: fields.sctid()?,
+        effective_time: fields.parse(parse::effective_time)?,
+        active: fields.parse(parse::boolean)?,
+        module_id: fields.sctid()?,
+        source_id: fields.sctid()?,
+        destination_id: fields.sctid()?,
+        relationship_group: fields.parse(parse::integer)?,
+        type_id: fields.sctid()?,
+        characteristic_type_id: fields.sctid()?,
+        modifier_id: fields.sctid()?,
+    };
+    Ok(relationship
+        .passes_relationship_filter(config)
+        .then_some(relationship))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snomed_types::{CharacteristicType, DefinitionStatus, DescriptionType, ModifierType};
+    use crate::types::Rf2Error;
+    use snomed_types::{
+        CharacteristicType, ConcreteValue, DefinitionStatus, DescriptionType, ModifierType,
+    };
 
     fn make_test_concept(id: SctId) -> Rf2Concept {
         Rf2Concept {
@@ -799,7 +2555,7 @@ mod tests {
         store.insert_descriptions([fsn, synonym]);
 
         // Should prefer synonym over FSN
-        let term = store.get_preferred_term(100).unwrap();
+        let term = store.get_preferred_term(100, &[]).unwrap();
         assert_eq!(term, "Test concept");
 
         // Concept with only FSN
@@ -813,7 +2569,945 @@ mod tests {
 
         store.insert_descriptions([fsn_only]);
 
-        let term = store.get_preferred_term(200).unwrap();
+        let term = store.get_preferred_term(200, &[]).unwrap();
         assert_eq!(term, "Another concept (procedure)");
     }
+
+    #[test]
+    fn test_preferred_term_dialect_chain() {
+        use snomed_types::well_known::{GB_ENGLISH_LANGUAGE_REFSET, US_ENGLISH_LANGUAGE_REFSET};
+        use snomed_types::LanguageRefsetMember;
+
+        let mut store = SnomedStore::new();
+        let synonym = |id: SctId, term: &str| Rf2Description {
+            term: term.to_string(),
+            ..make_test_description(id, 100, false)
+        };
+        store.insert_descriptions([
+            make_test_description(1, 100, true),
+            synonym(2, "Bruise"),
+            synonym(3, "Hematoma"),
+            synonym(4, "Haematoma"),
+        ]);
+
+        let member = |description_id: SctId, refset_id: SctId, acceptability_id: SctId| {
+            LanguageRefsetMember {
+                id: format!("{}-{}", description_id, refset_id),
+                effective_time: 20020131,
+                active: true,
+                module_id: 900000000000207008,
+                refset_id,
+                referenced_component_id: description_id,
+                acceptability_id,
+            }
+        };
+        store.language.apply_members([
+            member(2, US_ENGLISH_LANGUAGE_REFSET, Acceptability::ACCEPTABLE_ID),
+            member(3, US_ENGLISH_LANGUAGE_REFSET, Acceptability::PREFERRED_ID),
+            member(4, GB_ENGLISH_LANGUAGE_REFSET, Acceptability::PREFERRED_ID),
+        ]);
+
+        let us_first = [US_ENGLISH_LANGUAGE_REFSET, GB_ENGLISH_LANGUAGE_REFSET];
+        let gb_first = [GB_ENGLISH_LANGUAGE_REFSET, US_ENGLISH_LANGUAGE_REFSET];
+        assert_eq!(store.get_preferred_term(100, &us_first), Some("Hematoma"));
+        assert_eq!(store.get_preferred_term(100, &gb_first), Some("Haematoma"));
+
+        // Unknown dialects fall back to the first synonym
+        assert_eq!(store.get_preferred_term(100, &[999]), Some("Bruise"));
+        assert_eq!(
+            store.get_acceptability(2, US_ENGLISH_LANGUAGE_REFSET),
+            Some(Acceptability::Acceptable)
+        );
+    }
+
+    #[test]
+    fn test_get_inactivation_reason() {
+        use snomed_types::well_known::CONCEPT_INACTIVATION_INDICATOR_REFSET;
+        use snomed_types::AttributeValueRefsetMember;
+
+        let mut store = SnomedStore::new();
+        store.insert_concepts([
+            Rf2Concept {
+                active: false,
+                ..make_test_concept(200)
+            },
+            make_test_concept(300),
+        ]);
+        let indicator = |component_id| AttributeValueRefsetMember {
+            id: format!("{component_id}-indicator"),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id: CONCEPT_INACTIVATION_INDICATOR_REFSET,
+            referenced_component_id: component_id,
+            value_id: InactivationReason::DUPLICATE_ID,
+        };
+        store
+            .attribute_values
+            .apply_members([indicator(200), indicator(300)]);
+
+        assert_eq!(
+            store.get_inactivation_reason(200),
+            Some(InactivationReason::Duplicate)
+        );
+        assert_eq!(store.get_inactivation_reason(100), None);
+        // A stale indicator on an active concept is ignored
+        assert_eq!(store.get_inactivation_reason(300), None);
+    }
+
+    #[test]
+    fn test_resolve_active_follows_associations() {
+        use snomed_types::well_known::{
+            MOVED_TO_REFSET, POSSIBLY_EQUIVALENT_TO_REFSET, REPLACED_BY_REFSET, SAME_AS_REFSET,
+        };
+        use snomed_types::AssociationRefsetMember;
+
+        let mut store = SnomedStore::new();
+        store.insert_concepts([make_test_concept(400), make_test_concept(500)]);
+        store.insert_concepts([Rf2Concept {
+            active: false,
+            ..make_test_concept(200)
+        }]);
+
+        let member =
+            |source_id: SctId, refset_id: SctId, target_id: SctId| AssociationRefsetMember {
+                id: format!("{}-{}", source_id, target_id),
+                effective_time: 20020131,
+                active: true,
+                module_id: 900000000000207008,
+                refset_id,
+                referenced_component_id: source_id,
+                target_component_id: target_id,
+            };
+        store.associations.apply_members([
+            // 100 -> 200 (inactive) -> 400, and 100 -> 500 directly
+            member(100, REPLACED_BY_REFSET, 200),
+            member(200, SAME_AS_REFSET, 400),
+            member(100, POSSIBLY_EQUIVALENT_TO_REFSET, 500),
+            // Cycles and non-replacement associations are not followed
+            member(300, SAME_AS_REFSET, 300),
+            member(600, MOVED_TO_REFSET, 400),
+        ]);
+
+        assert_eq!(store.resolve_active(400), vec![400]);
+        assert_eq!(store.resolve_active(100), vec![400, 500]);
+        assert_eq!(store.resolve_active(200), vec![400]);
+        assert!(store.resolve_active(300).is_empty());
+        assert!(store.resolve_active(600).is_empty());
+        assert_eq!(store.resolve_active_via(600, &[MOVED_TO_REFSET]), vec![400]);
+        assert_eq!(store.get_associations(100).len(), 2);
+    }
+
+    #[test]
+    fn test_apply_concept_rows_upserts_and_removes() {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([make_test_concept(100), make_test_concept(200)]);
+
+        let updated = Rf2Concept {
+            effective_time: 20250201,
+            definition_status_id: DefinitionStatus::FULLY_DEFINED_ID,
+            ..make_test_concept(100)
+        };
+        let inactivated = Rf2Concept {
+            effective_time: 20250201,
+            active: false,
+            ..make_test_concept(200)
+        };
+        let added = Rf2Concept {
+            effective_time: 20250201,
+            ..make_test_concept(300)
+        };
+
+        let stats =
+            store.apply_concept_rows([updated, inactivated, added], &Rf2Config::default(), None);
+
+        assert_eq!(stats.upserted, 2);
+        assert_eq!(stats.removed, 1);
+        assert!(store.get_concept(100).unwrap().is_fully_defined());
+        assert!(!store.has_concept(200));
+        assert!(store.has_concept(300));
+    }
+
+    #[test]
+    fn test_apply_concept_rows_keeps_inactive_when_unfiltered() {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([make_test_concept(100)]);
+
+        let inactivated = Rf2Concept {
+            effective_time: 20250201,
+            active: false,
+            ..make_test_concept(100)
+        };
+        let config = Rf2Config {
+            active_only: false,
+            ..Default::default()
+        };
+
+        let stats = store.apply_concept_rows([inactivated], &config, None);
+
+        assert_eq!(stats.upserted, 1);
+        assert!(!store.get_concept(100).unwrap().active);
+    }
+
+    #[test]
+    fn test_apply_rows_skips_stale_versions() {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([Rf2Concept {
+            effective_time: 20250201,
+            ..make_test_concept(100)
+        }]);
+
+        let older = Rf2Concept {
+            active: false,
+            ..make_test_concept(100)
+        };
+        let stats = store.apply_concept_rows([older], &Rf2Config::default(), None);
+
+        assert_eq!(stats.skipped, 1);
+        assert!(store.has_concept(100));
+    }
+
+    #[test]
+    fn test_apply_description_rows() {
+        let mut store = SnomedStore::new();
+        store.insert_descriptions([
+            make_test_description(1, 100, true),
+            make_test_description(2, 100, false),
+        ]);
+
+        let renamed = Rf2Description {
+            effective_time: 20250201,
+            term: "Renamed".to_string(),
+            ..make_test_description(1, 100, true)
+        };
+        let inactivated = Rf2Description {
+            effective_time: 20250201,
+            active: false,
+            ..make_test_description(2, 100, false)
+        };
+
+        let stats = store.apply_description_rows(
+            [renamed, inactivated],
+            &DescriptionConfig::english_terms(),
+            None,
+        );
+
+        assert_eq!(stats.upserted, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(store.description_count(), 1);
+        assert_eq!(store.get_fsn(100).unwrap().term, "Renamed");
+    }
+
+    #[test]
+    fn test_apply_relationship_rows_keeps_indexes_consistent() {
+        let mut store = SnomedStore::new();
+        store.insert_relationships([
+            make_test_relationship(1, 100, 200, true),
+            make_test_relationship(2, 100, 300, true),
+        ]);
+
+        // Relationship 1 now points at 400; relationship 2 is inactivated
+        let moved = Rf2Relationship {
+            effective_time: 20250201,
+            ..make_test_relationship(1, 100, 400, true)
+        };
+        let inactivated = Rf2Relationship {
+            effective_time: 20250201,
+            active: false,
+            ..make_test_relationship(2, 100, 300, true)
+        };
+
+        let stats = store.apply_relationship_rows(
+            [moved, inactivated],
+            &RelationshipConfig::inferred_only(),
+            None,
+        );
+
+        assert_eq!(stats.upserted, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(store.relationship_count(), 1);
+        assert_eq!(store.get_parents(100), vec![400]);
+        assert!(store.get_children(200).is_empty());
+        assert!(store.get_children(300).is_empty());
+        assert_eq!(store.get_children(400), vec![100]);
+        assert!(store.get_incoming_relationships(200).is_none());
+    }
+
+    #[test]
+    fn test_concrete_relationships_in_outgoing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("sct2_RelationshipConcreteValues_Snapshot_INT_20250101.txt");
+        std::fs::write(
+            &path,
+            "id\teffectiveTime\tactive\tmoduleId\tsourceId\tvalue\trelationshipGroup\ttypeId\tcharacteristicTypeId\tmodifierId\n\
+             11\t20210731\t1\t900000000000207008\t100\t#500\t1\t1142135004\t900000000000011006\t900000000000451002\n\
+             12\t20210731\t1\t900000000000207008\t100\t\"tablet\"\t0\t1142139005\t900000000000011006\t900000000000451002\n\
+             13\t20210731\t1\t900000000000207008\t100\t#1\t0\t1142139005\t900000000000010007\t900000000000451002\n",
+        )
+        .unwrap();
+
+        let mut store = SnomedStore::new();
+        store.insert_relationships([make_test_relationship(1, 100, 200, true)]);
+        let count = store
+            .load_concrete_relationships(&path, RelationshipConfig::inferred_only())
+            .unwrap();
+
+        // The stated row is filtered out
+        assert_eq!(count, 2);
+        assert_eq!(store.concrete_relationship_count(), 2);
+
+        let outgoing = store.get_outgoing(100);
+        assert_eq!(outgoing.len(), 3);
+        assert_eq!(outgoing[0].destination_id(), Some(200));
+        let values: Vec<_> = outgoing.iter().filter_map(|r| r.value()).collect();
+        assert_eq!(
+            values,
+            vec![
+                &ConcreteValue::Integer(500),
+                &ConcreteValue::String("tablet".to_string())
+            ]
+        );
+
+        // A Delta row inactivating the strength removes it
+        let inactivated = Rf2ConcreteRelationship {
+            effective_time: 20250201,
+            active: false,
+            ..store.get_concrete_relationships(100)[0].clone()
+        };
+        let stats = store.apply_concrete_relationship_rows(
+            [inactivated],
+            &RelationshipConfig::inferred_only(),
+            None,
+        );
+        assert_eq!(stats.removed, 1);
+        assert_eq!(store.get_outgoing(100).len(), 2);
+    }
+
+    #[test]
+    fn test_latest_versions_as_of() {
+        let rows = [
+            make_test_concept(100),
+            Rf2Concept {
+                effective_time: 20210131,
+                active: false,
+                ..make_test_concept(100)
+            },
+            Rf2Concept {
+                effective_time: 20220131,
+                active: true,
+                ..make_test_concept(100)
+            },
+            Rf2Concept {
+                effective_time: 20220131,
+                ..make_test_concept(200)
+            },
+        ];
+
+        let latest = latest_versions(rows, 20210731);
+
+        // 100 was inactivated in 2021 and reactivated after the requested date
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[&100].effective_time, 20210131);
+        assert!(!latest[&100].active);
+    }
+
+    #[test]
+    fn test_load_concepts_as_of_full_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sct2_Concept_Full_INT_20220131.txt");
+        std::fs::write(
+            &path,
+            "id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n\
+             100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             100\t20210131\t0\t900000000000207008\t900000000000074008\n\
+             200\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20220131\t1\t900000000000207008\t900000000000073002\n",
+        )
+        .unwrap();
+
+        let mut store = SnomedStore::new();
+        let count = store
+            .load_concepts_as_of(&path, Rf2Config::default(), 20210731)
+            .unwrap();
+
+        assert_eq!(count, 1);
+        assert!(!store.has_concept(100));
+        assert!(store.get_concept(200).unwrap().is_primitive());
+
+        // Replacing versions already in the store still counts them
+        let count = store
+            .load_concepts_as_of(&path, Rf2Config::default(), 20220131)
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(!store.get_concept(200).unwrap().is_primitive());
+    }
+
+    #[test]
+    fn test_evaluate_extended_map_with_ancestor_findings() {
+        use crate::refset::MapContext;
+        use snomed_types::well_known::ICD10_EXTENDED_MAP_REFSET;
+
+        let mut store = SnomedStore::new();
+        store.insert_concepts([make_test_concept(100), make_test_concept(200)]);
+        // 200 (e.g. essential hypertension) IS_A 100 (hypertensive disorder)
+        store.insert_relationships([make_test_relationship(1, 200, 100, true)]);
+
+        let row = |id: &str, priority: u32, rule: &str, target: &str| ExtendedMapRefsetMember {
+            id: id.to_string(),
+            effective_time: 20250101,
+            active: true,
+            module_id: 449080006,
+            refset_id: ICD10_EXTENDED_MAP_REFSET,
+            referenced_component_id: 300,
+            map_group: 1,
+            map_priority: priority,
+            map_rule: rule.to_string(),
+            map_advice: String::new(),
+            map_target: target.to_string(),
+            correlation_id: 447561005,
+            map_category_id: 447637006,
+        };
+        store.extended_maps.apply_members([
+            row("a", 1, "IFA 100 | Hypertensive disorder |", "O10.0"),
+            row("b", 2, "OTHERWISE TRUE", "O16"),
+        ]);
+
+        let hypertensive = MapContext::new().with_finding(200);
+        let chosen = store.evaluate_extended_map(ICD10_EXTENDED_MAP_REFSET, 300, &hypertensive);
+        assert_eq!(chosen[0].target(), Some("O10.0"));
+
+        let chosen =
+            store.evaluate_extended_map(ICD10_EXTENDED_MAP_REFSET, 300, &MapContext::new());
+        assert_eq!(chosen[0].target(), Some("O16"));
+        assert_eq!(store.get_extended_map(300).len(), 2);
+    }
+
+    #[test]
+    fn test_load_owl_axioms() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("sct2_sRefset_OWLExpressionSnapshot_INT_20250101.txt");
+        std::fs::write(
+            &path,
+            "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\towlExpression\n\
+             a\t20200131\t1\t900000000000207008\t733073007\t73211009\tSubClassOf(:73211009 ObjectIntersectionOf(:362969004 ObjectSomeValuesFrom(:609096000 ObjectSomeValuesFrom(:363698007 :113331007))))\n\
+             b\t20200131\t1\t900000000000012004\t762103008\t734147008\tOntology(<http://snomed.info/sct/900000000000207008>)\n\
+             c\t20200131\t0\t900000000000207008\t733073007\t73211009\tSubClassOf(:73211009 :64572001)\n",
+        )
+        .unwrap();
+
+        let mut store = SnomedStore::new();
+        assert_eq!(
+            store.load_owl_axioms(&path, Rf2Config::default()).unwrap(),
+            1
+        );
+
+        let axioms = store.get_axioms(73211009);
+        assert_eq!(axioms.len(), 1);
+        assert_eq!(axioms[0].axiom.parents(), vec![362969004]);
+        let attributes = axioms[0].axiom.attributes();
+        assert_eq!(attributes[0].group, 1);
+        assert_eq!(attributes[0].type_id, 363698007);
+    }
+
+    #[test]
+    fn test_simple_map_lookup() {
+        use snomed_types::well_known::{CTV3_SIMPLE_MAP_REFSET, ICDO_SIMPLE_MAP_REFSET};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("der2_sRefset_SimpleMapSnapshot_INT_20250101.txt");
+        std::fs::write(
+            &path,
+            "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tmapTarget\n\
+             a\t20020131\t1\t900000000000207008\t900000000000497000\t100\tX200E\n\
+             b\t20020131\t1\t900000000000207008\t446608001\t100\tM8000/0\n\
+             c\t20020131\t1\t900000000000207008\t900000000000497000\t200\tX200E\n\
+             d\t20020131\t0\t900000000000207008\t900000000000497000\t300\tX200E\n",
+        )
+        .unwrap();
+
+        let mut store = SnomedStore::new();
+        let loaded = store.load_simple_map(&path, Rf2Config::default()).unwrap();
+        assert_eq!(loaded, 3);
+
+        assert_eq!(store.get_simple_map(100).len(), 2);
+        assert_eq!(
+            store.reverse_map(Some(CTV3_SIMPLE_MAP_REFSET), "X200E"),
+            vec![100, 200]
+        );
+        assert_eq!(store.reverse_map(None, "M8000/0"), vec![100]);
+        assert!(store
+            .reverse_map(Some(ICDO_SIMPLE_MAP_REFSET), "X200E")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_simple_refset_membership() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("der2_Refset_SimpleSnapshot_INT_20250101.txt");
+        std::fs::write(
+            &path,
+            "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\n\
+             a\t20020131\t1\t900000000000207008\t1000\t300\n\
+             b\t20020131\t1\t900000000000207008\t1000\t100\n\
+             c\t20020131\t0\t900000000000207008\t1000\t200\n\
+             d\t20020131\t1\t900000000000207008\t2000\t100\n",
+        )
+        .unwrap();
+
+        let mut store = SnomedStore::new();
+        assert_eq!(store.load_refset(&path, Rf2Config::default()).unwrap(), 3);
+
+        assert!(store.is_member(1000, 100));
+        assert!(!store.is_member(1000, 200));
+        assert_eq!(store.members(1000), vec![100, 300]);
+        assert_eq!(store.refsets_for(100), vec![1000, 2000]);
+        assert!(store.refsets_for(200).is_empty());
+        assert!(store.members(3000).is_empty());
+    }
+
+    fn write_edition(dir: &Path, concepts: &str, relationships: &str) -> Rf2Files {
+        std::fs::create_dir_all(dir).unwrap();
+        let concept_file = dir.join("sct2_Concept_Snapshot_XX_20250401.txt");
+        let relationship_file = dir.join("sct2_Relationship_Snapshot_XX_20250401.txt");
+        std::fs::write(
+            &concept_file,
+            format!(
+                "id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n{}",
+                concepts
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            &relationship_file,
+            format!(
+                "id\teffectiveTime\tactive\tmoduleId\tsourceId\tdestinationId\t\
+                 relationshipGroup\ttypeId\tcharacteristicTypeId\tmodifierId\n{}",
+                relationships
+            ),
+        )
+        .unwrap();
+
+        Rf2Files {
+            concept_file: Some(concept_file),
+            relationship_file: Some(relationship_file),
+            release_date: Some("20250401".to_string()),
+            ..Rf2Files::new()
+        }
+    }
+
+    #[test]
+    fn test_load_editions_merges_by_effective_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let international = write_edition(
+            &dir.path().join("int"),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             300\t20020131\t1\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t200\t100\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+        let extension = write_edition(
+            &dir.path().join("uk"),
+            "100\t20250401\t1\t999000011000000103\t900000000000073002\n\
+             200\t20010131\t0\t999000011000000103\t900000000000074008\n\
+             300\t20250401\t0\t999000011000000103\t900000000000074008\n\
+             400\t20250401\t1\t999000011000000103\t900000000000074008\n",
+            "2\t20250401\t1\t999000011000000103\t400\t100\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+
+        let mut store = SnomedStore::new();
+        let options = LoadOptions::default();
+        store.load_edition("INT", &international, &options).unwrap();
+        store.load_edition("UK", &extension, &options).unwrap();
+        let uk = store.get_edition("UK").unwrap();
+
+        assert_eq!(uk.id, 1);
+        assert!(uk.has_module(999000011000000103));
+        assert_eq!(
+            uk.stats,
+            DeltaStats {
+                upserted: 3,
+                removed: 1,
+                skipped: 1,
+            }
+        );
+
+        // Newer extension rows override, older ones are ignored
+        assert!(!store.get_concept(100).unwrap().is_primitive());
+        assert!(store.get_concept(200).unwrap().active);
+        assert!(!store.has_concept(300));
+        assert_eq!(store.get_component_edition(100).unwrap().name, "UK");
+        assert_eq!(store.get_component_edition(200).unwrap().name, "INT");
+        assert!(store.get_component_edition(300).is_none());
+
+        let int_view = store.edition_view(&["INT"]).unwrap();
+        assert!(int_view.has_concept(200));
+        assert!(!int_view.has_concept(400));
+        assert_eq!(int_view.get_children(100), vec![200]);
+
+        let uk_view = store.edition_view(&["UK"]).unwrap();
+        assert_eq!(uk_view.concept_count(), 2);
+        assert_eq!(uk_view.get_parents(400), vec![100]);
+
+        let merged = store.edition_view(&["INT", "UK"]).unwrap();
+        assert_eq!(merged.concept_count(), store.concept_count());
+        assert!(store.edition_view(&["US"]).is_none());
+    }
+
+    #[test]
+    fn test_load_edition_keeps_components_retired_by_an_earlier_edition() {
+        let dir = tempfile::tempdir().unwrap();
+        let write_refset = |dir: &Path, row: &str| {
+            let path = dir.join("der2_Refset_SimpleSnapshot_XX_20250401.txt");
+            std::fs::write(
+                &path,
+                format!(
+                    "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\n{row}"
+                ),
+            )
+            .unwrap();
+            vec![path]
+        };
+
+        let newer = write_edition(
+            &dir.path().join("newer"),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20250401\t0\t900000000000207008\t900000000000074008\n",
+            "1\t20250401\t0\t900000000000207008\t100\t138875005\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+        let newer = Rf2Files {
+            refset_files: write_refset(
+                &dir.path().join("newer"),
+                "a\t20250401\t0\t900000000000207008\t1000\t100\n",
+            ),
+            ..newer
+        };
+        let older = write_edition(
+            &dir.path().join("older"),
+            "200\t20020131\t1\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t100\t138875005\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+        let older = Rf2Files {
+            refset_files: write_refset(
+                &dir.path().join("older"),
+                "a\t20020131\t1\t900000000000207008\t1000\t100\n",
+            ),
+            ..older
+        };
+
+        // The edition holding the newer inactivations is loaded first
+        let mut store = SnomedStore::new();
+        let options = LoadOptions::default();
+        store.load_edition("NEW", &newer, &options).unwrap();
+        let report = store.load_edition("OLD", &older, &options).unwrap();
+
+        assert_eq!(report.changes.skipped, 3);
+        assert_eq!(report.changes.upserted, 0);
+        assert!(!store.has_concept(200));
+        assert!(store.get_parents(100).is_empty());
+        assert!(!store.is_member(1000, 100));
+    }
+
+    #[test]
+    fn test_apply_delta_filters_with_load_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let delta = write_edition(
+            dir.path(),
+            "100\t20250401\t1\t900000000000207008\t900000000000074008\n\
+             200\t20250401\t0\t900000000000207008\t900000000000074008\n",
+            "1\t20250401\t1\t900000000000207008\t200\t100\t0\t116680003\t900000000000010007\t900000000000451002\n",
+        );
+
+        // Default options drop the inactive concept and the stated relationship
+        let mut store = SnomedStore::new();
+        let report = store.apply_delta(&delta, &LoadOptions::default()).unwrap();
+        assert_eq!(report.changes.upserted, 1);
+        assert!(!store.has_concept(200));
+        assert!(store.get_parents_in(200, Hierarchy::Stated).is_empty());
+
+        // Full content keeps both
+        let mut store = SnomedStore::new();
+        let report = store
+            .apply_delta(&delta, &LoadOptions::full_content())
+            .unwrap();
+        assert_eq!(report.changes.upserted, 3);
+        assert!(!store.get_concept(200).unwrap().active);
+        assert_eq!(store.get_parents_in(200, Hierarchy::Stated), vec![100]);
+    }
+
+    #[test]
+    fn test_load_edition_fails_once_edition_ids_run_out() {
+        let mut store = SnomedStore::new();
+        store.editions = (0..=EditionId::MAX)
+            .map(|id| Edition {
+                id,
+                name: format!("E{id}"),
+                release_date: None,
+                module_ids: BTreeSet::new(),
+                stats: DeltaStats::default(),
+            })
+            .collect();
+
+        let result = store.load_edition("XX", &Rf2Files::default(), &LoadOptions::default());
+
+        assert!(matches!(
+            result,
+            Err(Rf2Error::TooManyEditions { ref name, loaded: 65536 }) if name == "XX"
+        ));
+    }
+
+    #[test]
+    fn test_load_all_reads_text_definitions_and_stated_relationships() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = write_edition(
+            dir.path(),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             300\t20020131\t1\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t300\t100\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+
+        let text_definition_file = dir
+            .path()
+            .join("sct2_TextDefinition_Snapshot-en_XX_20250401.txt");
+        std::fs::write(
+            &text_definition_file,
+            "id\teffectiveTime\tactive\tmoduleId\tconceptId\tlanguageCode\ttypeId\tterm\tcaseSignificanceId\n\
+             11\t20020131\t1\t900000000000207008\t300\ten\t900000000000550004\tA test definition\t900000000000448009\n\
+             12\t20020131\t0\t900000000000207008\t300\ten\t900000000000550004\tRetired definition\t900000000000448009\n",
+        )
+        .unwrap();
+        let stated_file = dir
+            .path()
+            .join("sct2_StatedRelationship_Snapshot_XX_20250401.txt");
+        std::fs::write(
+            &stated_file,
+            "id\teffectiveTime\tactive\tmoduleId\tsourceId\tdestinationId\t\
+             relationshipGroup\ttypeId\tcharacteristicTypeId\tmodifierId\n\
+             2\t20020131\t1\t900000000000207008\t300\t200\t0\t116680003\t900000000000010007\t900000000000451002\n",
+        )
+        .unwrap();
+        files.text_definition_file = Some(text_definition_file);
+        files.stated_relationship_file = Some(stated_file);
+
+        let mut store = SnomedStore::new();
+        store.load_all(&files).unwrap();
+
+        let definitions = store.get_text_definitions(300);
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].term, "A test definition");
+        assert!(store.get_fsn(300).is_none());
+
+        // Stated and inferred views are kept apart
+        assert_eq!(store.relationship_count(), 1);
+        assert_eq!(store.stated_relationship_count(), 1);
+        assert_eq!(store.get_parents(300), vec![100]);
+        assert_eq!(store.get_parents_in(300, Hierarchy::Stated), vec![200]);
+        assert_eq!(store.get_children_in(200, Hierarchy::Stated), vec![300]);
+        assert!(store.get_children(200).is_empty());
+        assert_eq!(store.get_stated_relationships(300)[0].id, 2);
+    }
+
+    #[test]
+    fn test_load_with_options_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = write_edition(
+            dir.path(),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20020131\t0\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t200\t100\t0\t116680003\t900000000000011006\t900000000000451002\n\
+             2\t20020131\t1\t900000000000207008\t200\t100\t0\t116680003\t900000000000227009\t900000000000451002\n",
+        );
+
+        for parallel in [false, true] {
+            let mut minimal = SnomedStore::new();
+            let options = LoadOptions {
+                parallel,
+                ..LoadOptions::minimal_runtime()
+            };
+            minimal.load(&files, &options).unwrap();
+            assert_eq!(minimal.concept_count(), 1);
+            assert_eq!(minimal.relationship_count(), 1);
+
+            // Inactive concepts and additional relationships are kept
+            let mut full = SnomedStore::new();
+            let options = LoadOptions {
+                parallel,
+                ..LoadOptions::full_content()
+            };
+            full.load(&files, &options).unwrap();
+            assert!(!full.get_concept(200).unwrap().active);
+            assert_eq!(full.relationship_count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_load_reports_malformed_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = write_edition(
+            dir.path(),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             abc\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             \n\
+             300\t20020131\t0\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t100\t138875005\t0\t116680003\t900000000000011006\n",
+        );
+        let concept_file = files.concept_file.clone().unwrap();
+        let relationship_file = files.relationship_file.clone().unwrap();
+
+        // The streaming, chunked and memory-mapped paths report alike
+        for (parallel, mmap) in [(false, false), (true, false), (false, true)] {
+            let mut store = SnomedStore::new();
+            let options = LoadOptions {
+                parallel,
+                mmap,
+                ..LoadOptions::minimal_runtime()
+            };
+            let report = store.load(&files, &options).unwrap();
+
+            assert_eq!(store.concept_count(), 1);
+            assert_eq!(report.errors.len(), 2);
+            assert_eq!(report.errors[0].path, concept_file);
+            assert_eq!(report.errors[0].line, 3);
+            assert_eq!(report.errors[0].column, Some("id"));
+            assert_eq!(report.errors[1].path, relationship_file);
+            assert_eq!(report.errors[1].line, 2);
+
+            let stats = report.stats(&concept_file).unwrap();
+            assert_eq!(stats.total_records, 3);
+            assert_eq!(stats.filtered_records, 1);
+            assert_eq!(stats.skipped_records, 1);
+            assert_eq!(stats.error_count, 1);
+
+            let options = LoadOptions {
+                strict: true,
+                ..options
+            };
+            let mut store = SnomedStore::new();
+            let err = store.load(&files, &options).unwrap_err();
+            assert!(matches!(err, Rf2Error::Row(row) if row.line == 3));
+            assert_eq!(store.concept_count(), 0);
+            assert_eq!(store.relationship_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_load_reports_malformed_refset_and_delta_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = write_edition(
+            dir.path(),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\n",
+            "1\t20020131\t1\t900000000000207008\t100\t138875005\t0\t116680003\t900000000000011006\t900000000000451002\n",
+        );
+        let refset_file = dir
+            .path()
+            .join("der2_Refset_SimpleSnapshot_XX_20250401.txt");
+        std::fs::write(
+            &refset_file,
+            "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\n\
+             a\t20020131\t1\t900000000000207008\t1000\t100\n\
+             b\t20020131\t1\t900000000000207008\t1000\tabc\n",
+        )
+        .unwrap();
+        files.refset_files = vec![refset_file.clone()];
+
+        for (parallel, mmap) in [(false, false), (true, false), (false, true)] {
+            let options = LoadOptions {
+                parallel,
+                mmap,
+                ..LoadOptions::default()
+            };
+            let mut store = SnomedStore::new();
+            let report = store.load(&files, &options).unwrap();
+
+            assert!(store.is_member(1000, 100));
+            assert_eq!(report.errors.len(), 1);
+            assert_eq!(report.errors[0].path, refset_file);
+            assert_eq!(report.errors[0].line, 3);
+            assert_eq!(report.stats(&refset_file).unwrap().error_count, 1);
+            assert_eq!(report.changes.upserted, 1);
+
+            // A strict load fails before anything is stored
+            let options = LoadOptions {
+                strict: true,
+                ..options
+            };
+            let mut store = SnomedStore::new();
+            let err = store.load(&files, &options).unwrap_err();
+            assert!(matches!(err, Rf2Error::Row(row) if row.path == refset_file));
+            assert_eq!(store.concept_count(), 0);
+            assert_eq!(store.relationship_count(), 0);
+            assert!(!store.is_member(1000, 100));
+        }
+
+        // Delta and edition rows are reported the same way
+        let mut store = SnomedStore::new();
+        let report = store.apply_delta(&files, &LoadOptions::default()).unwrap();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.stats(&refset_file).unwrap().error_count, 1);
+        assert!(store.is_member(1000, 100));
+
+        let strict = LoadOptions {
+            strict: true,
+            ..LoadOptions::default()
+        };
+        let mut store = SnomedStore::new();
+        assert!(store.load_edition("XX", &files, &strict).is_err());
+        assert!(store.editions().is_empty());
+        assert_eq!(store.concept_count(), 0);
+    }
+
+    #[test]
+    fn test_loaders_tokenize_rows_alike() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = write_edition(
+            dir.path(),
+            "100\t20020131\t1\t900000000000207008\t900000000000074008\r\n",
+            "1\t20020131\t1\t900000000000207008\t100\t138875005\t0\t116680003\t900000000000011006\t900000000000451002\t\r\n",
+        );
+        let description_file = dir
+            .path()
+            .join("sct2_Description_Snapshot-en_XX_20250401.txt");
+        std::fs::write(
+            &description_file,
+            "\u{feff}id\teffectiveTime\tactive\tmoduleId\tconceptId\tlanguageCode\ttypeId\tterm\tcaseSignificanceId\r\n\
+             1\t20020131\t1\t900000000000207008\t100\ten\t900000000000013009\tTape 1\" wide\t900000000000448009\r\n\
+             2\t20020131\t1\t900000000000207008\t100\ten\t900000000000013009\t\"Quoted\" term\t900000000000448009\t\r\n\
+             3\t20020131\t1\t900000000000207008\t100\ten\t900000000000013009\t\"unbalanced\t900000000000448009\r\n",
+        )
+        .unwrap();
+        files.description_file = Some(description_file);
+
+        // BOM, CRLF, quotes and trailing tabs read the same on every path
+        for (parallel, mmap) in [(false, false), (true, false), (false, true)] {
+            let mut store = SnomedStore::new();
+            let options = LoadOptions {
+                parallel,
+                mmap,
+                ..LoadOptions::minimal_runtime()
+            };
+            let report = store.load(&files, &options).unwrap();
+            assert!(report.is_clean(), "{:?}", report.errors);
+
+            let mut terms: Vec<&str> = store
+                .get_descriptions(100)
+                .unwrap()
+                .iter()
+                .map(|d| d.term.as_str())
+                .collect();
+            terms.sort_unstable();
+            assert_eq!(
+                terms,
+                vec!["\"Quoted\" term", "\"unbalanced", "Tape 1\" wide"]
+            );
+            assert_eq!(store.get_parents(100), vec![138875005]);
+        }
+    }
 }
@@ -0,0 +1,584 @@
+//! Backend-agnostic terminology lookups.
+//!
+//! [`TerminologyStore`] is the query surface the service and query engines
+//! are written against. [`SnomedStore`], [`CompactStore`] and, with the
+//! `mmap` feature, [`MappedStore`](crate::MappedStore) implement it, so the
+//! backend can be chosen at startup and used as `Arc<dyn TerminologyStore>`.
+
+use snomed_types::{
+    InactivationReason, MrcmAttributeDomain, MrcmAttributeRange, MrcmDomain, Rf2Concept,
+    Rf2Description, Rf2Relationship, SctId, SimpleMapRefsetMember,
+};
+use thiserror::Error;
+
+use crate::compact::CompactStore;
+#[cfg(feature = "mmap")]
+use crate::mapped_store::MappedStore;
+use crate::store::SnomedStore;
+use crate::types::Hierarchy;
+
+/// A lookup the backend holds no data for.
+///
+/// Returned instead of an empty answer, so callers can tell "not a member"
+/// from "this backend cannot say".
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("{backend} does not support {lookup}")]
+pub struct Unsupported {
+    /// The backend that was asked (e.g. "CompactStore").
+    pub backend: &'static str,
+    /// The lookup that was asked for (e.g. "members").
+    pub lookup: &'static str,
+}
+
+/// Concept, description, relationship, hierarchy and MRCM lookups over a
+/// loaded release.
+///
+/// Results are owned, so a backend may read them from memory, a mapped
+/// file, a database or over the network. Backends are shared between
+/// request handlers and must be `Send + Sync`.
+///
+/// MRCM and reference set lookups fail with [`Unsupported`] unless the
+/// backend overrides them; only [`SnomedStore`] holds that data.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use snomed_loader::{CompactStore, TerminologyStore};
+///
+/// let store: Arc<dyn TerminologyStore> = if compact {
+///     Arc::new(CompactStore::from_store(&store)?)
+/// } else {
+///     Arc::new(store)
+/// };
+///
+/// let parents = store.get_parents(73211009);
+/// let fsn = store.get_fsn(73211009).map(|d| d.term);
+/// ```
+pub trait TerminologyStore: Send + Sync {
+    // Concepts
+
+    /// Gets a concept by its ID.
+    fn get_concept(&self, id: SctId) -> Option<Rf2Concept>;
+
+    /// Returns true if a concept exists in the store.
+    fn has_concept(&self, id: SctId) -> bool {
+        self.get_concept(id).is_some()
+    }
+
+    /// Returns an iterator over all concepts.
+    fn concepts(&self) -> Box<dyn Iterator<Item = Rf2Concept> + '_>;
+
+    /// Returns the number of concepts in the store.
+    fn concept_count(&self) -> usize;
+
+    // Descriptions
+
+    /// Gets all descriptions for a concept.
+    fn get_descriptions(&self, concept_id: SctId) -> Vec<Rf2Description>;
+
+    /// Gets the FSN (Fully Specified Name) for a concept.
+    fn get_fsn(&self, concept_id: SctId) -> Option<Rf2Description> {
+        self.get_descriptions(concept_id)
+            .into_iter()
+            .find(|d| d.is_fsn())
+    }
+
+    /// Gets the text definitions of a concept.
+    fn get_text_definitions(&self, concept_id: SctId) -> Vec<Rf2Description> {
+        let mut descriptions = self.get_descriptions(concept_id);
+        descriptions.retain(|d| d.is_definition());
+        descriptions
+    }
+
+    /// Returns the number of descriptions in the store.
+    fn description_count(&self) -> usize;
+
+    // Relationships
+
+    /// Gets relationships where this concept is the source.
+    fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship>;
+
+    /// Gets relationships where this concept is the destination.
+    fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship>;
+
+    /// Gets stated relationships where this concept is the source.
+    fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship>;
+
+    /// Returns the number of relationships in the store.
+    fn relationship_count(&self) -> usize;
+
+    // Hierarchy
+
+    /// Gets parent concepts (via IS_A relationship) in the given hierarchy.
+    fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId>;
+
+    /// Gets child concepts (via IS_A relationship) in the given hierarchy.
+    fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId>;
+
+    /// Gets parent concepts (via IS_A relationship) in the inferred hierarchy.
+    fn get_parents(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_parents_in(concept_id, Hierarchy::Inferred)
+    }
+
+    /// Gets child concepts (via IS_A relationship) in the inferred hierarchy.
+    fn get_children(&self, concept_id: SctId) -> Vec<SctId> {
+        self.get_children_in(concept_id, Hierarchy::Inferred)
+    }
+
+    // MRCM
+
+    /// Gets the MRCM domains that apply to a concept.
+    fn get_mrcm_domains(&self, _concept_id: SctId) -> Result<Vec<MrcmDomain>, Unsupported> {
+        unsupported::<Self, _>("MRCM")
+    }
+
+    /// Gets the MRCM domain rules of an attribute.
+    fn get_mrcm_attribute_domains(
+        &self,
+        _attribute_id: SctId,
+    ) -> Result<Vec<MrcmAttributeDomain>, Unsupported> {
+        unsupported::<Self, _>("MRCM")
+    }
+
+    /// Gets the MRCM range rules of an attribute.
+    fn get_mrcm_attribute_ranges(
+        &self,
+        _attribute_id: SctId,
+    ) -> Result<Vec<MrcmAttributeRange>, Unsupported> {
+        unsupported::<Self, _>("MRCM")
+    }
+
+    // Reference sets
+
+    /// Gets the reason a concept or description was inactivated.
+    fn get_inactivation_reason(
+        &self,
+        _component_id: SctId,
+    ) -> Result<Option<InactivationReason>, Unsupported> {
+        unsupported::<Self, _>("inactivation reasons")
+    }
+
+    /// Resolves a concept id to the active concepts that currently carry its meaning.
+    fn resolve_active(&self, _id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        unsupported::<Self, _>("historical associations")
+    }
+
+    /// Returns true if a component is an active member of a reference set.
+    fn is_member(&self, _refset_id: SctId, _component_id: SctId) -> Result<bool, Unsupported> {
+        unsupported::<Self, _>("reference set membership")
+    }
+
+    /// Gets the components that are active members of a reference set, sorted by id.
+    fn members(&self, _refset_id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        unsupported::<Self, _>("reference set membership")
+    }
+
+    /// Gets the reference sets a component is an active member of, sorted by id.
+    fn refsets_for(&self, _component_id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        unsupported::<Self, _>("reference set membership")
+    }
+
+    /// Gets all active simple map rows for a concept (concept to codes).
+    fn get_simple_map(
+        &self,
+        _concept_id: SctId,
+    ) -> Result<Vec<SimpleMapRefsetMember>, Unsupported> {
+        unsupported::<Self, _>("simple maps")
+    }
+
+    /// Gets the concepts mapped to an external code (code to concepts), sorted by id.
+    ///
+    /// A `refset_id` of `None` searches every simple map reference set.
+    fn reverse_map(
+        &self,
+        _refset_id: Option<SctId>,
+        _map_target: &str,
+    ) -> Result<Vec<SctId>, Unsupported> {
+        unsupported::<Self, _>("simple maps")
+    }
+}
+
+/// Fails a lookup that backend `S` holds no data for.
+fn unsupported<S: ?Sized, T>(lookup: &'static str) -> Result<T, Unsupported> {
+    let backend = std::any::type_name::<S>();
+    Err(Unsupported {
+        backend: backend.rsplit("::").next().unwrap_or(backend),
+        lookup,
+    })
+}
+
+impl TerminologyStore for SnomedStore {
+    fn get_concept(&self, id: SctId) -> Option<Rf2Concept> {
+        SnomedStore::get_concept(self, id).cloned()
+    }
+
+    fn has_concept(&self, id: SctId) -> bool {
+        SnomedStore::has_concept(self, id)
+    }
+
+    fn concepts(&self) -> Box<dyn Iterator<Item = Rf2Concept> + '_> {
+        Box::new(SnomedStore::concepts(self).cloned())
+    }
+
+    fn concept_count(&self) -> usize {
+        SnomedStore::concept_count(self)
+    }
+
+    fn get_descriptions(&self, concept_id: SctId) -> Vec<Rf2Description> {
+        SnomedStore::get_descriptions(self, concept_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn get_fsn(&self, concept_id: SctId) -> Option<Rf2Description> {
+        SnomedStore::get_fsn(self, concept_id).cloned()
+    }
+
+    fn get_text_definitions(&self, concept_id: SctId) -> Vec<Rf2Description> {
+        SnomedStore::get_text_definitions(self, concept_id)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn description_count(&self) -> usize {
+        SnomedStore::description_count(self)
+    }
+
+    fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        SnomedStore::get_outgoing_relationships(self, source_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship> {
+        SnomedStore::get_incoming_relationships(self, destination_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        SnomedStore::get_stated_relationships(self, source_id).to_vec()
+    }
+
+    fn relationship_count(&self) -> usize {
+        SnomedStore::relationship_count(self)
+    }
+
+    fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        SnomedStore::get_parents_in(self, concept_id, hierarchy)
+    }
+
+    fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        SnomedStore::get_children_in(self, concept_id, hierarchy)
+    }
+
+    /// Fails if the store was loaded without MRCM.
+    fn get_mrcm_domains(&self, concept_id: SctId) -> Result<Vec<MrcmDomain>, Unsupported> {
+        let Some(mrcm) = self.get_mrcm() else {
+            return unsupported::<Self, _>("MRCM");
+        };
+        Ok(mrcm
+            .get_domains_for_concept(concept_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Fails if the store was loaded without MRCM.
+    fn get_mrcm_attribute_domains(
+        &self,
+        attribute_id: SctId,
+    ) -> Result<Vec<MrcmAttributeDomain>, Unsupported> {
+        let Some(mrcm) = self.get_mrcm() else {
+            return unsupported::<Self, _>("MRCM");
+        };
+        Ok(mrcm
+            .get_attribute_domains(attribute_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Fails if the store was loaded without MRCM.
+    fn get_mrcm_attribute_ranges(
+        &self,
+        attribute_id: SctId,
+    ) -> Result<Vec<MrcmAttributeRange>, Unsupported> {
+        let Some(mrcm) = self.get_mrcm() else {
+            return unsupported::<Self, _>("MRCM");
+        };
+        Ok(mrcm
+            .get_attribute_range(attribute_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn get_inactivation_reason(
+        &self,
+        component_id: SctId,
+    ) -> Result<Option<InactivationReason>, Unsupported> {
+        Ok(SnomedStore::get_inactivation_reason(self, component_id))
+    }
+
+    fn resolve_active(&self, id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        Ok(SnomedStore::resolve_active(self, id))
+    }
+
+    fn is_member(&self, refset_id: SctId, component_id: SctId) -> Result<bool, Unsupported> {
+        Ok(SnomedStore::is_member(self, refset_id, component_id))
+    }
+
+    fn members(&self, refset_id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        Ok(SnomedStore::members(self, refset_id))
+    }
+
+    fn refsets_for(&self, component_id: SctId) -> Result<Vec<SctId>, Unsupported> {
+        Ok(SnomedStore::refsets_for(self, component_id))
+    }
+
+    fn get_simple_map(&self, concept_id: SctId) -> Result<Vec<SimpleMapRefsetMember>, Unsupported> {
+        Ok(SnomedStore::get_simple_map(self, concept_id).to_vec())
+    }
+
+    fn reverse_map(
+        &self,
+        refset_id: Option<SctId>,
+        map_target: &str,
+    ) -> Result<Vec<SctId>, Unsupported> {
+        Ok(SnomedStore::reverse_map(self, refset_id, map_target))
+    }
+}
+
+impl TerminologyStore for CompactStore {
+    fn get_concept(&self, id: SctId) -> Option<Rf2Concept> {
+        CompactStore::get_concept(self, id)
+    }
+
+    fn has_concept(&self, id: SctId) -> bool {
+        CompactStore::has_concept(self, id)
+    }
+
+    fn concepts(&self) -> Box<dyn Iterator<Item = Rf2Concept> + '_> {
+        Box::new(CompactStore::concepts(self))
+    }
+
+    fn concept_count(&self) -> usize {
+        CompactStore::concept_count(self)
+    }
+
+    fn get_descriptions(&self, concept_id: SctId) -> Vec<Rf2Description> {
+        CompactStore::get_descriptions(self, concept_id)
+            .iter()
+            .map(|d| d.to_description())
+            .collect()
+    }
+
+    fn get_fsn(&self, concept_id: SctId) -> Option<Rf2Description> {
+        CompactStore::get_fsn(self, concept_id).map(|d| d.to_description())
+    }
+
+    fn description_count(&self) -> usize {
+        CompactStore::description_count(self)
+    }
+
+    fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        CompactStore::get_outgoing_relationships(self, source_id)
+    }
+
+    fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship> {
+        CompactStore::get_incoming_relationships(self, destination_id)
+    }
+
+    fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        CompactStore::get_stated_relationships(self, source_id)
+    }
+
+    fn relationship_count(&self) -> usize {
+        CompactStore::relationship_count(self)
+    }
+
+    fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        CompactStore::get_parents_in(self, concept_id, hierarchy)
+    }
+
+    fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        CompactStore::get_children_in(self, concept_id, hierarchy)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl TerminologyStore for MappedStore {
+    fn get_concept(&self, id: SctId) -> Option<Rf2Concept> {
+        MappedStore::get_concept(self, id)
+    }
+
+    fn has_concept(&self, id: SctId) -> bool {
+        MappedStore::has_concept(self, id)
+    }
+
+    fn concepts(&self) -> Box<dyn Iterator<Item = Rf2Concept> + '_> {
+        Box::new(MappedStore::concepts(self))
+    }
+
+    fn concept_count(&self) -> usize {
+        MappedStore::concept_count(self)
+    }
+
+    fn get_descriptions(&self, concept_id: SctId) -> Vec<Rf2Description> {
+        MappedStore::get_descriptions(self, concept_id)
+            .iter()
+            .map(|d| d.to_description())
+            .collect()
+    }
+
+    fn get_fsn(&self, concept_id: SctId) -> Option<Rf2Description> {
+        MappedStore::get_fsn(self, concept_id).map(|d| d.to_description())
+    }
+
+    fn description_count(&self) -> usize {
+        MappedStore::description_count(self)
+    }
+
+    fn get_outgoing_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        MappedStore::get_outgoing_relationships(self, source_id)
+    }
+
+    fn get_incoming_relationships(&self, destination_id: SctId) -> Vec<Rf2Relationship> {
+        MappedStore::get_incoming_relationships(self, destination_id)
+    }
+
+    fn get_stated_relationships(&self, source_id: SctId) -> Vec<Rf2Relationship> {
+        MappedStore::get_stated_relationships(self, source_id)
+    }
+
+    fn relationship_count(&self) -> usize {
+        MappedStore::relationship_count(self)
+    }
+
+    fn get_parents_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        MappedStore::get_parents_in(self, concept_id, hierarchy)
+    }
+
+    fn get_children_in(&self, concept_id: SctId, hierarchy: Hierarchy) -> Vec<SctId> {
+        MappedStore::get_children_in(self, concept_id, hierarchy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_types::DescriptionType;
+
+    fn concept(id: SctId, active: bool) -> Rf2Concept {
+        Rf2Concept {
+            id,
+            effective_time: 20020131,
+            active,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }
+    }
+
+    fn make_store() -> SnomedStore {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([concept(100, true), concept(200, true), concept(300, false)]);
+        store.insert_descriptions([Rf2Description {
+            id: 1,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            concept_id: 200,
+            language_code: "en".to_string(),
+            type_id: DescriptionType::FSN_ID,
+            term: "Child (finding)".to_string(),
+            case_significance_id: 900000000000448009,
+        }]);
+        store.insert_relationships([Rf2Relationship {
+            id: 2,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            source_id: 200,
+            destination_id: 100,
+            relationship_group: 0,
+            type_id: Rf2Relationship::IS_A_TYPE_ID,
+            characteristic_type_id: 900000000000011006,
+            modifier_id: 900000000000451002,
+        }]);
+        store
+    }
+
+    /// Lookups every backend must answer alike.
+    fn check_backend(store: &dyn TerminologyStore) {
+        assert_eq!(store.concept_count(), 3);
+        assert_eq!(store.concepts().count(), 3);
+        assert!(store.has_concept(100));
+        assert!(!store.has_concept(400));
+        assert_eq!(store.get_concept(300).map(|c| c.active), Some(false));
+
+        assert_eq!(store.description_count(), 1);
+        assert_eq!(store.get_fsn(200).unwrap().term, "Child (finding)");
+        assert!(store.get_text_definitions(200).is_empty());
+        assert!(store.get_descriptions(100).is_empty());
+
+        assert_eq!(store.relationship_count(), 1);
+        assert_eq!(store.get_outgoing_relationships(200)[0].destination_id, 100);
+        assert_eq!(store.get_incoming_relationships(100)[0].source_id, 200);
+        assert!(store.get_stated_relationships(200).is_empty());
+        assert_eq!(store.get_parents(200), vec![100]);
+        assert_eq!(store.get_children(100), vec![200]);
+        assert!(store.get_parents_in(200, Hierarchy::Stated).is_empty());
+    }
+
+    #[test]
+    fn test_backends_answer_alike() {
+        let store = make_store();
+        check_backend(&store);
+        check_backend(&CompactStore::from_store(&store).unwrap());
+
+        #[cfg(feature = "mmap")]
+        {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("snomed.store");
+            let source = crate::types::SnapshotSource::default();
+            MappedStore::write(&store, &path, &source).unwrap();
+            check_backend(&MappedStore::open(&path, &source).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_snomed_store_answers_reference_data_lookups() {
+        let store = make_store();
+        let backend: &dyn TerminologyStore = &store;
+
+        assert_eq!(backend.resolve_active(200), Ok(vec![200]));
+        assert_eq!(backend.resolve_active(300), Ok(vec![]));
+        assert_eq!(backend.get_inactivation_reason(300), Ok(None));
+        assert_eq!(backend.is_member(446609009, 100), Ok(false));
+        assert_eq!(backend.members(446609009), Ok(vec![]));
+        assert_eq!(backend.reverse_map(None, "A00"), Ok(vec![]));
+
+        // MRCM was not loaded
+        let error = backend.get_mrcm_domains(200).unwrap_err();
+        assert_eq!(error.backend, "SnomedStore");
+        assert_eq!(error.lookup, "MRCM");
+    }
+
+    #[test]
+    fn test_compact_store_reports_missing_reference_data() {
+        let compact = CompactStore::from_store(&make_store()).unwrap();
+        let backend: &dyn TerminologyStore = &compact;
+
+        let error = backend.is_member(446609009, 100).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "CompactStore does not support reference set membership"
+        );
+        assert!(backend.get_mrcm_attribute_ranges(363698007).is_err());
+        assert!(backend.get_inactivation_reason(300).is_err());
+        assert!(backend.resolve_active(300).is_err());
+        assert!(backend.refsets_for(100).is_err());
+        assert!(backend.get_simple_map(100).is_err());
+        assert!(backend.reverse_map(None, "A00").is_err());
+    }
+}
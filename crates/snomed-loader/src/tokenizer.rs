@@ -0,0 +1,190 @@
+//! RF2 line tokenizer.
+//!
+//! RF2 files are UTF-8 and tab-separated with no quoting: a `"` is part of
+//! the field it appears in (e.g. the inch mark in `Tape 1" wide`). Every
+//! loader splits lines with [`Rf2Line`], so the streaming, parallel and
+//! memory-mapped paths agree on every row:
+//!
+//! - A UTF-8 byte order mark at the start of the line is dropped.
+//! - The line terminator (`\n`, `\r\n` or a trailing `\r`) is dropped.
+//! - Fields are split on every tab; quotes and backslashes are data.
+//! - Trailing tabs produce extra empty fields, which fixed-column records ignore.
+//! - A line holding only whitespace is blank and is skipped.
+
+use std::str::Split;
+
+use snomed_types::SctId;
+
+use crate::parser::parse;
+use crate::types::{Rf2Error, Rf2Result};
+
+/// One line of an RF2 file, without its terminator.
+///
+/// # Example
+///
+/// ```
+/// use snomed_loader::Rf2Line;
+///
+/// let line = Rf2Line::new("1\t\"Tape\" 1\" wide\t\r\n");
+/// let fields: Vec<&str> = line.fields().collect();
+/// assert_eq!(fields, vec!["1", "\"Tape\" 1\" wide", ""]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rf2Line<'a> {
+    text: &'a str,
+}
+
+impl<'a> Rf2Line<'a> {
+    /// Wraps a raw line, dropping a leading BOM and the line terminator.
+    pub fn new(raw: &'a str) -> Self {
+        let text = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        Self { text }
+    }
+
+    /// Returns the line text.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns true if the line holds only whitespace.
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Returns the tab-separated fields of the line.
+    pub fn fields(&self) -> Split<'a, char> {
+        self.text.split('\t')
+    }
+
+    /// Splits the line into fields, requiring at least `expected` of them.
+    ///
+    /// # Errors
+    /// Returns [`Rf2Error::FieldCount`] if the line has fewer fields.
+    pub fn split(&self, expected: usize) -> Rf2Result<Vec<&'a str>> {
+        let fields: Vec<&str> = self.fields().collect();
+        if fields.len() < expected {
+            return Err(Rf2Error::FieldCount {
+                expected,
+                found: fields.len(),
+            });
+        }
+        Ok(fields)
+    }
+}
+
+/// The tab-separated fields of a line, read in column order.
+///
+/// Errors from reading a field, including a missing one, are tagged with
+/// the field's column so that row errors can name it.
+pub struct Fields<'a> {
+    split: Split<'a, char>,
+    columns: &'static [&'static str],
+    index: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// Reads the fields of `line`, whose columns are named by `columns`.
+    pub fn new(line: Rf2Line<'a>, columns: &'static [&'static str]) -> Self {
+        Self {
+            split: line.fields(),
+            columns,
+            index: 0,
+        }
+    }
+
+    /// Parses the next field with `parse`.
+    ///
+    /// # Errors
+    /// Returns [`Rf2Error::MissingColumn`] if the line is short, or the
+    /// error from `parse`, tagged with the column as [`Rf2Error::Field`].
+    pub fn parse<T>(&mut self, parse: impl FnOnce(&'a str) -> Rf2Result<T>) -> Rf2Result<T> {
+        let column = self.columns.get(self.index).copied().unwrap_or("");
+        self.index += 1;
+        self.split
+            .next()
+            .ok_or_else(|| Rf2Error::MissingColumn {
+                column: column.to_string(),
+            })
+            .and_then(parse)
+            .map_err(|error| error.in_column(column))
+    }
+
+    /// Returns the next field, or [`Rf2Error::MissingColumn`] if the line is short.
+    pub fn next_field(&mut self) -> Rf2Result<&'a str> {
+        self.parse(Ok)
+    }
+
+    /// Parses the next field as an SCTID.
+    pub fn sctid(&mut self) -> Rf2Result<SctId> {
+        self.parse(parse::sctid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Conformance corpus: raw line and the fields every loader must see.
+    ///
+    /// `None` marks a blank line, which loaders skip.
+    const CORPUS: &[(&str, Option<&[&str]>)] = &[
+        // Plain rows, with and without a terminator
+        ("1\t2\t3", Some(&["1", "2", "3"])),
+        ("1\t2\t3\n", Some(&["1", "2", "3"])),
+        // CRLF and a stray carriage return at end of file
+        ("1\t2\t3\r\n", Some(&["1", "2", "3"])),
+        ("1\t2\t3\r", Some(&["1", "2", "3"])),
+        // Byte order mark on the first line
+        ("\u{feff}id\tterm\r\n", Some(&["id", "term"])),
+        // Quotes are data, never field delimiters
+        ("1\tTape 1\" wide\t3", Some(&["1", "Tape 1\" wide", "3"])),
+        ("1\t\"quoted\"\t3", Some(&["1", "\"quoted\"", "3"])),
+        ("1\t\"unterminated\t3", Some(&["1", "\"unterminated", "3"])),
+        ("1\t\"\"\t3", Some(&["1", "\"\"", "3"])),
+        ("1\t\"#500\"\t3", Some(&["1", "\"#500\"", "3"])),
+        ("1\tC:\\path\\\t3", Some(&["1", "C:\\path\\", "3"])),
+        // Trailing tabs produce empty fields
+        ("1\t2\t", Some(&["1", "2", ""])),
+        ("1\t2\t\t\r\n", Some(&["1", "2", "", ""])),
+        // Empty fields in the middle and surrounding spaces are kept
+        ("1\t\t3", Some(&["1", "", "3"])),
+        ("1\t term \t3", Some(&["1", " term ", "3"])),
+        // Carriage returns inside a line are data
+        ("1\ta\rb\t3", Some(&["1", "a\rb", "3"])),
+        // Blank lines
+        ("", None),
+        ("\n", None),
+        ("\r\n", None),
+        ("  \t \r\n", None),
+    ];
+
+    #[test]
+    fn test_conformance_corpus() {
+        for (raw, expected) in CORPUS {
+            let line = Rf2Line::new(raw);
+            match expected {
+                Some(fields) => {
+                    assert!(!line.is_blank(), "{:?} is not blank", raw);
+                    assert_eq!(line.fields().collect::<Vec<_>>(), *fields, "{:?}", raw);
+                }
+                None => assert!(line.is_blank(), "{:?} is blank", raw),
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_checks_field_count() {
+        let line = Rf2Line::new("1\t2\t\r\n");
+        assert_eq!(line.split(3).unwrap(), vec!["1", "2", ""]);
+        assert_eq!(line.split(2).unwrap().len(), 3);
+        assert!(matches!(
+            line.split(4),
+            Err(Rf2Error::FieldCount {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Parser-specific types for RF2 file processing.
 
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Errors that can occur during RF2 file parsing.
@@ -49,6 +50,13 @@ pub enum Rf2Error {
         value: String,
     },
 
+    /// Invalid concrete relationship value.
+    #[error("Invalid concrete value: {value} (expected #number or \"string\")")]
+    InvalidConcreteValue {
+        /// The invalid value.
+        value: String,
+    },
+
     /// File not found.
     #[error("File not found: {path}")]
     FileNotFound {
@@ -72,6 +80,51 @@ pub enum Rf2Error {
         directory: String,
     },
 
+    /// File name does not follow the expected RF2 naming pattern.
+    #[error("File name does not follow the RF2 reference set naming pattern: {path}")]
+    InvalidFileName {
+        /// The offending path.
+        path: String,
+    },
+
+    /// Reference set file's columns are not those of the member type it is read as.
+    #[error("Reference set file {path} has columns '{found}', expected '{expected}'")]
+    UnexpectedRefsetFields {
+        /// The offending path (empty when parsing from a reader).
+        path: String,
+        /// Leading column type letters the member type reads (e.g. "iissscc").
+        expected: String,
+        /// Column type letters from the file name.
+        found: String,
+    },
+
+    /// Release archive could not be read.
+    #[error("Cannot read release archive {path}: {message}")]
+    Archive {
+        /// The archive (or archive entry) path.
+        path: String,
+        /// Description of the failure.
+        message: String,
+    },
+
+    /// Store snapshot could not be read.
+    #[error("Invalid store snapshot {path}: {message}")]
+    InvalidSnapshot {
+        /// The snapshot path.
+        path: String,
+        /// Description of the failure.
+        message: String,
+    },
+
+    /// The store already holds as many editions as [`EditionId`](crate::EditionId) can number.
+    #[error("Cannot load edition {name}: the store already holds {loaded} editions")]
+    TooManyEditions {
+        /// Name of the edition that was being loaded.
+        name: String,
+        /// Number of editions already loaded.
+        loaded: usize,
+    },
+
     /// Invalid header - column count mismatch.
     #[error("Invalid header: expected {expected} columns, found {found}")]
     InvalidHeader {
@@ -91,11 +144,83 @@ pub enum Rf2Error {
         /// Found column name.
         found: String,
     },
+
+    /// Row has fewer fields than the file's columns.
+    #[error("Invalid row: expected {expected} fields, found {found}")]
+    FieldCount {
+        /// Expected field count.
+        expected: usize,
+        /// Found field count.
+        found: usize,
+    },
+
+    /// A field failed to parse; carries the column it was read from.
+    #[error("{error} (column {column})")]
+    Field {
+        /// Name of the column.
+        column: &'static str,
+        /// What was wrong with the field.
+        error: Box<Rf2Error>,
+    },
+
+    /// A row failed to parse; carries the file, line and column.
+    #[error("{0}")]
+    Row(Box<RowError>),
+}
+
+impl Rf2Error {
+    /// Tags an error from reading a field with the field's column.
+    pub(crate) fn in_column(self, column: &'static str) -> Self {
+        Self::Field {
+            column,
+            error: Box::new(self),
+        }
+    }
 }
 
 /// Result type for RF2 operations.
 pub type Rf2Result<T> = Result<T, Rf2Error>;
 
+/// A row that could not be parsed, with the file, line and column it came from.
+#[derive(Debug)]
+pub struct RowError {
+    /// File the row was read from (empty when parsing from a reader).
+    pub path: PathBuf,
+    /// Line number in the file (the header is line 1).
+    pub line: u64,
+    /// Column holding the rejected value, when known.
+    pub column: Option<&'static str>,
+    /// What was wrong with the row.
+    pub error: Rf2Error,
+}
+
+impl RowError {
+    /// Creates a row error, taking the column from an [`Rf2Error::Field`] error.
+    pub(crate) fn new(path: &Path, line: u64, error: Rf2Error) -> Self {
+        let (column, error) = match error {
+            Rf2Error::Field { column, error } => (Some(column), *error),
+            error => (None, error),
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            line,
+            column,
+            error,
+        }
+    }
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.display(), self.line, self.error)?;
+        if let Some(column) = self.column {
+            write!(f, " (column {})", column)?;
+        }
+        Ok(())
+    }
+}
+
 /// Configuration for RF2 parsing.
 #[derive(Debug, Clone)]
 pub struct Rf2Config {
@@ -156,6 +281,15 @@ impl DescriptionConfig {
             type_ids: vec![900000000000003001], // FSN only
         }
     }
+
+    /// Creates a config for English text definitions.
+    pub fn text_definitions() -> Self {
+        Self {
+            base: Rf2Config::default(),
+            language_codes: vec!["en".to_string()],
+            type_ids: vec![900000000000550004], // Definition
+        }
+    }
 }
 
 /// Configuration specific to relationship parsing.
@@ -179,6 +313,15 @@ impl RelationshipConfig {
         }
     }
 
+    /// Creates a config for stated relationships only.
+    pub fn stated_only() -> Self {
+        Self {
+            base: Rf2Config::default(),
+            type_ids: vec![],
+            characteristic_type_ids: vec![900000000000010007], // Stated
+        }
+    }
+
     /// Creates a config for IS_A relationships only.
     pub fn is_a_only() -> Self {
         Self {
@@ -215,25 +358,316 @@ impl ParseStats {
     }
 }
 
+/// Parse statistics for one file of a load.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    /// The file that was read.
+    pub path: PathBuf,
+    /// Row counts and timing for the file.
+    pub stats: ParseStats,
+}
+
+/// Outcome of loading a release with [`SnomedStore::load`](crate::SnomedStore::load),
+/// [`apply_delta`](crate::SnomedStore::apply_delta) and the other file set loaders.
+///
+/// Lists every component and reference set file read with its
+/// [`ParseStats`], and every row that was skipped because it could not be
+/// parsed.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = store.load(&files, &LoadOptions::default())?;
+/// for file in &report.files {
+///     println!("{}: {} rows", file.path.display(), file.stats.filtered_records);
+/// }
+/// for error in &report.errors {
+///     eprintln!("skipped {}", error);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// Files read, in load order.
+    pub files: Vec<FileReport>,
+    /// Rows that could not be parsed.
+    pub errors: Vec<RowError>,
+    /// Changes made by rows merged into the existing store contents: Delta
+    /// and edition rows, and reference set members.
+    pub changes: DeltaStats,
+}
+
+impl LoadReport {
+    /// Returns true if no row failed to parse.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Gets the statistics of a file read by the load.
+    pub fn stats(&self, path: &Path) -> Option<&ParseStats> {
+        self.files
+            .iter()
+            .find(|file| file.path == path)
+            .map(|file| &file.stats)
+    }
+
+    /// Returns the number of rows loaded across all files.
+    pub fn records_loaded(&self) -> usize {
+        self.files
+            .iter()
+            .map(|file| file.stats.filtered_records)
+            .sum()
+    }
+
+    /// Records a file's statistics and row errors.
+    ///
+    /// In `strict` mode the first row error is returned instead.
+    pub(crate) fn record_file(
+        &mut self,
+        path: &Path,
+        stats: ParseStats,
+        errors: Vec<RowError>,
+        strict: bool,
+    ) -> Rf2Result<()> {
+        if strict {
+            if let Some(first) = errors.into_iter().next() {
+                return Err(Rf2Error::Row(Box::new(first)));
+            }
+        } else {
+            self.errors.extend(errors);
+        }
+
+        self.files.push(FileReport {
+            path: path.to_path_buf(),
+            stats,
+        });
+        Ok(())
+    }
+}
+
+/// RF2 release type, as used in directory and file names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReleaseType {
+    /// Snapshot release - the current version of every component.
+    #[default]
+    Snapshot,
+    /// Delta release - only the rows changed since the previous release.
+    Delta,
+    /// Full release - every version of every component ever released.
+    Full,
+}
+
+impl ReleaseType {
+    /// Returns the name used for this release type in RF2 paths (e.g. `Snapshot`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Snapshot => "Snapshot",
+            Self::Delta => "Delta",
+            Self::Full => "Full",
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Options for loading a release with [`SnomedStore::load`](crate::SnomedStore::load).
+///
+/// Holds the filter for each file type and which optional files are read.
+/// Optional files set to `None` (or disabled) are skipped even if they were
+/// discovered.
+///
+/// # Example
+///
+/// ```ignore
+/// use snomed_loader::{LoadOptions, SnomedStore};
+///
+/// // Everything, including inactive rows and all languages
+/// let mut store = SnomedStore::new();
+/// store.load(&files, &LoadOptions::full_content())?;
+///
+/// // Active English terms and the inferred hierarchy only, without rayon
+/// let options = LoadOptions {
+///     parallel: false,
+///     ..LoadOptions::minimal_runtime()
+/// };
+/// store.load(&files, &options)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// Filter for the concept file.
+    pub concepts: Rf2Config,
+    /// Filter for the description file.
+    pub descriptions: DescriptionConfig,
+    /// Filter for the (inferred) relationship file.
+    pub relationships: RelationshipConfig,
+    /// Filter for the concrete value relationship file; `None` skips it.
+    pub concrete_relationships: Option<RelationshipConfig>,
+    /// Filter for the text definition file; `None` skips it.
+    pub text_definitions: Option<DescriptionConfig>,
+    /// Filter for the stated relationship file; `None` skips it.
+    pub stated_relationships: Option<RelationshipConfig>,
+    /// Whether to load reference sets (language, association, maps, OWL, generic).
+    pub include_refsets: bool,
+    /// Whether to load the MRCM reference sets.
+    pub include_mrcm: bool,
+    /// Whether to parse the core files in parallel.
+    ///
+    /// Ignored without the `parallel` feature.
+    pub parallel: bool,
+    /// Whether to read the core files through a memory map, allocating only
+    /// for the rows that are kept.
+    ///
+    /// Takes precedence over `parallel`. Ignored without the `mmap` feature
+    /// and for files inside a ZIP archive, which are streamed.
+    pub mmap: bool,
+    /// Whether to fail the load on the first malformed row instead of
+    /// skipping it and recording it in the [`LoadReport`].
+    pub strict: bool,
+}
+
+impl Default for LoadOptions {
+    /// Active English content with the inferred and stated views and all
+    /// reference sets; MRCM is not loaded.
+    fn default() -> Self {
+        Self {
+            concepts: Rf2Config::default(),
+            descriptions: DescriptionConfig::english_terms(),
+            relationships: RelationshipConfig::inferred_only(),
+            concrete_relationships: Some(RelationshipConfig::inferred_only()),
+            text_definitions: Some(DescriptionConfig::text_definitions()),
+            stated_relationships: Some(RelationshipConfig::stated_only()),
+            include_refsets: true,
+            include_mrcm: false,
+            parallel: cfg!(feature = "parallel"),
+            mmap: false,
+            strict: false,
+        }
+    }
+}
+
+impl LoadOptions {
+    /// Creates options that load every row of every file: inactive
+    /// components, all languages and description types, all characteristic
+    /// types, reference sets and MRCM.
+    pub fn full_content() -> Self {
+        let all_rows = Rf2Config {
+            active_only: false,
+            ..Rf2Config::default()
+        };
+        let all_descriptions = DescriptionConfig {
+            base: all_rows.clone(),
+            language_codes: vec![],
+            type_ids: vec![],
+        };
+        let all_relationships = RelationshipConfig {
+            base: all_rows.clone(),
+            type_ids: vec![],
+            characteristic_type_ids: vec![],
+        };
+
+        Self {
+            concepts: all_rows,
+            descriptions: all_descriptions.clone(),
+            relationships: all_relationships.clone(),
+            concrete_relationships: Some(all_relationships.clone()),
+            text_definitions: Some(all_descriptions),
+            stated_relationships: Some(all_relationships),
+            include_refsets: true,
+            include_mrcm: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates options for the smallest useful runtime store: active
+    /// concepts, English FSNs and synonyms and the inferred relationships.
+    /// Optional files, reference sets and MRCM are skipped.
+    pub fn minimal_runtime() -> Self {
+        Self {
+            concrete_relationships: None,
+            text_definitions: None,
+            stated_relationships: None,
+            include_refsets: false,
+            include_mrcm: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Relationship view used for hierarchy queries.
+///
+/// Stated relationships are the definitions as authored; inferred ones are
+/// the classifier's output and the normal choice for navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Hierarchy {
+    /// Inferred relationships (`sct2_Relationship_*`).
+    #[default]
+    Inferred,
+    /// Stated relationships (`sct2_StatedRelationship_*`).
+    Stated,
+}
+
+/// Statistics from applying a Delta release to a store.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaStats {
+    /// Components inserted or replaced.
+    pub upserted: usize,
+    /// Components removed because their new version no longer passes the load filters.
+    pub removed: usize,
+    /// Rows ignored because the store already holds a newer version.
+    pub skipped: usize,
+}
+
+impl std::ops::AddAssign for DeltaStats {
+    fn add_assign(&mut self, other: Self) {
+        self.upserted += other.upserted;
+        self.removed += other.removed;
+        self.skipped += other.skipped;
+    }
+}
+
 /// Discovered RF2 files in a release directory.
 #[derive(Debug, Clone, Default)]
 pub struct Rf2Files {
+    /// Release type these files belong to.
+    pub release_type: ReleaseType,
     /// Path to concept file.
     pub concept_file: Option<PathBuf>,
     /// Path to description file.
     pub description_file: Option<PathBuf>,
     /// Path to relationship file.
     pub relationship_file: Option<PathBuf>,
+    /// Path to concrete value relationship file (e.g. drug strengths).
+    pub concrete_relationship_file: Option<PathBuf>,
     /// Path to stated relationship file (if separate).
     pub stated_relationship_file: Option<PathBuf>,
     /// Path to text definition file.
     pub text_definition_file: Option<PathBuf>,
+    /// Path to OWL expression reference set file (stated axioms).
+    pub owl_expression_file: Option<PathBuf>,
     /// Path to MRCM Domain reference set file.
     pub mrcm_domain: Option<PathBuf>,
     /// Path to MRCM Attribute Domain reference set file.
     pub mrcm_attribute_domain: Option<PathBuf>,
     /// Path to MRCM Attribute Range reference set file.
     pub mrcm_attribute_range: Option<PathBuf>,
+    /// Paths to language reference set files (one per language).
+    pub language_refset_files: Vec<PathBuf>,
+    /// Path to historical association reference set file.
+    pub association_refset_file: Option<PathBuf>,
+    /// Path to attribute value reference set file (inactivation indicators).
+    pub attribute_value_refset_file: Option<PathBuf>,
+    /// Paths to extended map reference set files (e.g. SNOMED CT to ICD-10).
+    pub extended_map_files: Vec<PathBuf>,
+    /// Paths to simple map reference set files (e.g. CTV3, ICD-O).
+    pub simple_map_files: Vec<PathBuf>,
+    /// Path to the reference set descriptor file.
+    pub refset_descriptor_file: Option<PathBuf>,
+    /// Paths to other content reference set files, loaded generically.
+    pub refset_files: Vec<PathBuf>,
     /// Release date extracted from filename (YYYYMMDD).
     pub release_date: Option<String>,
 }
@@ -251,6 +685,13 @@ impl Rf2Files {
             && self.relationship_file.is_some()
     }
 
+    /// Returns true if any of the MRCM reference set files is present.
+    pub fn has_mrcm_files(&self) -> bool {
+        self.mrcm_domain.is_some()
+            || self.mrcm_attribute_domain.is_some()
+            || self.mrcm_attribute_range.is_some()
+    }
+
     /// Returns a list of missing required files.
     pub fn missing_files(&self) -> Vec<&'static str> {
         let mut missing = Vec::new();
@@ -267,6 +708,46 @@ impl Rf2Files {
     }
 }
 
+/// The release and load options a store snapshot or mapped store was built from.
+///
+/// Recorded in every snapshot and mapped store and compared on load, so a
+/// store of another release, or of the same release loaded differently, is
+/// never served.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "snapshot", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotSource {
+    /// Release directory or archive the store was loaded from.
+    pub data_path: String,
+    /// Release date taken from the file names (YYYYMMDD).
+    pub release_date: Option<String>,
+    /// The load options that shaped the store, in a stable textual form
+    /// chosen by the caller (e.g. `"profile=full strict=false"`).
+    pub options: String,
+}
+
+impl SnapshotSource {
+    /// Describes a store loaded from `files`, discovered at `data_path`.
+    pub fn new(data_path: impl Into<String>, files: &Rf2Files, options: impl Into<String>) -> Self {
+        Self {
+            data_path: data_path.into(),
+            release_date: files.release_date.clone(),
+            options: options.into(),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {})",
+            self.data_path,
+            self.release_date.as_deref().unwrap_or("undated"),
+            self.options
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +773,75 @@ mod tests {
         assert_eq!(config.characteristic_type_ids, vec![900000000000011006]);
     }
 
+    #[test]
+    fn test_relationship_config_stated_only() {
+        let config = RelationshipConfig::stated_only();
+        assert!(config.type_ids.is_empty());
+        assert_eq!(config.characteristic_type_ids, vec![900000000000010007]);
+    }
+
+    #[test]
+    fn test_load_option_presets() {
+        let full = LoadOptions::full_content();
+        assert!(!full.concepts.active_only);
+        assert!(full.descriptions.language_codes.is_empty());
+        assert!(full.relationships.characteristic_type_ids.is_empty());
+        assert!(full.stated_relationships.is_some());
+        assert!(full.include_mrcm);
+
+        let minimal = LoadOptions::minimal_runtime();
+        assert!(minimal.concepts.active_only);
+        assert!(minimal.text_definitions.is_none());
+        assert!(minimal.stated_relationships.is_none());
+        assert!(!minimal.include_refsets);
+    }
+
+    #[test]
+    fn test_load_report_strict_mode() {
+        let row_error = || RowError {
+            path: PathBuf::from("sct2_Concept_Snapshot_INT_20250101.txt"),
+            line: 7,
+            column: None,
+            error: Rf2Error::InvalidSctId {
+                value: "abc".to_string(),
+            },
+        };
+
+        let mut report = LoadReport::default();
+        let path = Path::new("sct2_Concept_Snapshot_INT_20250101.txt");
+        report
+            .record_file(path, ParseStats::default(), vec![row_error()], false)
+            .unwrap();
+        assert!(!report.is_clean());
+        assert!(report.stats(path).is_some());
+
+        let err = report
+            .record_file(path, ParseStats::default(), vec![row_error()], true)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sct2_Concept_Snapshot_INT_20250101.txt:7: Invalid SCTID format: abc"
+        );
+    }
+
+    #[test]
+    fn test_row_error_takes_column_from_field_error() {
+        let error = RowError::new(
+            Path::new("concepts.txt"),
+            3,
+            Rf2Error::InvalidDate {
+                value: "2002013".to_string(),
+            }
+            .in_column("effectiveTime"),
+        );
+        assert!(matches!(error.error, Rf2Error::InvalidDate { .. }));
+        assert_eq!(error.column, Some("effectiveTime"));
+        assert_eq!(
+            error.to_string(),
+            "concepts.txt:3: Invalid date format: 2002013 (column effectiveTime)"
+        );
+    }
+
     #[test]
     fn test_parse_stats_filter_rate() {
         let stats = ParseStats {
@@ -302,6 +852,36 @@ mod tests {
         assert!((stats.filter_rate() - 75.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_release_type_names() {
+        assert_eq!(ReleaseType::default(), ReleaseType::Snapshot);
+        assert_eq!(ReleaseType::Delta.as_str(), "Delta");
+        assert_eq!(ReleaseType::Full.to_string(), "Full");
+    }
+
+    #[test]
+    fn test_delta_stats_add_assign() {
+        let mut total = DeltaStats::default();
+        total += DeltaStats {
+            upserted: 2,
+            removed: 1,
+            skipped: 0,
+        };
+        total += DeltaStats {
+            upserted: 1,
+            removed: 0,
+            skipped: 3,
+        };
+        assert_eq!(
+            total,
+            DeltaStats {
+                upserted: 3,
+                removed: 1,
+                skipped: 3
+            }
+        );
+    }
+
     #[test]
     fn test_rf2_files_missing() {
         let files = Rf2Files {
@@ -1,9 +1,12 @@
 //! SNOMED CT gRPC Server binary.
 
-use snomed_loader::{discover_rf2_files, SnomedStore};
+use snomed_loader::{
+    discover_rf2_files, discover_rf2_full_files, format_bytes, CompactStore, HistoryStore,
+    LoadOptions, MappedStore, Rf2Files, SnapshotSource, SnomedStore,
+};
 use snomed_service::proto::{
-    concept_service_server::ConceptServiceServer,
-    search_service_server::SearchServiceServer,
+    concept_service_server::ConceptServiceServer, map_service_server::MapServiceServer,
+    refset_service_server::RefsetServiceServer, search_service_server::SearchServiceServer,
 };
 use snomed_service::SnomedServer;
 use tonic::transport::Server;
@@ -23,7 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .init();
 
-    // Get data path from env or use default
+    // Get data path from env or use default (a release directory or its .zip archive)
     let data_path = std::env::var("SNOMED_DATA_PATH")
         .unwrap_or_else(|_| DEFAULT_DATA_PATH.to_string());
 
@@ -33,12 +36,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let files = discover_rf2_files(&data_path)?;
     tracing::info!("Discovered RF2 files: {:?}", files.release_date);
 
-    // Load data into store
-    let mut store = SnomedStore::new();
+    let (options, settings) = load_options();
+    // A snapshot or mapped store of another release or other load settings is rebuilt
+    let source = SnapshotSource::new(&data_path, &files, settings);
 
-    tracing::info!("Loading concepts...");
-    store.load_all(&files)?;
+    // Choose the store backend: "compact", "mapped" or the in-memory default
+    let backend = std::env::var("SNOMED_STORE_BACKEND").unwrap_or_default();
+    let mut server = match backend.as_str() {
+        "compact" => {
+            let compact = CompactStore::from_store(&load_store(&files, &options, &source)?)?;
+            tracing::info!(
+                "Built compact store ({})",
+                format_bytes(compact.estimated_memory_bytes())
+            );
+            SnomedServer::new(compact)
+        }
+        "mapped" => SnomedServer::new(open_mapped_store(&files, &options, &source)?),
+        _ => SnomedServer::new(load_store(&files, &options, &source)?),
+    };
 
+    let store = server.store();
     tracing::info!(
         "Loaded {} concepts, {} descriptions, {} relationships",
         store.concept_count(),
@@ -46,8 +63,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         store.relationship_count()
     );
 
-    // Create server
-    let server = SnomedServer::new(store);
+    // Optionally load Full release history for GetConceptHistory
+    if env_flag("SNOMED_LOAD_HISTORY") {
+        tracing::info!("Loading concept history from Full release...");
+        let full_files = discover_rf2_full_files(&data_path)?;
+        let history = HistoryStore::load(&full_files)?;
+        tracing::info!(
+            "Loaded {} versions for {} concepts",
+            history.version_count(),
+            history.concept_count()
+        );
+        server = server.with_history(history);
+    }
 
     // Get port from env or use default
     let port = std::env::var("SNOMED_PORT")
@@ -61,9 +88,117 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start gRPC server
     Server::builder()
         .add_service(ConceptServiceServer::new(server.clone()))
+        .add_service(MapServiceServer::new(server.clone()))
+        .add_service(RefsetServiceServer::new(server.clone()))
         .add_service(SearchServiceServer::new(server))
         .serve(addr)
         .await?;
 
     Ok(())
 }
+
+/// Loads the store from a snapshot when one is configured, else parses the RF2 release.
+fn load_store(
+    files: &Rf2Files,
+    options: &LoadOptions,
+    source: &SnapshotSource,
+) -> Result<SnomedStore, Box<dyn std::error::Error>> {
+    let snapshot_path = std::env::var("SNOMED_SNAPSHOT_PATH").ok();
+    if let Some(path) = &snapshot_path {
+        if let Some(store) = read_snapshot(path, source) {
+            return Ok(store);
+        }
+    }
+
+    let store = load_release(files, options)?;
+    // Write the snapshot so the next start skips RF2 parsing
+    if let Some(path) = &snapshot_path {
+        match store.save_snapshot(path, source) {
+            Ok(()) => tracing::info!("Saved store snapshot to: {}", path),
+            Err(e) => tracing::warn!("Could not save store snapshot: {}", e),
+        }
+    }
+    Ok(store)
+}
+
+/// Maps the store file at `SNOMED_MAPPED_STORE_PATH`, writing it from the
+/// release first if there is no usable one built from `source`.
+fn open_mapped_store(
+    files: &Rf2Files,
+    options: &LoadOptions,
+    source: &SnapshotSource,
+) -> Result<MappedStore, Box<dyn std::error::Error>> {
+    let path = std::env::var("SNOMED_MAPPED_STORE_PATH")
+        .map_err(|_| "the mapped backend needs SNOMED_MAPPED_STORE_PATH")?;
+    match MappedStore::open(&path, source) {
+        Ok(store) => {
+            tracing::info!("Mapped store from: {}", path);
+            return Ok(store);
+        }
+        Err(snomed_loader::Rf2Error::FileNotFound { .. }) => {}
+        Err(e) => tracing::warn!("Rewriting mapped store: {}", e),
+    }
+
+    MappedStore::write(&load_store(files, options, source)?, &path, source)?;
+    tracing::info!("Wrote mapped store to: {}", path);
+    Ok(MappedStore::open(&path, source)?)
+}
+
+/// Reads the store snapshot at `path`, if there is one built from `source`.
+fn read_snapshot(path: &str, source: &SnapshotSource) -> Option<SnomedStore> {
+    match SnomedStore::load_snapshot(path, source) {
+        Ok(store) => {
+            tracing::info!("Loaded store snapshot from: {}", path);
+            Some(store)
+        }
+        Err(snomed_loader::Rf2Error::FileNotFound { .. }) => None,
+        Err(e) => {
+            tracing::warn!("Ignoring store snapshot: {}", e);
+            None
+        }
+    }
+}
+
+/// Reads the load settings from the environment.
+///
+/// Returns the options and the settings they came from, as recorded in
+/// store snapshots.
+fn load_options() -> (LoadOptions, String) {
+    // Choose what to load: "full" (every row, MRCM), "minimal" or the default
+    let profile = std::env::var("SNOMED_LOAD_PROFILE").unwrap_or_default();
+    let options = match profile.as_str() {
+        "full" => LoadOptions::full_content(),
+        "minimal" => LoadOptions::minimal_runtime(),
+        _ => LoadOptions::default(),
+    };
+
+    // Refuse to start on malformed rows instead of skipping them
+    let strict = env_flag("SNOMED_STRICT_LOAD");
+    let history = env_flag("SNOMED_LOAD_HISTORY");
+    let settings = format!("profile={profile:?} strict={strict} history={history}");
+    (LoadOptions { strict, ..options }, settings)
+}
+
+/// Returns true if the environment variable is set to `1` or `true`.
+fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Parses the discovered RF2 release into a new store.
+fn load_release(
+    files: &Rf2Files,
+    options: &LoadOptions,
+) -> Result<SnomedStore, Box<dyn std::error::Error>> {
+    // Load data into store
+    let mut store = SnomedStore::new();
+
+    tracing::info!("Loading concepts...");
+    let report = store.load(files, options)?;
+    for error in &report.errors {
+        tracing::warn!("Skipped malformed row {}", error);
+    }
+
+    Ok(store)
+}
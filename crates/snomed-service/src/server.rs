@@ -1,43 +1,67 @@
 //! gRPC server implementation.
 
+use snomed_loader::{ChangeKind, ConceptChange, HistoryStore, TerminologyStore, Unsupported};
 use std::sync::Arc;
-use snomed_loader::SnomedStore;
 use tonic::{Request, Response, Status};
 
 use crate::proto::{
     concept_service_server::ConceptService,
+    map_service_server::MapService,
+    refset_service_server::RefsetService,
     search_service_server::SearchService,
-    Concept, Description, GetConceptRequest, GetConceptResponse,
+    ChangeType, Concept, ConceptHistoryEvent, Description, GetConceptRequest, GetConceptResponse,
+    GetConceptHistoryRequest, GetConceptHistoryResponse,
+    GetMapTargetsRequest, GetMapTargetsResponse,
     GetParentsRequest, GetParentsResponse, GetChildrenRequest, GetChildrenResponse,
-    IsDescendantOfRequest, IsDescendantOfResponse,
+    GetRefsetMembersRequest, GetRefsetMembersResponse,
+    GetRefsetsForComponentRequest, GetRefsetsForComponentResponse,
+    IsDescendantOfRequest, IsDescendantOfResponse, IsMemberRequest, IsMemberResponse,
+    MapTarget, ReverseMapRequest, ReverseMapResponse,
+    ResolveActiveRequest, ResolveActiveResponse,
     SearchRequest, SearchResponse,
 };
 
 /// SNOMED CT gRPC Server.
+///
+/// Queries go through [`TerminologyStore`], so any backend can serve them:
+/// a `SnomedStore`, a `CompactStore` or a `MappedStore`.
 #[derive(Clone)]
 pub struct SnomedServer {
-    store: Arc<SnomedStore>,
+    store: Arc<dyn TerminologyStore>,
+    history: Option<Arc<HistoryStore>>,
 }
 
 impl SnomedServer {
     /// Creates a new server with the given store.
-    pub fn new(store: SnomedStore) -> Self {
+    pub fn new(store: impl TerminologyStore + 'static) -> Self {
         Self {
             store: Arc::new(store),
+            history: None,
         }
     }
 
+    /// Attaches Full release history, enabling `GetConceptHistory`.
+    pub fn with_history(mut self, history: HistoryStore) -> Self {
+        self.history = Some(Arc::new(history));
+        self
+    }
+
     /// Returns a reference to the store.
-    pub fn store(&self) -> &SnomedStore {
-        &self.store
+    pub fn store(&self) -> &dyn TerminologyStore {
+        self.store.as_ref()
+    }
+
+    /// Returns a reference to the history store, if loaded.
+    pub fn history(&self) -> Option<&HistoryStore> {
+        self.history.as_deref()
     }
 
     /// Convert internal concept to proto Concept
     fn to_proto_concept(&self, id: snomed_types::SctId) -> Option<Concept> {
         let rf2_concept = self.store.get_concept(id)?;
-        let fsn = self.store.get_fsn(id)
-            .map(|d| d.term.clone())
-            .unwrap_or_default();
+        let fsn = self.store.get_fsn(id).map(|d| d.term).unwrap_or_default();
+        // Backends without reference sets leave the reason blank
+        let reason = self.store.get_inactivation_reason(id).ok().flatten();
 
         Some(Concept {
             id: rf2_concept.id,
@@ -46,9 +70,82 @@ impl SnomedServer {
             module_id: rf2_concept.module_id,
             definition_status_id: rf2_concept.definition_status_id,
             fsn,
+            inactivation_reason_id: reason.map(|r| r.to_id()).unwrap_or_default(),
+            inactivation_reason: reason.map(|r| r.name().to_string()).unwrap_or_default(),
         })
     }
 
+    /// Convert internal history change to proto ConceptHistoryEvent
+    fn to_proto_history_event(change: ConceptChange) -> ConceptHistoryEvent {
+        let mut event = ConceptHistoryEvent {
+            effective_time: change.effective_time,
+            component_id: change.component_id,
+            ..Default::default()
+        };
+
+        let change_type = match change.kind {
+            ChangeKind::ConceptCreated => ChangeType::ConceptCreated,
+            ChangeKind::ConceptActivated => ChangeType::ConceptActivated,
+            ChangeKind::ConceptInactivated => ChangeType::ConceptInactivated,
+            ChangeKind::DefinitionStatusChanged { from, to } => {
+                event.old_value = from.to_string();
+                event.new_value = to.to_string();
+                ChangeType::DefinitionStatusChanged
+            }
+            ChangeKind::ModuleChanged { from, to } => {
+                event.old_value = from.to_string();
+                event.new_value = to.to_string();
+                ChangeType::ModuleChanged
+            }
+            ChangeKind::DescriptionAdded { type_id, term } => {
+                event.type_id = type_id;
+                event.new_value = term;
+                ChangeType::DescriptionAdded
+            }
+            ChangeKind::DescriptionInactivated { type_id, term } => {
+                event.type_id = type_id;
+                event.old_value = term;
+                ChangeType::DescriptionInactivated
+            }
+            ChangeKind::FsnChanged { from, to } => {
+                event.old_value = from;
+                event.new_value = to;
+                ChangeType::FsnChanged
+            }
+            ChangeKind::ParentAdded { parent_id } => {
+                event.new_value = parent_id.to_string();
+                ChangeType::ParentAdded
+            }
+            ChangeKind::ParentRemoved { parent_id } => {
+                event.old_value = parent_id.to_string();
+                ChangeType::ParentRemoved
+            }
+            ChangeKind::AttributeAdded {
+                type_id,
+                destination_id,
+                group,
+            } => {
+                event.type_id = type_id;
+                event.new_value = destination_id.to_string();
+                event.relationship_group = group.into();
+                ChangeType::AttributeAdded
+            }
+            ChangeKind::AttributeRemoved {
+                type_id,
+                destination_id,
+                group,
+            } => {
+                event.type_id = type_id;
+                event.old_value = destination_id.to_string();
+                event.relationship_group = group.into();
+                ChangeType::AttributeRemoved
+            }
+        };
+
+        event.set_change_type(change_type);
+        event
+    }
+
     /// Convert internal description to proto Description
     fn to_proto_description(desc: &snomed_types::Rf2Description) -> Description {
         Description {
@@ -62,6 +159,11 @@ impl SnomedServer {
     }
 }
 
+/// Answers a lookup the store backend cannot serve with `UNIMPLEMENTED`.
+fn unimplemented(error: Unsupported) -> Status {
+    Status::unimplemented(error.to_string())
+}
+
 #[tonic::async_trait]
 impl ConceptService for SnomedServer {
     async fn get_concept(
@@ -72,9 +174,12 @@ impl ConceptService for SnomedServer {
 
         let concept = self.to_proto_concept(id);
 
-        let descriptions = self.store.get_descriptions(id)
-            .map(|descs| descs.iter().map(Self::to_proto_description).collect())
-            .unwrap_or_default();
+        let descriptions = self
+            .store
+            .get_descriptions(id)
+            .iter()
+            .map(Self::to_proto_description)
+            .collect();
 
         Ok(Response::new(GetConceptResponse {
             concept,
@@ -137,6 +242,51 @@ impl ConceptService for SnomedServer {
 
         Ok(Response::new(IsDescendantOfResponse { is_descendant: false }))
     }
+
+    async fn get_concept_history(
+        &self,
+        request: Request<GetConceptHistoryRequest>,
+    ) -> Result<Response<GetConceptHistoryResponse>, Status> {
+        let id = request.into_inner().id;
+
+        let history = self
+            .history
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("Concept history is not loaded"))?;
+
+        if !history.has_concept(id) {
+            return Err(Status::not_found(format!("Concept {} has no history", id)));
+        }
+
+        let events = history
+            .concept_timeline(id)
+            .into_iter()
+            .map(Self::to_proto_history_event)
+            .collect();
+
+        Ok(Response::new(GetConceptHistoryResponse { events }))
+    }
+
+    async fn resolve_active(
+        &self,
+        request: Request<ResolveActiveRequest>,
+    ) -> Result<Response<ResolveActiveResponse>, Status> {
+        let id = request.into_inner().id;
+
+        let is_active = self.store.get_concept(id).is_some_and(|c| c.active);
+        let concepts = self
+            .store
+            .resolve_active(id)
+            .map_err(unimplemented)?
+            .into_iter()
+            .filter_map(|cid| self.to_proto_concept(cid))
+            .collect();
+
+        Ok(Response::new(ResolveActiveResponse {
+            is_active,
+            concepts,
+        }))
+    }
 }
 
 #[tonic::async_trait]
@@ -160,17 +310,16 @@ impl SearchService for SnomedServer {
             }
 
             // Check if any description matches the query
-            if let Some(descriptions) = self.store.get_descriptions(concept.id) {
-                let matches = descriptions.iter().any(|d| {
-                    d.term.to_lowercase().contains(&query)
-                });
-
-                if matches {
-                    if let Some(proto_concept) = self.to_proto_concept(concept.id) {
-                        results.push(proto_concept);
-                        if results.len() >= limit {
-                            break;
-                        }
+            let descriptions = self.store.get_descriptions(concept.id);
+            let matches = descriptions
+                .iter()
+                .any(|d| d.term.to_lowercase().contains(&query));
+
+            if matches {
+                if let Some(proto_concept) = self.to_proto_concept(concept.id) {
+                    results.push(proto_concept);
+                    if results.len() >= limit {
+                        break;
                     }
                 }
             }
@@ -179,3 +328,182 @@ impl SearchService for SnomedServer {
         Ok(Response::new(SearchResponse { concepts: results }))
     }
 }
+
+#[tonic::async_trait]
+impl RefsetService for SnomedServer {
+    async fn is_member(
+        &self,
+        request: Request<IsMemberRequest>,
+    ) -> Result<Response<IsMemberResponse>, Status> {
+        let req = request.into_inner();
+
+        let is_member = self
+            .store
+            .is_member(req.refset_id, req.component_id)
+            .map_err(unimplemented)?;
+
+        Ok(Response::new(IsMemberResponse { is_member }))
+    }
+
+    async fn get_refset_members(
+        &self,
+        request: Request<GetRefsetMembersRequest>,
+    ) -> Result<Response<GetRefsetMembersResponse>, Status> {
+        let req = request.into_inner();
+        let offset = req.offset.max(0) as usize;
+        let limit = if req.limit > 0 {
+            req.limit as usize
+        } else {
+            usize::MAX
+        };
+
+        let members = self.store.members(req.refset_id).map_err(unimplemented)?;
+        let total = members.len() as u32;
+        let component_ids = members.into_iter().skip(offset).take(limit).collect();
+
+        Ok(Response::new(GetRefsetMembersResponse {
+            component_ids,
+            total,
+        }))
+    }
+
+    async fn get_refsets_for_component(
+        &self,
+        request: Request<GetRefsetsForComponentRequest>,
+    ) -> Result<Response<GetRefsetsForComponentResponse>, Status> {
+        let component_id = request.into_inner().component_id;
+
+        let refset_ids = self
+            .store
+            .refsets_for(component_id)
+            .map_err(unimplemented)?;
+
+        Ok(Response::new(GetRefsetsForComponentResponse { refset_ids }))
+    }
+}
+
+#[tonic::async_trait]
+impl MapService for SnomedServer {
+    async fn get_map_targets(
+        &self,
+        request: Request<GetMapTargetsRequest>,
+    ) -> Result<Response<GetMapTargetsResponse>, Status> {
+        let req = request.into_inner();
+
+        let targets = self
+            .store
+            .get_simple_map(req.concept_id)
+            .map_err(unimplemented)?
+            .iter()
+            .filter(|row| req.refset_id == 0 || row.refset_id == req.refset_id)
+            .map(|row| MapTarget {
+                refset_id: row.refset_id,
+                map_target: row.map_target.clone(),
+            })
+            .collect();
+
+        Ok(Response::new(GetMapTargetsResponse { targets }))
+    }
+
+    async fn reverse_map(
+        &self,
+        request: Request<ReverseMapRequest>,
+    ) -> Result<Response<ReverseMapResponse>, Status> {
+        let req = request.into_inner();
+        let refset_id = (req.refset_id != 0).then_some(req.refset_id);
+
+        let concepts = self
+            .store
+            .reverse_map(refset_id, &req.map_target)
+            .map_err(unimplemented)?
+            .into_iter()
+            .filter_map(|cid| self.to_proto_concept(cid))
+            .collect();
+
+        Ok(Response::new(ReverseMapResponse { concepts }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snomed_loader::{Rf2Config, SnomedStore};
+    use snomed_types::InactivationReason;
+
+    #[tokio::test]
+    async fn test_get_concept_reports_inactivation_reason() {
+        let dir = tempfile::tempdir().unwrap();
+        let concept_file = dir.path().join("sct2_Concept_Snapshot_INT_20250101.txt");
+        std::fs::write(
+            &concept_file,
+            "id\teffectiveTime\tactive\tmoduleId\tdefinitionStatusId\n\
+             100\t20020131\t1\t900000000000207008\t900000000000074008\n\
+             200\t20250101\t0\t900000000000207008\t900000000000074008\n",
+        )
+        .unwrap();
+        let indicator_file = dir
+            .path()
+            .join("der2_cRefset_AttributeValueSnapshot_INT_20250101.txt");
+        std::fs::write(
+            &indicator_file,
+            "id\teffectiveTime\tactive\tmoduleId\trefsetId\treferencedComponentId\tvalueId\n\
+             80000000-0000-0000-0000-000000000000\t20250101\t1\t900000000000207008\t\
+             900000000000489007\t200\t900000000000482003\n",
+        )
+        .unwrap();
+
+        // Inactive concepts only reach the server when the active-only filter is off
+        let config = Rf2Config {
+            active_only: false,
+            ..Rf2Config::default()
+        };
+        let mut store = SnomedStore::new();
+        store.load_concepts(&concept_file, config.clone()).unwrap();
+        store
+            .load_attribute_value_refset(&indicator_file, config)
+            .unwrap();
+        let server = SnomedServer::new(store);
+
+        let get = |id| server.get_concept(Request::new(GetConceptRequest { id }));
+        let inactive = get(200).await.unwrap().into_inner().concept.unwrap();
+        assert!(!inactive.active);
+        assert_eq!(
+            inactive.inactivation_reason_id,
+            InactivationReason::DUPLICATE_ID
+        );
+        assert_eq!(
+            inactive.inactivation_reason,
+            InactivationReason::Duplicate.name()
+        );
+
+        let active = get(100).await.unwrap().into_inner().concept.unwrap();
+        assert_eq!(active.inactivation_reason_id, 0);
+        assert!(active.inactivation_reason.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refset_rpcs_are_unimplemented_without_reference_sets() {
+        let mut store = SnomedStore::new();
+        store.insert_concepts([snomed_types::Rf2Concept {
+            id: 100,
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            definition_status_id: 900000000000074008,
+        }]);
+        let compact = snomed_loader::CompactStore::from_store(&store).unwrap();
+        let server = SnomedServer::new(compact);
+
+        let request = Request::new(IsMemberRequest {
+            refset_id: 446609009,
+            component_id: 100,
+        });
+        let status = server.is_member(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unimplemented);
+
+        // Concept lookups still work, without an inactivation reason
+        let request = Request::new(GetConceptRequest { id: 100 });
+        let concept = server.get_concept(request).await.unwrap().into_inner();
+        assert_eq!(concept.concept.unwrap().inactivation_reason_id, 0);
+    }
+}
@@ -261,6 +261,183 @@ impl ModifierType {
     }
 }
 
+/// Acceptability of a description in a language reference set.
+///
+/// Each synonym is either the preferred term or an acceptable alternative
+/// within a given language or dialect.
+///
+/// # Examples
+///
+/// ```
+/// use snomed_types::Acceptability;
+///
+/// let acceptability = Acceptability::from_id(900000000000548007);
+/// assert_eq!(acceptability, Some(Acceptability::Preferred));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Acceptability {
+    /// Preferred term in the language or dialect.
+    Preferred,
+    /// Acceptable alternative term.
+    Acceptable,
+}
+
+impl Acceptability {
+    /// SCTID for preferred acceptability.
+    pub const PREFERRED_ID: SctId = 900000000000548007;
+    /// SCTID for acceptable acceptability.
+    pub const ACCEPTABLE_ID: SctId = 900000000000549004;
+
+    /// Creates an Acceptability from its SCTID.
+    ///
+    /// Returns `None` if the ID doesn't match a known acceptability.
+    pub fn from_id(id: SctId) -> Option<Self> {
+        match id {
+            Self::PREFERRED_ID => Some(Self::Preferred),
+            Self::ACCEPTABLE_ID => Some(Self::Acceptable),
+            _ => None,
+        }
+    }
+
+    /// Returns the SCTID for this acceptability.
+    pub fn to_id(self) -> SctId {
+        match self {
+            Self::Preferred => Self::PREFERRED_ID,
+            Self::Acceptable => Self::ACCEPTABLE_ID,
+        }
+    }
+}
+
+/// Reason a component was inactivated.
+///
+/// Values of the concept and description inactivation indicator
+/// attribute value reference sets.
+///
+/// # Examples
+///
+/// ```
+/// use snomed_types::InactivationReason;
+///
+/// let reason = InactivationReason::from_id(900000000000482003);
+/// assert_eq!(reason, Some(InactivationReason::Duplicate));
+/// assert_eq!(reason.unwrap().name(), "Duplicate component");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InactivationReason {
+    /// The component duplicates another component.
+    Duplicate,
+    /// The component is outdated.
+    Outdated,
+    /// The component's meaning is ambiguous.
+    Ambiguous,
+    /// The component was created in error.
+    Erroneous,
+    /// The component has limited clinical value.
+    Limited,
+    /// The component was moved to another namespace or extension.
+    MovedElsewhere,
+    /// The component is about to be moved elsewhere.
+    PendingMove,
+    /// The description's concept is no longer current.
+    ConceptNonCurrent,
+    /// The component does not conform to editorial policy.
+    NonconformanceToEditorialPolicy,
+    /// The description is not semantically equivalent to its concept.
+    NotSemanticallyEquivalent,
+    /// The component was only needed for classification.
+    ClassificationDerived,
+    /// The meaning of the component is unknown.
+    MeaningUnknown,
+}
+
+impl InactivationReason {
+    /// SCTID for "Duplicate component".
+    pub const DUPLICATE_ID: SctId = 900000000000482003;
+    /// SCTID for "Outdated component".
+    pub const OUTDATED_ID: SctId = 900000000000483008;
+    /// SCTID for "Ambiguous component".
+    pub const AMBIGUOUS_ID: SctId = 900000000000484002;
+    /// SCTID for "Erroneous component".
+    pub const ERRONEOUS_ID: SctId = 900000000000485001;
+    /// SCTID for "Limited component".
+    pub const LIMITED_ID: SctId = 900000000000486000;
+    /// SCTID for "Component moved elsewhere".
+    pub const MOVED_ELSEWHERE_ID: SctId = 900000000000487009;
+    /// SCTID for "Pending move".
+    pub const PENDING_MOVE_ID: SctId = 900000000000492006;
+    /// SCTID for "Concept non-current".
+    pub const CONCEPT_NON_CURRENT_ID: SctId = 900000000000495008;
+    /// SCTID for "Nonconformance to editorial policy component".
+    pub const NONCONFORMANCE_TO_EDITORIAL_POLICY_ID: SctId = 723277005;
+    /// SCTID for "Not semantically equivalent component".
+    pub const NOT_SEMANTICALLY_EQUIVALENT_ID: SctId = 723278000;
+    /// SCTID for "Classification derived component".
+    pub const CLASSIFICATION_DERIVED_ID: SctId = 1186917008;
+    /// SCTID for "Meaning of component unknown".
+    pub const MEANING_UNKNOWN_ID: SctId = 1186919006;
+
+    /// Creates an InactivationReason from its SCTID.
+    ///
+    /// Returns `None` if the ID doesn't match a known inactivation reason.
+    pub fn from_id(id: SctId) -> Option<Self> {
+        match id {
+            Self::DUPLICATE_ID => Some(Self::Duplicate),
+            Self::OUTDATED_ID => Some(Self::Outdated),
+            Self::AMBIGUOUS_ID => Some(Self::Ambiguous),
+            Self::ERRONEOUS_ID => Some(Self::Erroneous),
+            Self::LIMITED_ID => Some(Self::Limited),
+            Self::MOVED_ELSEWHERE_ID => Some(Self::MovedElsewhere),
+            Self::PENDING_MOVE_ID => Some(Self::PendingMove),
+            Self::CONCEPT_NON_CURRENT_ID => Some(Self::ConceptNonCurrent),
+            Self::NONCONFORMANCE_TO_EDITORIAL_POLICY_ID => {
+                Some(Self::NonconformanceToEditorialPolicy)
+            }
+            Self::NOT_SEMANTICALLY_EQUIVALENT_ID => Some(Self::NotSemanticallyEquivalent),
+            Self::CLASSIFICATION_DERIVED_ID => Some(Self::ClassificationDerived),
+            Self::MEANING_UNKNOWN_ID => Some(Self::MeaningUnknown),
+            _ => None,
+        }
+    }
+
+    /// Returns the SCTID for this inactivation reason.
+    pub fn to_id(self) -> SctId {
+        match self {
+            Self::Duplicate => Self::DUPLICATE_ID,
+            Self::Outdated => Self::OUTDATED_ID,
+            Self::Ambiguous => Self::AMBIGUOUS_ID,
+            Self::Erroneous => Self::ERRONEOUS_ID,
+            Self::Limited => Self::LIMITED_ID,
+            Self::MovedElsewhere => Self::MOVED_ELSEWHERE_ID,
+            Self::PendingMove => Self::PENDING_MOVE_ID,
+            Self::ConceptNonCurrent => Self::CONCEPT_NON_CURRENT_ID,
+            Self::NonconformanceToEditorialPolicy => Self::NONCONFORMANCE_TO_EDITORIAL_POLICY_ID,
+            Self::NotSemanticallyEquivalent => Self::NOT_SEMANTICALLY_EQUIVALENT_ID,
+            Self::ClassificationDerived => Self::CLASSIFICATION_DERIVED_ID,
+            Self::MeaningUnknown => Self::MEANING_UNKNOWN_ID,
+        }
+    }
+
+    /// Returns the preferred term of the reason's value concept.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Duplicate => "Duplicate component",
+            Self::Outdated => "Outdated component",
+            Self::Ambiguous => "Ambiguous component",
+            Self::Erroneous => "Erroneous component",
+            Self::Limited => "Limited component",
+            Self::MovedElsewhere => "Component moved elsewhere",
+            Self::PendingMove => "Pending move",
+            Self::ConceptNonCurrent => "Concept non-current",
+            Self::NonconformanceToEditorialPolicy => "Nonconformance to editorial policy component",
+            Self::NotSemanticallyEquivalent => "Not semantically equivalent component",
+            Self::ClassificationDerived => "Classification derived component",
+            Self::MeaningUnknown => "Meaning of component unknown",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,4 +504,31 @@ mod tests {
             Some(ModifierType::Universal)
         );
     }
+
+    #[test]
+    fn test_acceptability_conversion() {
+        assert_eq!(
+            Acceptability::from_id(900000000000548007),
+            Some(Acceptability::Preferred)
+        );
+        assert_eq!(
+            Acceptability::from_id(900000000000549004),
+            Some(Acceptability::Acceptable)
+        );
+        assert_eq!(Acceptability::Preferred.to_id(), 900000000000548007);
+    }
+
+    #[test]
+    fn test_inactivation_reason_conversion() {
+        assert_eq!(
+            InactivationReason::from_id(900000000000484002),
+            Some(InactivationReason::Ambiguous)
+        );
+        assert_eq!(
+            InactivationReason::from_id(900000000000495008),
+            Some(InactivationReason::ConceptNonCurrent)
+        );
+        assert_eq!(InactivationReason::from_id(12345), None);
+        assert_eq!(InactivationReason::Erroneous.to_id(), 900000000000485001);
+    }
 }
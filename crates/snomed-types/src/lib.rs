@@ -49,6 +49,7 @@ mod concept;
 mod description;
 mod enums;
 pub mod mrcm;
+pub mod refset;
 mod relationship;
 mod sctid;
 pub mod well_known;
@@ -57,12 +58,18 @@ pub mod well_known;
 pub use concept::Rf2Concept;
 pub use description::Rf2Description;
 pub use enums::{
-    CaseSignificance, CharacteristicType, DefinitionStatus, DescriptionType, ModifierType,
+    Acceptability, CaseSignificance, CharacteristicType, DefinitionStatus, DescriptionType,
+    InactivationReason, ModifierType,
 };
 pub use mrcm::{
     Cardinality, CardinalityParseError, MrcmAttributeDomain, MrcmAttributeRange, MrcmDomain,
 };
-pub use relationship::Rf2Relationship;
+pub use refset::{
+    AssociationRefsetMember, AttributeValueRefsetMember, ExtendedMapRefsetMember,
+    LanguageRefsetMember, OwlExpressionRefsetMember, RefsetFieldType, RefsetMember, RefsetValue,
+    SimpleMapRefsetMember,
+};
+pub use relationship::{ConcreteValue, Rf2ConcreteRelationship, Rf2Relationship};
 pub use sctid::SctId;
 
 #[cfg(test)]
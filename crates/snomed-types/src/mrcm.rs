@@ -199,7 +199,7 @@ impl std::fmt::Display for Cardinality {
 /// For example, the "Clinical finding" domain allows finding site, severity, etc.
 ///
 /// # RF2 File
-/// Pattern: `der2_cRefset_MRCMDomainSnapshot_*.txt`
+/// Pattern: `der2_sssssssRefset_MRCMDomainSnapshot_*.txt`
 ///
 /// # Fields
 /// The `domain_constraint` and `proximal_primitive_constraint` fields contain ECL
@@ -240,7 +240,7 @@ pub struct MrcmDomain {
 /// Defines which attributes are valid in which domains and their cardinality.
 ///
 /// # RF2 File
-/// Pattern: `der2_cRefset_MRCMAttributeDomainSnapshot_*.txt`
+/// Pattern: `der2_cissccRefset_MRCMAttributeDomainSnapshot_*.txt`
 ///
 /// # Example
 /// An attribute domain record might specify that "Finding site" (363698007)
@@ -292,7 +292,7 @@ impl MrcmAttributeDomain {
 /// Defines valid value ranges for attributes using ECL expressions.
 ///
 /// # RF2 File
-/// Pattern: `der2_cRefset_MRCMAttributeRangeSnapshot_*.txt`
+/// Pattern: `der2_ssccRefset_MRCMAttributeRangeSnapshot_*.txt`
 ///
 /// # Example
 /// An attribute range record might specify that "Finding site" (363698007)
@@ -0,0 +1,386 @@
+//! Reference set member type definitions.
+//!
+//! Reference sets (refsets) attach extra information to components. Every
+//! member row shares the same leading columns (`id`, `effectiveTime`,
+//! `active`, `moduleId`, `refsetId`, `referencedComponentId`) followed by
+//! pattern-specific columns.
+//!
+//! # Examples
+//!
+//! ```
+//! use snomed_types::refset::LanguageRefsetMember;
+//! use snomed_types::{well_known, Acceptability};
+//!
+//! let member = LanguageRefsetMember {
+//!     id: "a1b2c3d4-0000-0000-0000-000000000000".to_string(),
+//!     effective_time: 20020131,
+//!     active: true,
+//!     module_id: well_known::SNOMED_CT_CORE_MODULE,
+//!     refset_id: well_known::US_ENGLISH_LANGUAGE_REFSET,
+//!     referenced_component_id: 1234567011,
+//!     acceptability_id: Acceptability::PREFERRED_ID,
+//! };
+//!
+//! assert!(member.is_preferred());
+//! ```
+
+use crate::{Acceptability, InactivationReason, SctId};
+
+/// Language reference set member.
+///
+/// States whether a description is preferred or acceptable in a language
+/// or dialect (e.g. US English, GB English).
+///
+/// # RF2 File
+/// Pattern: `der2_cRefset_LanguageSnapshot-*_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanguageRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The language reference set (e.g. US English 900000000000509007).
+    pub refset_id: SctId,
+    /// The description this member applies to.
+    pub referenced_component_id: SctId,
+    /// Preferred (900000000000548007) or acceptable (900000000000549004).
+    pub acceptability_id: SctId,
+}
+
+impl LanguageRefsetMember {
+    /// Returns the acceptability as an enum, if it is a known value.
+    pub fn acceptability(&self) -> Option<Acceptability> {
+        Acceptability::from_id(self.acceptability_id)
+    }
+
+    /// Returns true if the description is preferred in this language refset.
+    pub fn is_preferred(&self) -> bool {
+        self.acceptability_id == Acceptability::PREFERRED_ID
+    }
+}
+
+/// Association reference set member.
+///
+/// Links a (usually inactive) component to a target component, e.g. the
+/// historical SAME AS and REPLACED BY associations.
+///
+/// # RF2 File
+/// Pattern: `der2_cRefset_AssociationSnapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssociationRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The association reference set (e.g. REPLACED BY 900000000000526001).
+    pub refset_id: SctId,
+    /// The source component of the association.
+    pub referenced_component_id: SctId,
+    /// The target component of the association.
+    pub target_component_id: SctId,
+}
+
+/// Attribute value reference set member.
+///
+/// Attaches a coded value to a component, e.g. the reason a concept or
+/// description was inactivated.
+///
+/// # RF2 File
+/// Pattern: `der2_cRefset_AttributeValueSnapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeValueRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The attribute value reference set (e.g. concept inactivation indicator 900000000000489007).
+    pub refset_id: SctId,
+    /// The component the value applies to.
+    pub referenced_component_id: SctId,
+    /// The attribute value concept.
+    pub value_id: SctId,
+}
+
+impl AttributeValueRefsetMember {
+    /// Returns the value as an inactivation reason, if it is a known one.
+    pub fn inactivation_reason(&self) -> Option<InactivationReason> {
+        InactivationReason::from_id(self.value_id)
+    }
+}
+
+/// Simple map reference set member.
+///
+/// Maps a concept to a code in another code system (e.g. CTV3, ICD-O) with
+/// no rules or grouping.
+///
+/// # RF2 File
+/// Pattern: `der2_sRefset_SimpleMapSnapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleMapRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The map reference set (e.g. CTV3 900000000000497000).
+    pub refset_id: SctId,
+    /// The SNOMED CT component being mapped.
+    pub referenced_component_id: SctId,
+    /// Code in the target code system.
+    pub map_target: String,
+}
+
+/// Extended map reference set member.
+///
+/// One rule-based candidate target for mapping a concept to another code
+/// system, e.g. SNOMED CT to ICD-10. Rows are grouped by `map_group`; within
+/// a group, rules are tried in `map_priority` order and the first that holds
+/// for the patient gives the target.
+///
+/// # RF2 File
+/// Pattern: `der2_iisssccRefset_ExtendedMapSnapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedMapRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The map reference set (e.g. ICD-10 447562003).
+    pub refset_id: SctId,
+    /// The SNOMED CT concept being mapped.
+    pub referenced_component_id: SctId,
+    /// Map group; each group yields at most one target.
+    pub map_group: u32,
+    /// Order in which rules are tried within the group.
+    pub map_priority: u32,
+    /// Rule deciding whether this row applies (e.g. `IFA 248153007 | Male (finding) |`).
+    pub map_rule: String,
+    /// Human-readable advice (e.g. `ALWAYS E11.9`).
+    pub map_advice: String,
+    /// Target code; empty when the group has no target for this rule.
+    pub map_target: String,
+    /// Correlation between source and target.
+    pub correlation_id: SctId,
+    /// Map category (e.g. properly classified, context dependent).
+    pub map_category_id: SctId,
+}
+
+impl ExtendedMapRefsetMember {
+    /// Returns the target code, or `None` if this row maps to no target.
+    pub fn target(&self) -> Option<&str> {
+        let target = self.map_target.trim();
+        (!target.is_empty()).then_some(target)
+    }
+}
+
+/// OWL expression reference set member.
+///
+/// Holds one OWL axiom (or ontology declaration) about the referenced
+/// component, in OWL functional syntax, e.g.
+/// `SubClassOf(:73211009 :362969004)`.
+///
+/// # RF2 File
+/// Pattern: `sct2_sRefset_OWLExpressionSnapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwlExpressionRefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The OWL reference set (axiom 733073007 or ontology 762103008).
+    pub refset_id: SctId,
+    /// The concept the axiom is about.
+    pub referenced_component_id: SctId,
+    /// The axiom in OWL functional syntax.
+    pub owl_expression: String,
+}
+
+/// Type of an additional reference set column, as encoded in the file name.
+///
+/// RF2 refset file names start with one letter per additional column, e.g.
+/// `der2_cciRefset_RefsetDescriptorSnapshot` has two component columns
+/// followed by an integer column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefsetFieldType {
+    /// `c` - a component identifier (SCTID).
+    Component,
+    /// `i` - a signed integer.
+    Integer,
+    /// `s` - a string.
+    String,
+}
+
+impl RefsetFieldType {
+    /// Creates a field type from its file name letter (`c`, `i` or `s`).
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'c' => Some(Self::Component),
+            'i' => Some(Self::Integer),
+            's' => Some(Self::String),
+            _ => None,
+        }
+    }
+
+    /// Returns the file name letter for this field type.
+    pub fn code(self) -> char {
+        match self {
+            Self::Component => 'c',
+            Self::Integer => 'i',
+            Self::String => 's',
+        }
+    }
+}
+
+/// A typed value from an additional reference set column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefsetValue {
+    /// A component identifier.
+    Component(SctId),
+    /// A signed integer.
+    Integer(i64),
+    /// A string.
+    String(String),
+}
+
+impl RefsetValue {
+    /// Returns the field type of this value.
+    pub fn field_type(&self) -> RefsetFieldType {
+        match self {
+            Self::Component(_) => RefsetFieldType::Component,
+            Self::Integer(_) => RefsetFieldType::Integer,
+            Self::String(_) => RefsetFieldType::String,
+        }
+    }
+
+    /// Returns the component identifier, if this is a component value.
+    pub fn as_component(&self) -> Option<SctId> {
+        match self {
+            Self::Component(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if this is an integer value.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if this is a string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Member of any reference set, with its additional columns as typed values.
+///
+/// Used for reference sets that have no dedicated member type. The meaning
+/// of each value is given by the refset's entry in the reference set
+/// descriptor (see [`well_known::REFSET_DESCRIPTOR_REFSET`](crate::well_known::REFSET_DESCRIPTOR_REFSET)).
+///
+/// # RF2 File
+/// Pattern: `der2_<columns>Refset_<Name>Snapshot_*.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefsetMember {
+    /// Unique row identifier (UUID in RF2, stored as string).
+    pub id: String,
+    /// Effective time (YYYYMMDD format).
+    pub effective_time: u32,
+    /// Whether this member is active.
+    pub active: bool,
+    /// Module that owns this member.
+    pub module_id: SctId,
+    /// The reference set this member belongs to.
+    pub refset_id: SctId,
+    /// The component this member refers to.
+    pub referenced_component_id: SctId,
+    /// Values of the additional columns, in file order.
+    pub values: Vec<RefsetValue>,
+}
+
+impl RefsetMember {
+    /// Gets the value of the additional column at `index` (0 = first column after referencedComponentId).
+    pub fn value(&self, index: usize) -> Option<&RefsetValue> {
+        self.values.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_member_acceptability() {
+        let member = LanguageRefsetMember {
+            id: "a1b2c3d4-0000-0000-0000-000000000000".to_string(),
+            effective_time: 20020131,
+            active: true,
+            module_id: 900000000000207008,
+            refset_id: 900000000000508004,
+            referenced_component_id: 1234567011,
+            acceptability_id: Acceptability::ACCEPTABLE_ID,
+        };
+
+        assert_eq!(member.acceptability(), Some(Acceptability::Acceptable));
+        assert!(!member.is_preferred());
+    }
+
+    #[test]
+    fn test_refset_field_types() {
+        let types: Vec<_> = "cis"
+            .chars()
+            .filter_map(RefsetFieldType::from_code)
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                RefsetFieldType::Component,
+                RefsetFieldType::Integer,
+                RefsetFieldType::String
+            ]
+        );
+        assert_eq!(RefsetFieldType::from_code('x'), None);
+
+        let value = RefsetValue::Component(900000000000461009);
+        assert_eq!(value.field_type().code(), 'c');
+        assert_eq!(value.as_component(), Some(900000000000461009));
+        assert_eq!(value.as_str(), None);
+    }
+}
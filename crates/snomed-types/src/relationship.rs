@@ -1,7 +1,8 @@
 //! SNOMED CT Relationship type.
 //!
 //! This module provides the `Rf2Relationship` struct representing a relationship
-//! from an RF2 Relationship file.
+//! from an RF2 Relationship file, and `Rf2ConcreteRelationship` for relationships
+//! whose target is a concrete value (e.g. a drug strength) rather than a concept.
 
 use crate::{CharacteristicType, ModifierType, SctId};
 
@@ -95,6 +96,162 @@ impl Rf2Relationship {
     }
 }
 
+/// A concrete value from the RF2 RelationshipConcreteValues file.
+///
+/// RF2 encodes numbers with a leading `#` (`#500`, `#0.5`) and strings in
+/// double quotes, with embedded quotes doubled (`"say ""hi"""`).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConcreteValue {
+    /// An integer, e.g. `#2` (count of base of active ingredient).
+    Integer(i64),
+    /// A decimal, e.g. `#0.5` (strength numerator value).
+    Decimal(f64),
+    /// A string, e.g. `"tablet"`.
+    String(String),
+}
+
+impl ConcreteValue {
+    /// Parses a value in RF2 notation.
+    ///
+    /// Returns `None` if the value is neither `#<number>` nor a quoted string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use snomed_types::ConcreteValue;
+    ///
+    /// assert_eq!(ConcreteValue::parse("#500"), Some(ConcreteValue::Integer(500)));
+    /// assert_eq!(ConcreteValue::parse("#0.5"), Some(ConcreteValue::Decimal(0.5)));
+    /// assert_eq!(
+    ///     ConcreteValue::parse("\"tablet\""),
+    ///     Some(ConcreteValue::String("tablet".to_string()))
+    /// );
+    /// assert_eq!(ConcreteValue::parse("500"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(number) = value.strip_prefix('#') {
+            if let Ok(integer) = number.parse::<i64>() {
+                return Some(Self::Integer(integer));
+            }
+            return number
+                .parse::<f64>()
+                .ok()
+                .filter(|d| d.is_finite())
+                .map(Self::Decimal);
+        }
+
+        let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+        Some(Self::String(inner.replace("\"\"", "\"")))
+    }
+
+    /// Returns the value as a number, if it is an integer or decimal.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Integer(i) => Some(*i as f64),
+            Self::Decimal(d) => Some(*d),
+            Self::String(_) => None,
+        }
+    }
+
+    /// Returns the value as a string, if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ConcreteValue {
+    /// Formats the value in RF2 notation.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(i) => write!(f, "#{}", i),
+            Self::Decimal(d) => write!(f, "#{}", d),
+            Self::String(s) => write!(f, "\"{}\"", s.replace('"', "\"\"")),
+        }
+    }
+}
+
+/// A SNOMED CT relationship from the RF2 RelationshipConcreteValues file.
+///
+/// Represents a row from `sct2_RelationshipConcreteValues_*.txt`. It has the
+/// same columns as [`Rf2Relationship`], except that `destinationId` is
+/// replaced by a concrete `value`.
+///
+/// # Examples
+///
+/// ```
+/// use snomed_types::{ConcreteValue, Rf2ConcreteRelationship};
+///
+/// let strength = Rf2ConcreteRelationship {
+///     id: 3552454029,
+///     effective_time: 20210731,
+///     active: true,
+///     module_id: 900000000000207008,
+///     source_id: 322236009,       // Paracetamol 500 mg oral tablet
+///     value: ConcreteValue::Integer(500),
+///     relationship_group: 1,
+///     type_id: 1142135004,        // Has presentation strength numerator value
+///     characteristic_type_id: 900000000000011006, // Inferred
+///     modifier_id: 900000000000451002, // Existential
+/// };
+///
+/// assert_eq!(strength.value.as_f64(), Some(500.0));
+/// assert!(strength.is_inferred());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rf2ConcreteRelationship {
+    /// Unique identifier for this relationship (SCTID).
+    pub id: SctId,
+    /// Effective date in YYYYMMDD format.
+    pub effective_time: u32,
+    /// Whether this relationship is active.
+    pub active: bool,
+    /// The module containing this relationship.
+    pub module_id: SctId,
+    /// Source concept (subject).
+    pub source_id: SctId,
+    /// Concrete value (object).
+    pub value: ConcreteValue,
+    /// Role group number (0 = ungrouped).
+    pub relationship_group: u16,
+    /// Relationship type (e.g., Has presentation strength numerator value).
+    pub type_id: SctId,
+    /// Whether this is stated or inferred.
+    pub characteristic_type_id: SctId,
+    /// Modifier (existential or universal).
+    pub modifier_id: SctId,
+}
+
+impl Rf2ConcreteRelationship {
+    /// Returns the characteristic type enum value.
+    ///
+    /// Returns `None` if the characteristic type ID is not recognized.
+    pub fn characteristic_type(&self) -> Option<CharacteristicType> {
+        CharacteristicType::from_id(self.characteristic_type_id)
+    }
+
+    /// Returns true if this is a stated relationship.
+    pub fn is_stated(&self) -> bool {
+        self.characteristic_type_id == CharacteristicType::STATED_ID
+    }
+
+    /// Returns true if this is an inferred relationship.
+    pub fn is_inferred(&self) -> bool {
+        self.characteristic_type_id == CharacteristicType::INFERRED_ID
+    }
+
+    /// Returns the modifier type enum value.
+    ///
+    /// Returns `None` if the modifier ID is not recognized.
+    pub fn modifier_type(&self) -> Option<ModifierType> {
+        ModifierType::from_id(self.modifier_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +299,19 @@ mod tests {
         let rel = make_relationship(363698007, CharacteristicType::INFERRED_ID);
         assert!(!rel.is_is_a());
     }
+
+    #[test]
+    fn test_concrete_value_round_trip() {
+        for raw in ["#2", "#0.5", "#-3", "\"tablet\"", "\"say \"\"hi\"\"\""] {
+            let value = ConcreteValue::parse(raw).unwrap();
+            assert_eq!(value.to_string(), raw);
+        }
+        assert_eq!(
+            ConcreteValue::parse("\"say \"\"hi\"\"\"").unwrap().as_str(),
+            Some("say \"hi\"")
+        );
+        assert_eq!(ConcreteValue::parse("#abc"), None);
+        assert_eq!(ConcreteValue::parse("\"unterminated"), None);
+        assert_eq!(ConcreteValue::parse("#2").unwrap().as_f64(), Some(2.0));
+    }
 }
@@ -136,6 +136,11 @@ pub const INTERPRETS: SctId = 363714003;
 /// Indicates the interpretation of an observation.
 pub const HAS_INTERPRETATION: SctId = 363713009;
 
+/// Role group attribute - 609096000.
+///
+/// Groups attributes in OWL axioms, e.g. `ObjectSomeValuesFrom(:609096000 ...)`.
+pub const ROLE_GROUP: SctId = 609096000;
+
 // =============================================================================
 // Modules
 // =============================================================================
@@ -225,6 +230,114 @@ pub const ALL_POSTCOORDINATED_CONTENT: SctId = 723595009;
 /// Content type for new precoordinated content.
 pub const ALL_NEW_PRECOORDINATED_CONTENT: SctId = 723593002;
 
+// =============================================================================
+// Reference Set Metadata
+// =============================================================================
+
+/// Reference set descriptor reference set - 900000000000456007.
+///
+/// Describes the additional columns (name, type and order) of every reference set.
+pub const REFSET_DESCRIPTOR_REFSET: SctId = 900000000000456007;
+
+/// OWL axiom reference set - 733073007.
+///
+/// Holds the authoritative stated definitions of concepts as OWL axioms.
+pub const OWL_AXIOM_REFSET: SctId = 733073007;
+
+/// OWL ontology reference set - 762103008.
+///
+/// Holds ontology-level declarations (prefixes, ontology IRI).
+pub const OWL_ONTOLOGY_REFSET: SctId = 762103008;
+
+// =============================================================================
+// Language Reference Sets
+// =============================================================================
+
+/// US English language reference set - 900000000000509007.
+pub const US_ENGLISH_LANGUAGE_REFSET: SctId = 900000000000509007;
+
+/// GB English language reference set - 900000000000508004.
+pub const GB_ENGLISH_LANGUAGE_REFSET: SctId = 900000000000508004;
+
+// =============================================================================
+// Historical Association Reference Sets
+// =============================================================================
+
+/// SAME AS association reference set - 900000000000527005.
+///
+/// Targets a concept with exactly the same meaning (duplicate inactivation).
+pub const SAME_AS_REFSET: SctId = 900000000000527005;
+
+/// REPLACED BY association reference set - 900000000000526001.
+///
+/// Targets the concept that replaces an inactivated component.
+pub const REPLACED_BY_REFSET: SctId = 900000000000526001;
+
+/// POSSIBLY EQUIVALENT TO association reference set - 900000000000523009.
+///
+/// Targets one or more concepts that may have the intended meaning (ambiguous inactivation).
+pub const POSSIBLY_EQUIVALENT_TO_REFSET: SctId = 900000000000523009;
+
+/// POSSIBLY REPLACED BY association reference set - 1186921001.
+pub const POSSIBLY_REPLACED_BY_REFSET: SctId = 1186921001;
+
+/// PARTIALLY EQUIVALENT TO association reference set - 1186924009.
+pub const PARTIALLY_EQUIVALENT_TO_REFSET: SctId = 1186924009;
+
+/// WAS A association reference set - 900000000000528000.
+pub const WAS_A_REFSET: SctId = 900000000000528000;
+
+/// MOVED TO association reference set - 900000000000524003.
+pub const MOVED_TO_REFSET: SctId = 900000000000524003;
+
+/// MOVED FROM association reference set - 900000000000525002.
+pub const MOVED_FROM_REFSET: SctId = 900000000000525002;
+
+/// ALTERNATIVE association reference set - 900000000000530003.
+pub const ALTERNATIVE_REFSET: SctId = 900000000000530003;
+
+/// REFERS TO concept association reference set - 900000000000531004.
+pub const REFERS_TO_REFSET: SctId = 900000000000531004;
+
+// =============================================================================
+// Map Reference Sets
+// =============================================================================
+
+/// SNOMED CT to ICD-10 extended map reference set - 447562003.
+pub const ICD10_EXTENDED_MAP_REFSET: SctId = 447562003;
+
+/// CTV3 simple map reference set - 900000000000497000.
+pub const CTV3_SIMPLE_MAP_REFSET: SctId = 900000000000497000;
+
+/// ICD-O simple map reference set - 446608001.
+pub const ICDO_SIMPLE_MAP_REFSET: SctId = 446608001;
+
+/// Male (finding) - 248153007, used in map rules (`IFA 248153007 | Male |`).
+pub const MALE: SctId = 248153007;
+
+/// Female (finding) - 248152002, used in map rules.
+pub const FEMALE: SctId = 248152002;
+
+/// Age at onset of clinical finding (observable entity) - 445518008, used in map rules.
+pub const AGE_AT_ONSET: SctId = 445518008;
+
+/// Current chronological age (observable entity) - 424144002, used in map rules.
+pub const CURRENT_CHRONOLOGICAL_AGE: SctId = 424144002;
+
+// =============================================================================
+// Inactivation Indicator Reference Sets
+// =============================================================================
+
+/// Concept inactivation indicator attribute value reference set - 900000000000489007.
+///
+/// Records why a concept was inactivated (duplicate, ambiguous, erroneous, ...).
+pub const CONCEPT_INACTIVATION_INDICATOR_REFSET: SctId = 900000000000489007;
+
+/// Description inactivation indicator attribute value reference set - 900000000000490003.
+///
+/// Records why a description was inactivated, or that its concept is no longer current.
+pub const DESCRIPTION_INACTIVATION_INDICATOR_REFSET: SctId = 900000000000490003;
+
 #[cfg(test)]
 mod tests {
     use super::*;